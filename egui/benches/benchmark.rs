@@ -43,6 +43,36 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
         // let _ = ctx.end_frame(); // skip, because tessellating all that text is slow
     }
+
+    {
+        use egui::{
+            paint::{tessellator, FontDefinitions, Fonts, PaintCmd},
+            pos2, Rect, Srgba,
+        };
+
+        let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+        let clip_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(1280.0, 1024.0));
+        let mut commands = vec![];
+        for i in 0..10_000 {
+            let pos = pos2((i % 128) as f32 * 10.0, (i / 128) as f32 * 10.0);
+            let color = Srgba::gray((i % 256) as u8);
+            commands.push((clip_rect, PaintCmd::circle_filled(pos, 4.0, color)));
+            commands.push((
+                clip_rect,
+                PaintCmd::line_segment([pos, pos + egui::vec2(8.0, 3.0)], (1.5, color)),
+            ));
+        }
+        let options = Default::default();
+
+        c.bench_function("tessellate_serial", |b| {
+            b.iter(|| tessellator::tessellate_paint_commands(commands.clone(), options, &fonts))
+        });
+
+        #[cfg(feature = "rayon")]
+        c.bench_function("tessellate_parallel", |b| {
+            b.iter(|| tessellator::tessellate_paint_commands_par(commands.clone(), options, &fonts))
+        });
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
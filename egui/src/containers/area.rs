@@ -205,13 +205,30 @@ impl Prepared {
             }
         }
 
-        // Constrain to screen:
+        // Constrain to screen, and keep windows out of the panels:
+        let screen = ctx.rect();
         let margin = 32.0;
-        state.pos = state.pos.max(pos2(margin - state.size.x, 0.0));
-        state.pos = state.pos.min(pos2(
-            ctx.input().screen_size.x - margin,
-            ctx.input().screen_size.y - margin,
-        ));
+        let mut min_pos = pos2(screen.left() + margin - state.size.x, screen.top());
+        let mut max_pos = screen.max - vec2(margin, margin);
+        if layer.order == Order::Middle {
+            // Only the edges the panels have taken:
+            let available = ctx.available_rect();
+            if available.left() > screen.left() {
+                min_pos.x = available.left();
+            }
+            if available.top() > screen.top() {
+                min_pos.y = available.top();
+            }
+            if available.right() < screen.right() {
+                max_pos.x = max_pos.x.min(available.right() - state.size.x);
+            }
+            if available.bottom() < screen.bottom() {
+                max_pos.y = max_pos.y.min(available.bottom() - state.size.y);
+            }
+        }
+        state.pos = state.pos.min(max_pos);
+        // The top left corner wins if there isn't room for both:
+        state.pos = state.pos.max(min_pos);
         state.pos = ctx.round_pos_to_pixels(state.pos);
 
         if move_response.active
@@ -245,9 +262,10 @@ fn automatic_area_position(ctx: &Context) -> Pos2 {
         .collect();
     existing.sort_by_key(|r| r.left().round() as i32);
 
-    let left = 16.0;
-    let top = 32.0; // allow existence of menu bar. TODO: get from ui.available()
+    let bounds = ctx.available_rect(); // Don't put windows on top of panels.
     let spacing = 16.0;
+    let left = bounds.left() + spacing;
+    let top = bounds.top() + spacing;
 
     if existing.is_empty() {
         return pos2(left, top);
@@ -281,14 +299,14 @@ fn automatic_area_position(ctx: &Context) -> Pos2 {
 
     // Find first column with some available space at the bottom of it:
     for col_bb in &column_bbs {
-        if col_bb.bottom() < ctx.input().screen_size.y * 0.5 {
+        if col_bb.bottom() < bounds.center().y {
             return pos2(col_bb.left(), col_bb.bottom() + spacing);
         }
     }
 
     // Maybe we can fit a new column?
     let rightmost = column_bbs.last().unwrap().right();
-    if rightmost < ctx.input().screen_size.x - 200.0 {
+    if rightmost < bounds.right() - 200.0 {
         return pos2(rightmost + spacing, top);
     }
 
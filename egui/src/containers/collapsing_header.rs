@@ -237,8 +237,11 @@ impl CollapsingHeader {
             bg_index,
             PaintCmd::Rect {
                 rect: header_response.rect,
-                corner_radius: ui.style().interact(&header_response).corner_radius,
-                fill: ui.style().interact(&header_response).bg_fill,
+                rounding: ui.style().interact(&header_response).rounding,
+                fill: ui
+                    .style()
+                    .interact(&header_response)
+                    .bg_fill_in(header_response.rect),
                 stroke: Default::default(),
             },
         );
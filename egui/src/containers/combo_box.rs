@@ -100,8 +100,8 @@ fn button_frame(
         where_to_put_background,
         PaintCmd::Rect {
             rect: outer_rect,
-            corner_radius: visuals.corner_radius,
-            fill: visuals.bg_fill,
+            rounding: visuals.rounding,
+            fill: visuals.bg_fill_in(outer_rect),
             stroke: visuals.bg_stroke,
         },
     );
@@ -0,0 +1,548 @@
+//! A dock area lays out tabs in groups, and the groups in resizable splits.
+//!
+//! Drag a tab onto the middle of a group to add it to that group,
+//! or onto one of its sides to split the group in two.
+//! Drag the space between two groups to change how the space is shared.
+//! The layout is remembered in [`Memory`].
+
+use std::hash::Hash;
+
+use super::splitter::SplitHandle;
+use crate::{
+    paint::{Rgba, Srgba, Stroke},
+    *,
+};
+
+/// Neither side of a split can be made smaller than this.
+const MIN_SPLIT_SIZE: f32 = 32.0;
+
+/// A group of tabs, or a split of the space between two nodes.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) enum Node {
+    /// Only the active tab of a group is shown.
+    Tabs { tabs: Vec<String>, active: usize },
+    /// `first` is left of `second` if `horizontal`, else above it.
+    Split {
+        horizontal: bool,
+        /// How much of the space `first` gets, in 0-1.
+        fraction: f32,
+        first: Box<Node>,
+        second: Box<Node>,
+    },
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Node::Tabs {
+            tabs: vec![],
+            active: 0,
+        }
+    }
+}
+
+impl Node {
+    fn contains(&self, tab: &str) -> bool {
+        match self {
+            Node::Tabs { tabs, .. } => tabs.iter().any(|t| t == tab),
+            Node::Split { first, second, .. } => first.contains(tab) || second.contains(tab),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Tabs { tabs, .. } => tabs.is_empty(),
+            Node::Split { .. } => false,
+        }
+    }
+
+    /// Add the tab to the first group.
+    fn add_tab(&mut self, tab: String) {
+        match self {
+            Node::Tabs { tabs, .. } => tabs.push(tab),
+            Node::Split { first, .. } => first.add_tab(tab),
+        }
+    }
+
+    /// Remove tabs, but leave any empty groups for [`Self::simplify`].
+    fn retain_tabs(&mut self, keep: &impl Fn(&str) -> bool) {
+        match self {
+            Node::Tabs { tabs, active } => {
+                // Keep the same tab active, by moving over for every tab removed before it:
+                let removed_before = tabs[..(*active).min(tabs.len())]
+                    .iter()
+                    .filter(|tab| !keep(tab))
+                    .count();
+                tabs.retain(|tab| keep(tab));
+                *active = (*active - removed_before).min(tabs.len().saturating_sub(1));
+            }
+            Node::Split { first, second, .. } => {
+                first.retain_tabs(keep);
+                second.retain_tabs(keep);
+            }
+        }
+    }
+
+    /// Remove empty groups, letting the other side of their split take over.
+    fn simplify(&mut self) {
+        if let Node::Split { first, second, .. } = self {
+            first.simplify();
+            second.simplify();
+            if first.is_empty() {
+                *self = std::mem::take(second);
+            } else if second.is_empty() {
+                *self = std::mem::take(first);
+            }
+        }
+    }
+
+    /// The group with the given index, counting depth first.
+    fn group_mut(&mut self, index: &mut usize) -> Option<&mut Node> {
+        match self {
+            Node::Tabs { .. } => {
+                if *index == 0 {
+                    Some(self)
+                } else {
+                    *index -= 1;
+                    None
+                }
+            }
+            Node::Split { first, second, .. } => {
+                if let Some(node) = first.group_mut(index) {
+                    Some(node)
+                } else {
+                    second.group_mut(index)
+                }
+            }
+        }
+    }
+
+    /// Move `tab` to the group with index `group`.
+    fn dock(&mut self, tab: String, group: usize, zone: DropZone) {
+        // Leave the empty group (if any) until after we have found the target group,
+        // so that the group indices stay valid:
+        self.retain_tabs(&|t| t != tab);
+
+        if let Some(node) = self.group_mut(&mut { group }) {
+            if zone == DropZone::Center {
+                if let Node::Tabs { tabs, active } = node {
+                    tabs.push(tab);
+                    *active = tabs.len() - 1;
+                }
+            } else {
+                let old = std::mem::take(node);
+                let new = Node::Tabs {
+                    tabs: vec![tab],
+                    active: 0,
+                };
+                let (first, second) = match zone {
+                    DropZone::Left | DropZone::Top => (new, old),
+                    _ => (old, new),
+                };
+                *node = Node::Split {
+                    horizontal: zone == DropZone::Left || zone == DropZone::Right,
+                    fraction: 0.5,
+                    first: Box::new(first),
+                    second: Box::new(second),
+                };
+            }
+        }
+
+        self.simplify();
+    }
+}
+
+/// State that is persisted between frames
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct State {
+    root: Node,
+
+    /// The tab being dragged, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dragged: Option<String>,
+}
+
+/// Where a tab dropped on a group ends up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DropZone {
+    /// In the group, as another tab.
+    Center,
+    /// In a new group, splitting the group in two.
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl DropZone {
+    /// The outer quarter of each side of a group splits it, the middle adds a tab to it.
+    fn at(rect: Rect, pos: Pos2) -> Self {
+        let x = (pos.x - rect.left()) / rect.width();
+        let y = (pos.y - rect.top()) / rect.height();
+        let sides = [
+            (x, DropZone::Left),
+            (1.0 - x, DropZone::Right),
+            (y, DropZone::Top),
+            (1.0 - y, DropZone::Bottom),
+        ];
+        let (distance, zone) =
+            sides
+                .iter()
+                .copied()
+                .fold((f32::INFINITY, DropZone::Center), |closest, side| {
+                    if side.0 < closest.0 {
+                        side
+                    } else {
+                        closest
+                    }
+                });
+        if distance < 0.25 {
+            zone
+        } else {
+            DropZone::Center
+        }
+    }
+
+    /// Where the tab would go if dropped on a group covering `rect`.
+    fn preview_rect(self, rect: Rect) -> Rect {
+        let center = rect.center();
+        match self {
+            DropZone::Center => rect,
+            DropZone::Left => Rect::from_min_max(rect.min, pos2(center.x, rect.bottom())),
+            DropZone::Right => Rect::from_min_max(pos2(center.x, rect.top()), rect.max),
+            DropZone::Top => Rect::from_min_max(rect.min, pos2(rect.right(), center.y)),
+            DropZone::Bottom => Rect::from_min_max(pos2(rect.left(), center.y), rect.max),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Tabs that the user can arrange into groups and splits by dragging them around.
+///
+/// The dock area fills the available space, so put it in e.g. a [`CentralPanel`] or a [`Resize`].
+/// Tabs are identified by their titles, which must be unique within the dock area.
+/// New tabs are added to the first group, and tabs that are no longer given are removed.
+///
+/// ``` ignore
+/// egui::DockArea::new("editor")
+///     .tabs(&["Scene", "Inspector", "Console"])
+///     .show(ui, |ui, tab| match tab {
+///         "Scene" => scene_ui(ui),
+///         "Inspector" => inspector_ui(ui),
+///         _ => console_ui(ui),
+///     });
+/// ```
+#[derive(Clone, Debug)]
+pub struct DockArea {
+    id_source: Id,
+    tabs: Vec<String>,
+}
+
+impl DockArea {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            tabs: vec![],
+        }
+    }
+
+    pub fn tab(mut self, title: impl Into<String>) -> Self {
+        self.tabs.push(title.into());
+        self
+    }
+
+    pub fn tabs(mut self, titles: &[impl ToString]) -> Self {
+        self.tabs.extend(titles.iter().map(ToString::to_string));
+        self
+    }
+
+    /// `add_contents` is called with the title of each tab that is showing.
+    pub fn show(self, ui: &mut Ui, mut add_contents: impl FnMut(&mut Ui, &str)) {
+        let Self { id_source, tabs } = self;
+
+        let id = ui.make_child_id(id_source);
+        let mut state = ui.memory().docks.get(&id).cloned().unwrap_or_default();
+        let prev_root = state.root.clone();
+
+        state.root.retain_tabs(&|tab| tabs.iter().any(|t| t == tab));
+        for tab in tabs {
+            if !state.root.contains(&tab) {
+                state.root.add_tab(tab);
+            }
+        }
+        state.root.simplify();
+
+        let rect = ui.available_finite();
+        let mut dock_ui = DockUi {
+            ui,
+            id,
+            group_rects: vec![],
+            num_splits: 0,
+            started_drag: None,
+        };
+        dock_ui.node_ui(&mut state.root, rect, &mut add_contents);
+        let DockUi {
+            ui,
+            group_rects,
+            started_drag,
+            ..
+        } = dock_ui;
+
+        if let Some(tab) = state.dragged.take().or(started_drag) {
+            drag_tab(ui, id, &mut state, tab, &group_rects);
+        }
+
+        ui.allocate_space(rect.size());
+
+        if state.root != prev_root {
+            ui.ctx().request_repaint(); // Show the new layout right away.
+        }
+        ui.memory().docks.insert(id, state);
+    }
+}
+
+/// Show the tab following the mouse and where it will go, and move it there when dropped.
+fn drag_tab(ui: &Ui, id: Id, state: &mut State, tab: String, group_rects: &[Rect]) {
+    let mouse = &ui.input().mouse;
+    let mouse_pos = match mouse.pos {
+        Some(mouse_pos) => mouse_pos,
+        None => return,
+    };
+    let target = group_rects
+        .iter()
+        .position(|rect| rect.contains(mouse_pos))
+        .map(|group| (group, DropZone::at(group_rects[group], mouse_pos)));
+
+    if mouse.down {
+        if let Some((group, zone)) = target {
+            let selection = ui.style().visuals.selection_bg_color;
+            ui.painter().rect(
+                zone.preview_rect(group_rects[group]).shrink(2.0),
+                2.0,
+                Srgba::from(Rgba::from(selection).multiply(0.5)),
+                Stroke::new(1.0, selection),
+            );
+        }
+        Area::new(id.with("dragged_tab"))
+            .order(Order::Foreground)
+            .interactable(false)
+            .fixed_pos(mouse_pos + vec2(8.0, 8.0))
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| ui.label(&tab));
+            });
+        state.dragged = Some(tab);
+    } else if let Some((group, zone)) = target {
+        state.root.dock(tab, group, zone);
+    }
+}
+
+struct DockUi<'ui> {
+    ui: &'ui mut Ui,
+    id: Id,
+    /// The rectangles of the groups, in depth first order. Used as drop targets.
+    group_rects: Vec<Rect>,
+    /// For giving the splitters ids.
+    num_splits: usize,
+    started_drag: Option<String>,
+}
+
+impl<'ui> DockUi<'ui> {
+    fn node_ui(
+        &mut self,
+        node: &mut Node,
+        rect: Rect,
+        add_contents: &mut dyn FnMut(&mut Ui, &str),
+    ) {
+        match node {
+            Node::Tabs { tabs, active } => self.tabs_ui(tabs, active, rect, add_contents),
+            Node::Split {
+                horizontal,
+                fraction,
+                first,
+                second,
+            } => {
+                let split = SplitHandle::new(self.ui, rect, *horizontal);
+                let range = split.fraction_range([MIN_SPLIT_SIZE; 2]);
+                *fraction = clamp(*fraction, range.clone());
+                let splitter_id = self.id.with("splitter").with(self.num_splits);
+                self.num_splits += 1;
+                let response = split.interact(self.ui, splitter_id, fraction, range);
+                let (first_rect, second_rect) = split.sides(self.ui, *fraction, &response);
+                self.node_ui(first, first_rect, add_contents);
+                self.node_ui(second, second_rect, add_contents);
+            }
+        }
+    }
+
+    fn tabs_ui(
+        &mut self,
+        tabs: &[String],
+        active: &mut usize,
+        rect: Rect,
+        add_contents: &mut dyn FnMut(&mut Ui, &str),
+    ) {
+        self.group_rects.push(rect);
+        let ui = &mut *self.ui;
+
+        let bar_height = ui.style().spacing.interact_size.y;
+        let bar_rect = Rect::from_min_max(rect.min, pos2(rect.right(), rect.top() + bar_height));
+        let body_rect = Rect::from_min_max(pos2(rect.left(), bar_rect.bottom()), rect.max);
+
+        let frame = Frame::panel(ui.style());
+        let bar_painter = ui.painter().sub_region(bar_rect);
+        let padding = ui.style().spacing.button_padding.x;
+        let mut left = bar_rect.left();
+        for (i, tab) in tabs.iter().enumerate() {
+            let galley = ui.fonts()[TextStyle::Button].layout_single_line(tab.clone());
+            let tab_rect = Rect::from_min_size(
+                pos2(left, bar_rect.top()),
+                vec2(galley.size.x + 2.0 * padding, bar_height),
+            );
+            left = tab_rect.right();
+
+            let response = ui.interact(
+                tab_rect,
+                self.id.with("tab").with(tab),
+                Sense::click_and_drag(),
+            );
+            if response.clicked {
+                *active = i;
+            }
+            let mouse = &ui.input().mouse;
+            if response.active && mouse.down && !mouse.could_be_click {
+                self.started_drag = Some(tab.clone());
+            }
+
+            if i == *active {
+                bar_painter.rect(
+                    tab_rect,
+                    0.0,
+                    frame.fill.fit_to_rect(tab_rect),
+                    frame.stroke,
+                );
+            } else if response.hovered {
+                bar_painter.rect_filled(
+                    tab_rect,
+                    0.0,
+                    ui.style().interact(&response).bg_fill_in(tab_rect),
+                );
+            }
+            let text_pos = pos2(
+                tab_rect.left() + padding,
+                tab_rect.center().y - 0.5 * galley.size.y,
+            );
+            bar_painter.galley(
+                text_pos,
+                galley,
+                TextStyle::Button,
+                ui.style().visuals.text_color(),
+            );
+        }
+
+        if let Some(tab) = tabs.get(*active) {
+            // Give each tab its own id, so it keeps its state when moved to another group:
+            let mut body_ui = Ui::new(ui.ctx().clone(), ui.layer(), self.id.with(tab), body_rect);
+            body_ui.set_clip_rect(body_rect.intersect(ui.clip_rect()));
+            Frame {
+                margin: ui.style().spacing.window_padding,
+                ..frame
+            }
+            .show(&mut body_ui, |ui| {
+                // Fill the whole group, so that the background does too:
+                let fill = ui.available().size();
+                ui.set_min_size(fill);
+                add_contents(ui, tab);
+            });
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_dock_tab_to_the_side() {
+    let mut root = Node::Tabs {
+        tabs: vec!["A".into(), "B".into()],
+        active: 1,
+    };
+    root.dock("B".into(), 0, DropZone::Right);
+    assert_eq!(
+        root,
+        Node::Split {
+            horizontal: true,
+            fraction: 0.5,
+            first: Box::new(Node::Tabs {
+                tabs: vec!["A".into()],
+                active: 0
+            }),
+            second: Box::new(Node::Tabs {
+                tabs: vec!["B".into()],
+                active: 0
+            }),
+        }
+    );
+
+    // Dock it back, leaving an empty group to be removed:
+    root.dock("B".into(), 0, DropZone::Center);
+    assert_eq!(
+        root,
+        Node::Tabs {
+            tabs: vec!["A".into(), "B".into()],
+            active: 1
+        }
+    );
+}
+
+#[test]
+fn test_retain_tabs_keeps_active_tab() {
+    let tabs = |tabs: &[&str], active| Node::Tabs {
+        tabs: tabs.iter().map(ToString::to_string).collect(),
+        active,
+    };
+    let mut root = tabs(&["A", "B", "C"], 1);
+    root.retain_tabs(&|tab| tab != "A");
+    assert_eq!(root, tabs(&["B", "C"], 0));
+
+    // Removing the active tab activates the next one, or the last one:
+    let mut root = tabs(&["A", "B", "C"], 1);
+    root.retain_tabs(&|tab| tab != "B");
+    assert_eq!(root, tabs(&["A", "C"], 1));
+    let mut root = tabs(&["A", "B", "C"], 2);
+    root.retain_tabs(&|tab| tab != "C");
+    assert_eq!(root, tabs(&["A", "B"], 1));
+}
+
+#[test]
+fn test_drag_tab_to_the_side() {
+    // Press the mouse on the first tab, drag it to the right edge of the dock area and drop it:
+    let mouse = [
+        (pos2(4.0, 8.0), false),
+        (pos2(4.0, 8.0), true),
+        (pos2(100.0, 100.0), true),
+        (pos2(390.0, 150.0), true),
+        (pos2(390.0, 150.0), false),
+    ];
+    let ctx = super::run_mouse_frames(vec2(400.0, 300.0), &mouse, |ui| {
+        DockArea::new("dock").tabs(&["A", "B"]).show(ui, |ui, tab| {
+            ui.label(tab);
+        });
+    });
+
+    let root = ctx.memory().docks.values().next().unwrap().root.clone();
+    match &root {
+        Node::Split {
+            horizontal,
+            first,
+            second,
+            ..
+        } => {
+            assert!(*horizontal);
+            assert!(first.contains("B"));
+            assert!(second.contains("A"));
+        }
+        _ => panic!("Expected a split, got {:?}", root),
+    }
+}
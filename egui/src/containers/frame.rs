@@ -7,59 +7,93 @@ use crate::{layers::PaintCmdIdx, paint::*, *};
 pub struct Frame {
     // On each side
     pub margin: Vec2,
-    pub corner_radius: f32,
-    pub fill: Srgba,
+    pub rounding: Rounding,
+    /// Gradients are relative to the frame, see [`Fill::fit_to_rect`].
+    pub fill: Fill,
     pub stroke: Stroke,
+    pub shadow: Shadow,
+    /// Clip the contents to the rounded corners of the frame. Default: `false`.
+    ///
+    /// The clipping is done on the CPU (see `PaintCmd::Clipped`), so only turn it on
+    /// if the contents actually reach into the corners.
+    pub clip_contents: bool,
 }
 
 impl Frame {
     pub fn none() -> Self {
         Self {
             margin: Vec2::zero(),
-            corner_radius: 0.0,
+            rounding: Rounding::none(),
             fill: Default::default(),
             stroke: Stroke::none(),
+            shadow: Shadow::none(),
+            clip_contents: false,
         }
     }
 
     pub fn window(style: &Style) -> Self {
         Self {
             margin: style.spacing.window_padding,
-            corner_radius: style.visuals.window_corner_radius,
-            fill: style.visuals.widgets.noninteractive.bg_fill,
+            rounding: style.visuals.window_corner_radius.into(),
+            fill: style.visuals.widgets.noninteractive.bg_fill.clone(),
             stroke: style.visuals.widgets.inactive.bg_stroke, // because we can resize windows
+            shadow: style.visuals.window_shadow,
+            clip_contents: false,
         }
     }
 
     pub fn menu_bar(style: &Style) -> Self {
         Self {
             margin: Vec2::splat(1.0),
-            corner_radius: 0.0,
-            fill: style.visuals.widgets.noninteractive.bg_fill,
+            rounding: Rounding::none(),
+            fill: style.visuals.widgets.noninteractive.bg_fill.clone(),
             stroke: style.visuals.widgets.noninteractive.bg_stroke,
+            shadow: Shadow::none(),
+            clip_contents: false,
+        }
+    }
+
+    pub fn panel(style: &Style) -> Self {
+        Self {
+            margin: Vec2::new(8.0, 2.0),
+            rounding: Rounding::none(),
+            fill: style.visuals.widgets.noninteractive.bg_fill.clone(),
+            stroke: style.visuals.widgets.noninteractive.bg_stroke,
+            shadow: Shadow::none(),
+            clip_contents: false,
         }
     }
 
     pub fn menu(style: &Style) -> Self {
         Self {
             margin: Vec2::splat(1.0),
-            corner_radius: 2.0,
-            fill: style.visuals.widgets.noninteractive.bg_fill,
+            rounding: Rounding::same(2.0),
+            fill: style.visuals.widgets.noninteractive.bg_fill.clone(),
             stroke: style.visuals.widgets.noninteractive.bg_stroke,
+            shadow: style.visuals.popup_shadow,
+            clip_contents: false,
         }
     }
 
     pub fn popup(style: &Style) -> Self {
         Self {
             margin: style.spacing.window_padding,
-            corner_radius: 5.0,
-            fill: style.visuals.widgets.noninteractive.bg_fill,
+            rounding: Rounding::same(5.0),
+            fill: style.visuals.widgets.noninteractive.bg_fill.clone(),
             stroke: style.visuals.widgets.noninteractive.bg_stroke,
+            shadow: style.visuals.popup_shadow,
+            clip_contents: false,
         }
     }
 
-    pub fn fill(mut self, fill: Srgba) -> Self {
-        self.fill = fill;
+    /// A color, or a gradient relative to the frame (e.g. [`Fill::vertical_gradient`]).
+    pub fn fill(mut self, fill: impl Into<Fill>) -> Self {
+        self.fill = fill.into();
+        self
+    }
+
+    pub fn rounding(mut self, rounding: impl Into<Rounding>) -> Self {
+        self.rounding = rounding.into();
         self
     }
 
@@ -67,24 +101,37 @@ impl Frame {
         self.stroke = stroke;
         self
     }
+
+    pub fn shadow(mut self, shadow: Shadow) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
+    pub fn clip_contents(mut self, clip_contents: bool) -> Self {
+        self.clip_contents = clip_contents;
+        self
+    }
 }
 
 pub struct Prepared {
     pub frame: Frame,
     outer_rect_bounds: Rect,
+    where_to_put_shadow: PaintCmdIdx,
     where_to_put_background: PaintCmdIdx,
     pub content_ui: Ui,
 }
 
 impl Frame {
     pub fn begin(self, ui: &mut Ui) -> Prepared {
-        let outer_rect_bounds = ui.available();
+        let outer_rect_bounds = ui.available_for_child();
         let inner_rect = outer_rect_bounds.shrink2(self.margin);
+        let where_to_put_shadow = ui.painter().add(PaintCmd::Noop);
         let where_to_put_background = ui.painter().add(PaintCmd::Noop);
         let content_ui = ui.child_ui(inner_rect, *ui.layout());
         Prepared {
             frame: self,
             outer_rect_bounds,
+            where_to_put_shadow,
             where_to_put_background,
             content_ui,
         }
@@ -111,22 +158,105 @@ impl Prepared {
 
         let Prepared {
             frame,
+            where_to_put_shadow,
             where_to_put_background,
             ..
         } = self;
 
+        if frame.shadow.is_visible() {
+            // The frame of a window, popup or menu is the first thing in its layer,
+            // and its shadow is allowed to spill outside of the clip rect of the area.
+            // The shadow of a frame inside e.g. a window stays within the window.
+            let mut painter = ui.painter().clone();
+            if where_to_put_shadow.is_first() {
+                painter.set_clip_rect(
+                    painter
+                        .clip_rect()
+                        .union(frame.shadow.visual_rect(outer_rect)),
+                );
+            }
+            painter.set(
+                where_to_put_shadow,
+                PaintCmd::Shadow {
+                    rect: outer_rect,
+                    rounding: frame.rounding,
+                    shadow: frame.shadow,
+                },
+            );
+        }
+
         ui.painter().set(
             where_to_put_background,
             PaintCmd::Rect {
-                corner_radius: frame.corner_radius,
-                fill: frame.fill,
+                rounding: frame.rounding,
+                fill: frame.fill.fit_to_rect(outer_rect),
                 stroke: frame.stroke,
                 rect: outer_rect,
             },
         );
 
-        ui.allocate_space(outer_rect.size());
+        if frame.clip_contents && !frame.rounding.is_none() {
+            ui.painter()
+                .clip_after(where_to_put_background, outer_rect, frame.rounding);
+        }
+
+        ui.allocate_child(outer_rect.size());
 
         outer_rect
     }
 }
+
+#[test]
+fn test_shadow_clip_rect() {
+    let mut ctx = Context::new();
+    let _ = ctx.begin_frame(RawInput {
+        screen_size: vec2(400.0, 400.0),
+        ..Default::default()
+    });
+    let ctx = &ctx;
+    let mut inner_clip_rect = Rect::nothing();
+    Window::new("window")
+        .default_pos(pos2(100.0, 100.0))
+        .show(ctx, |ui| {
+            inner_clip_rect = ui.clip_rect();
+            let shadow = Shadow {
+                blur: 100.0,
+                ..Shadow::small()
+            };
+            Frame::popup(ui.style()).shadow(shadow).show(ui, |ui| {
+                ui.label("In a popup frame in a window");
+            });
+        });
+    let (_, commands) = ctx.end_frame_commands();
+
+    fn find_shadows(clip_rect: Rect, cmd: PaintCmd, shadows: &mut Vec<(Rect, Rect, Shadow)>) {
+        match cmd {
+            PaintCmd::Shadow { rect, shadow, .. } => shadows.push((clip_rect, rect, shadow)),
+            PaintCmd::Clipped { commands, .. } => {
+                for cmd in commands {
+                    find_shadows(clip_rect, cmd, shadows);
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut shadows = vec![];
+    for (clip_rect, cmd) in commands {
+        find_shadows(clip_rect, cmd, &mut shadows);
+    }
+    assert_eq!(shadows.len(), 2);
+    let contains = |outer: Rect, inner: Rect| outer.union(inner) == outer;
+
+    let (window_clip_rect, window_rect, window_shadow) = shadows[0];
+    assert!(contains(
+        window_clip_rect,
+        window_shadow.visual_rect(window_rect)
+    ));
+
+    let (popup_clip_rect, popup_rect, popup_shadow) = shadows[1];
+    assert!(!contains(
+        inner_clip_rect,
+        popup_shadow.visual_rect(popup_rect)
+    ));
+    assert_eq!(popup_clip_rect, inner_clip_rect);
+}
@@ -0,0 +1,305 @@
+use std::hash::Hash;
+
+use crate::{paint::PaintCmd, *};
+
+/// Sizes measured last frame, so that all rows can line up.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct State {
+    col_widths: Vec<f32>,
+    row_heights: Vec<f32>,
+
+    /// Width of the contents of each cell, row by row. Used for centering.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cell_widths: Vec<Vec<f32>>,
+}
+
+/// A container that lines up its contents in columns, e.g. for property editors.
+///
+/// Each row is added with a closure, and each cell in it with another closure.
+/// The width of each column is that of its widest cell the previous frame.
+///
+/// ``` ignore
+/// egui::Grid::new("person").striped(true).show(ui, |grid| {
+///     grid.row(|row| {
+///         row.col(|ui| ui.label("Name"));
+///         row.col(|ui| ui.text_edit(&mut name));
+///     });
+///     grid.row(|row| {
+///         row.col(|ui| ui.label("Age"));
+///         row.col(|ui| ui.add(egui::DragValue::i32(&mut age)));
+///     });
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Grid {
+    id_source: Id,
+    striped: bool,
+    spacing: Option<Vec2>,
+    min_col_width: f32,
+    col_aligns: Vec<Align>,
+}
+
+impl Grid {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            striped: false,
+            spacing: None,
+            min_col_width: 0.0,
+            col_aligns: vec![],
+        }
+    }
+
+    /// Paint every other row with `style.visuals.faint_bg_color`. Default: `false`.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Space between columns (x) and rows (y). Default: `style.spacing.item_spacing`.
+    pub fn spacing(mut self, spacing: impl Into<Vec2>) -> Self {
+        self.spacing = Some(spacing.into());
+        self
+    }
+
+    pub fn min_col_width(mut self, min_col_width: f32) -> Self {
+        self.min_col_width = min_col_width;
+        self
+    }
+
+    /// How to align the contents of column `col` horizontally. Default: `Align::Min` (left).
+    pub fn col_align(mut self, col: usize, align: Align) -> Self {
+        if self.col_aligns.len() <= col {
+            self.col_aligns.resize(col + 1, Align::Min);
+        }
+        self.col_aligns[col] = align;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut GridRows<'_>) -> R) -> R {
+        let Self {
+            id_source,
+            striped,
+            spacing,
+            min_col_width,
+            col_aligns,
+        } = self;
+
+        let id = ui.make_child_id(id_source);
+        let prev_state = ui.memory().grids.get(&id).cloned().unwrap_or_default();
+        let spacing = spacing.unwrap_or(ui.style().spacing.item_spacing);
+        let origin = ui.available().min;
+
+        let mut rows = GridRows {
+            ui,
+            prev_state,
+            state: Default::default(),
+            spacing,
+            min_col_width,
+            col_aligns,
+            origin,
+            cursor_y: origin.y,
+            stripes: vec![],
+            striped,
+        };
+        let ret = add_contents(&mut rows);
+        rows.end(id);
+        ret
+    }
+}
+
+/// Passed to the closure of [`Grid::show`]. Add rows with [`Self::row`].
+pub struct GridRows<'ui> {
+    ui: &'ui mut Ui,
+    prev_state: State,
+    state: State,
+    spacing: Vec2,
+    min_col_width: f32,
+    col_aligns: Vec<Align>,
+    origin: Pos2,
+    /// Top of the next row.
+    cursor_y: f32,
+    /// Where to paint the background of every other row, once we know how wide the grid is.
+    stripes: Vec<(PaintCmdIdx, f32, f32)>,
+    striped: bool,
+}
+
+impl<'ui> GridRows<'ui> {
+    /// Add a row. Add the cells of the row with [`GridRow::col`].
+    pub fn row<R>(&mut self, add_contents: impl FnOnce(&mut GridRow<'_, 'ui>) -> R) -> R {
+        let row_idx = self.state.row_heights.len();
+        let stripe = if self.striped && row_idx % 2 == 1 {
+            Some(self.ui.painter().add(PaintCmd::Noop))
+        } else {
+            None
+        };
+
+        let top = self.cursor_y;
+        let cursor_x = self.origin.x;
+        let mut row = GridRow {
+            rows: self,
+            row_idx,
+            top,
+            cursor_x,
+            height: 0.0,
+            cell_widths: vec![],
+        };
+        let ret = add_contents(&mut row);
+        let GridRow {
+            height,
+            cell_widths,
+            ..
+        } = row;
+
+        if let Some(stripe) = stripe {
+            self.stripes.push((stripe, top, height));
+        }
+        self.state.row_heights.push(height);
+        self.state.cell_widths.push(cell_widths);
+        self.cursor_y = top + height + self.spacing.y;
+        ret
+    }
+
+    fn end(self, id: Id) {
+        let GridRows {
+            ui,
+            prev_state,
+            state,
+            spacing,
+            origin,
+            stripes,
+            ..
+        } = self;
+
+        let num_cols = state.col_widths.len();
+        let num_rows = state.row_heights.len();
+        let width = state.col_widths.iter().sum::<f32>() + spacing.x * (num_cols.max(1) - 1) as f32;
+        let height =
+            state.row_heights.iter().sum::<f32>() + spacing.y * (num_rows.max(1) - 1) as f32;
+
+        for (idx, top, row_height) in stripes {
+            let rect = Rect::from_min_size(pos2(origin.x, top), vec2(width, row_height))
+                .expand2(0.5 * spacing);
+            let fill = ui.style().visuals.faint_bg_color;
+            ui.painter()
+                .set(idx, PaintCmd::rect_filled(rect, 2.0, fill));
+        }
+
+        ui.allocate_space(vec2(width, height));
+
+        if state != prev_state {
+            ui.ctx().request_repaint(); // Things have moved. Line them up next frame.
+        }
+        ui.memory().grids.insert(id, state);
+    }
+}
+
+/// Passed to the closure of [`GridRows::row`]. Add cells with [`Self::col`].
+pub struct GridRow<'a, 'ui> {
+    rows: &'a mut GridRows<'ui>,
+    row_idx: usize,
+    top: f32,
+    /// Left side of the next cell.
+    cursor_x: f32,
+    height: f32,
+    cell_widths: Vec<f32>,
+}
+
+impl<'a, 'ui> GridRow<'a, 'ui> {
+    /// Add the next cell of this row.
+    pub fn col<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        let rows = &mut *self.rows;
+        let col_idx = self.cell_widths.len();
+        let align = rows.col_aligns.get(col_idx).copied().unwrap_or(Align::Min);
+
+        let prev_col_width = rows.prev_state.col_widths.get(col_idx).copied();
+        let col_width = prev_col_width.unwrap_or(0.0).max(rows.min_col_width);
+        let row_height = rows
+            .prev_state
+            .row_heights
+            .get(self.row_idx)
+            .copied()
+            .unwrap_or(0.0)
+            .max(rows.ui.style().spacing.interact_size.y);
+        let prev_cell_width = rows
+            .prev_state
+            .cell_widths
+            .get(self.row_idx)
+            .and_then(|row| row.get(col_idx))
+            .copied();
+
+        let left = self.cursor_x;
+        let right = rows.ui.max_rect_finite().right().max(left);
+        let (max_rect, layout) = match align {
+            Align::Min => (
+                Rect::from_min_max(pos2(left, self.top), pos2(right, self.top + row_height)),
+                Layout::horizontal(Align::Center),
+            ),
+            Align::Center => {
+                let offset = 0.5 * (col_width - prev_cell_width.unwrap_or(col_width));
+                let left = left + offset.max(0.0);
+                (
+                    Rect::from_min_max(pos2(left, self.top), pos2(right, self.top + row_height)),
+                    Layout::horizontal(Align::Center),
+                )
+            }
+            Align::Max => {
+                // Until we know how wide the column is, we can't know where its right side is.
+                let col_right = match prev_col_width {
+                    Some(_) => left + col_width,
+                    None => right,
+                };
+                // Leave room to the left, so that a cell wider than last frame doesn't wrap.
+                let room_left = rows.ui.max_rect_finite().left().min(left);
+                (
+                    Rect::from_min_max(
+                        pos2(room_left, self.top),
+                        pos2(col_right, self.top + row_height),
+                    ),
+                    Layout::horizontal(Align::Center).reverse(),
+                )
+            }
+        };
+
+        let mut cell_ui = rows.ui.child_ui(max_rect, layout);
+        let ret = add_contents(&mut cell_ui);
+        let cell_size = cell_ui.min_size();
+
+        if rows.state.col_widths.len() <= col_idx {
+            rows.state.col_widths.push(rows.min_col_width);
+        }
+        let new_col_width = &mut rows.state.col_widths[col_idx];
+        *new_col_width = new_col_width.max(cell_size.x);
+
+        self.height = self.height.max(cell_size.y);
+        self.cursor_x = left + col_width.max(cell_size.x) + rows.spacing.x;
+        self.cell_widths.push(cell_size.x);
+        ret
+    }
+}
+
+#[test]
+fn test_grid_columns_line_up() {
+    let mut ctx = Context::new();
+    let mut lefts = vec![];
+    for frame in 0..2 {
+        let raw_input = RawInput {
+            time: frame as f64,
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        lefts.clear();
+        Grid::new("grid").show(&mut ui, |grid| {
+            for label in &["Short", "A much longer label"] {
+                grid.row(|row| {
+                    row.col(|ui| ui.label(*label));
+                    lefts.push(row.col(|ui| ui.label("Value")).rect.left());
+                });
+            }
+        });
+        let _ = ctx.end_frame();
+    }
+    assert_eq!(lefts[0], lefts[1]);
+}
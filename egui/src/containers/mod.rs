@@ -5,13 +5,53 @@
 pub(crate) mod area;
 pub(crate) mod collapsing_header;
 mod combo_box;
+pub(crate) mod dock;
 pub(crate) mod frame;
+pub(crate) mod grid;
+pub(crate) mod panel;
 pub(crate) mod popup;
 pub(crate) mod resize;
 pub(crate) mod scroll_area;
+pub(crate) mod splitter;
+pub(crate) mod table;
 pub(crate) mod window;
 
 pub use {
-    area::Area, collapsing_header::*, combo_box::*, frame::Frame, popup::*, resize::Resize,
-    scroll_area::ScrollArea, window::Window,
+    area::Area,
+    collapsing_header::*,
+    combo_box::*,
+    dock::DockArea,
+    frame::Frame,
+    grid::{Grid, GridRow, GridRows},
+    panel::{BottomPanel, CentralPanel, SidePanel, TopPanel},
+    popup::*,
+    resize::Resize,
+    scroll_area::ScrollArea,
+    splitter::Splitter,
+    table::{Column, SortOrder, Table, TableBody, TableRow},
+    window::Window,
 };
+
+/// Run one frame for each `(mouse_pos, mouse_down)`, a twentieth of a second apart,
+/// so that tests can click and drag things. Returns the context, to look at what is in `Memory`.
+#[cfg(test)]
+pub(crate) fn run_mouse_frames(
+    screen_size: crate::Vec2,
+    mouse: &[(crate::Pos2, bool)],
+    mut add_contents: impl FnMut(&mut crate::Ui),
+) -> std::sync::Arc<crate::Context> {
+    let mut ctx = crate::Context::new();
+    for (frame, &(mouse_pos, mouse_down)) in mouse.iter().enumerate() {
+        let raw_input = crate::RawInput {
+            screen_size,
+            time: 0.05 * frame as f64,
+            mouse_pos: Some(mouse_pos),
+            mouse_down,
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        add_contents(&mut ui);
+        let _ = ctx.end_frame();
+    }
+    ctx
+}
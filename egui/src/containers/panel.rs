@@ -0,0 +1,461 @@
+//! Panels are `Ui` regions along the edges of the screen, e.g. for a menu bar or a tool bar.
+//!
+//! Each panel takes its space from what is left of the screen by the panels before it,
+//! and a [`CentralPanel`] fills whatever remains.
+//! Windows are placed and kept within the space not taken by panels,
+//! so add your panels first, then your windows.
+//!
+//! ``` ignore
+//! egui::TopPanel::top("menu_bar").show(ctx, |ui| {
+//!     egui::menu::bar(ui, |ui| { /* ... */ });
+//! });
+//! egui::SidePanel::left("side_panel", 200.0).show(ctx, |ui| {
+//!     ui.label("Always here");
+//! });
+//! egui::CentralPanel::default().show(ctx, |ui| {
+//!     ui.label("The rest of the screen");
+//! });
+//! ```
+
+use std::{hash::Hash, sync::Arc};
+
+use crate::*;
+
+/// State that is persisted between frames
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct State {
+    /// Width of a side panel, or height of a top or bottom panel.
+    size: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// What the different panels have in common.
+#[derive(Clone, Copy, Debug)]
+struct Panel {
+    id: Id,
+    side: Side,
+    default_size: f32,
+    resizable: bool,
+    min_size: f32,
+    max_size: f32,
+}
+
+impl Panel {
+    fn new(id_source: impl Hash, side: Side, default_size: f32, resizable: bool) -> Self {
+        Self {
+            id: Id::new(id_source),
+            side,
+            default_size,
+            resizable,
+            min_size: 0.0,
+            max_size: f32::INFINITY,
+        }
+    }
+
+    /// 0 for side panels (sized in x), 1 for top and bottom panels (sized in y).
+    fn axis(&self) -> usize {
+        match self.side {
+            Side::Left | Side::Right => 0,
+            Side::Top | Side::Bottom => 1,
+        }
+    }
+
+    /// The rectangle of the panel, taken from the given available space.
+    fn rect(&self, available: Rect, size: f32) -> Rect {
+        match self.side {
+            Side::Left => Rect::from_min_max(
+                available.min,
+                pos2(available.left() + size, available.bottom()),
+            ),
+            Side::Right => Rect::from_min_max(
+                pos2(available.right() - size, available.top()),
+                available.max,
+            ),
+            Side::Top => Rect::from_min_max(
+                available.min,
+                pos2(available.right(), available.top() + size),
+            ),
+            Side::Bottom => Rect::from_min_max(
+                pos2(available.left(), available.bottom() - size),
+                available.max,
+            ),
+        }
+    }
+
+    /// The edge facing the rest of the screen, i.e. the one you can resize.
+    fn inner_edge(&self, rect: Rect) -> [Pos2; 2] {
+        match self.side {
+            Side::Left => [rect.right_top(), rect.right_bottom()],
+            Side::Right => [rect.left_top(), rect.left_bottom()],
+            Side::Top => [rect.left_bottom(), rect.right_bottom()],
+            Side::Bottom => [rect.left_top(), rect.right_top()],
+        }
+    }
+
+    /// What size would put the inner edge at the mouse position?
+    fn size_from_mouse(&self, available: Rect, mouse_pos: Pos2) -> f32 {
+        match self.side {
+            Side::Left => mouse_pos.x - available.left(),
+            Side::Right => available.right() - mouse_pos.x,
+            Side::Top => mouse_pos.y - available.top(),
+            Side::Bottom => available.bottom() - mouse_pos.y,
+        }
+    }
+
+    /// What is left of `available` once this panel has taken `rect`.
+    fn remaining(&self, available: Rect, rect: Rect) -> Rect {
+        let mut remaining = available;
+        match self.side {
+            Side::Left => remaining.min.x = rect.right(),
+            Side::Right => remaining.max.x = rect.left(),
+            Side::Top => remaining.min.y = rect.bottom(),
+            Side::Bottom => remaining.max.y = rect.top(),
+        }
+        remaining
+    }
+
+    fn show<R>(self, ctx: &Arc<Context>, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        let id = self.id;
+        let axis = self.axis();
+        let available = ctx.available_rect();
+        let max_size = self.max_size.min(available.size()[axis]);
+        let clamp_size = |size: f32| size.at_most(max_size).at_least(self.min_size);
+
+        let prev_state = ctx.memory().panels.get(&id).copied();
+        let mut size = clamp_size(prev_state.map_or(self.default_size, |state| state.size));
+
+        let layer = Layer {
+            order: Order::Background,
+            id: Id::background(),
+        };
+
+        // Interact with the edge before the contents, so the edge wins over any widget under it:
+        let mut edge_stroke = None;
+        if self.resizable {
+            let [a, b] = self.inner_edge(self.rect(available, size));
+            let grab_radius = ctx.style().interaction.resize_grab_radius_side;
+            let edge_rect = Rect::from_min_max(a, b).expand(grab_radius);
+            let response = ctx.interact(
+                layer,
+                ctx.rect(),
+                edge_rect,
+                Some(id.with("resize")),
+                Sense::drag(),
+            );
+
+            if response.active {
+                if let Some(mouse_pos) = ctx.input().mouse.pos {
+                    size = clamp_size(self.size_from_mouse(available, mouse_pos));
+                }
+            }
+            if response.hovered || response.active {
+                ctx.output().cursor_icon = if axis == 0 {
+                    CursorIcon::ResizeHorizontal
+                } else {
+                    CursorIcon::ResizeVertical
+                };
+                edge_stroke = Some(ctx.style().interact(&response).bg_stroke);
+            }
+        }
+
+        let mut panel_ui = Ui::new(ctx.clone(), layer, id, self.rect(available, size));
+        let mut prepared = Frame::panel(&ctx.style()).begin(&mut panel_ui);
+        let ret = add_contents(&mut prepared.content_ui);
+        // Measure the contents before filling the whole panel with them:
+        let content_size = prepared.outer_rect().size()[axis];
+        let fill = prepared.content_ui.max_rect().size();
+        prepared.content_ui.set_min_size(fill); // So that the background covers the whole panel
+        prepared.end(&mut panel_ui);
+
+        size = if self.resizable {
+            // As the user left it, but never smaller than the contents:
+            clamp_size(size.max(content_size))
+        } else {
+            // Follow the contents, also when they shrink:
+            clamp_size(content_size.max(self.default_size))
+        };
+        let rect = self.rect(available, size);
+
+        if let Some(stroke) = edge_stroke {
+            panel_ui
+                .painter()
+                .line_segment(self.inner_edge(rect), stroke);
+        }
+
+        ctx.set_available_rect(self.remaining(available, rect));
+
+        let state = State { size };
+        if prev_state != Some(state) {
+            ctx.request_repaint(); // The space left for everything else has changed.
+        }
+        ctx.memory().panels.insert(id, state);
+
+        ret
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A panel along the left or right side of the screen, filling its full height.
+///
+/// Drag its inner edge to resize it. The width is remembered in [`Memory`].
+///
+/// Show side panels after top and bottom panels if these should span the full width.
+#[derive(Clone, Copy, Debug)]
+pub struct SidePanel {
+    panel: Panel,
+}
+
+impl SidePanel {
+    pub fn left(id_source: impl Hash, default_width: f32) -> Self {
+        Self {
+            panel: Panel::new(id_source, Side::Left, default_width, true),
+        }
+    }
+
+    pub fn right(id_source: impl Hash, default_width: f32) -> Self {
+        Self {
+            panel: Panel::new(id_source, Side::Right, default_width, true),
+        }
+    }
+
+    /// Can the user resize the panel by dragging its inner edge? Default: `true`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.panel.resizable = resizable;
+        self
+    }
+
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.panel.min_size = min_width;
+        self
+    }
+
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.panel.max_size = max_width;
+        self
+    }
+
+    pub fn show<R>(self, ctx: &Arc<Context>, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        self.panel.show(ctx, add_contents)
+    }
+}
+
+/// A panel along the top of the screen, e.g. for a menu bar.
+///
+/// It is as tall as its contents, unless the user drags its bottom edge to make it taller.
+/// The height is remembered in [`Memory`].
+#[derive(Clone, Copy, Debug)]
+pub struct TopPanel {
+    panel: Panel,
+}
+
+impl TopPanel {
+    pub fn top(id_source: impl Hash) -> Self {
+        Self {
+            panel: Panel::new(id_source, Side::Top, 0.0, false),
+        }
+    }
+
+    /// Can the user resize the panel by dragging its bottom edge? Default: `false`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.panel.resizable = resizable;
+        self
+    }
+
+    pub fn default_height(mut self, default_height: f32) -> Self {
+        self.panel.default_size = default_height;
+        self
+    }
+
+    pub fn min_height(mut self, min_height: f32) -> Self {
+        self.panel.min_size = min_height;
+        self
+    }
+
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.panel.max_size = max_height;
+        self
+    }
+
+    pub fn show<R>(self, ctx: &Arc<Context>, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        self.panel.show(ctx, add_contents)
+    }
+}
+
+/// A panel along the bottom of the screen, e.g. for a status bar.
+///
+/// It is as tall as its contents, unless the user drags its top edge to make it taller.
+/// The height is remembered in [`Memory`].
+#[derive(Clone, Copy, Debug)]
+pub struct BottomPanel {
+    panel: Panel,
+}
+
+impl BottomPanel {
+    pub fn bottom(id_source: impl Hash) -> Self {
+        Self {
+            panel: Panel::new(id_source, Side::Bottom, 0.0, false),
+        }
+    }
+
+    /// Can the user resize the panel by dragging its top edge? Default: `false`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.panel.resizable = resizable;
+        self
+    }
+
+    pub fn default_height(mut self, default_height: f32) -> Self {
+        self.panel.default_size = default_height;
+        self
+    }
+
+    pub fn min_height(mut self, min_height: f32) -> Self {
+        self.panel.min_size = min_height;
+        self
+    }
+
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.panel.max_size = max_height;
+        self
+    }
+
+    pub fn show<R>(self, ctx: &Arc<Context>, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        self.panel.show(ctx, add_contents)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The region of the screen not taken by any other panel. Show it after all other panels.
+///
+/// Windows still float on top of it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CentralPanel {}
+
+impl CentralPanel {
+    pub fn show<R>(self, ctx: &Arc<Context>, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        let layer = Layer {
+            order: Order::Background,
+            id: Id::background(),
+        };
+        let id = Id::new("central_panel");
+        let mut panel_ui = Ui::new(ctx.clone(), layer, id, ctx.available_rect());
+        let frame = Frame {
+            margin: ctx.style().spacing.window_padding,
+            ..Frame::none()
+        };
+        frame.show(&mut panel_ui, add_contents)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_windows_avoid_side_panel() {
+    let mut ctx = Context::new();
+    let mut window_response = None;
+    for frame in 0..3 {
+        let raw_input = RawInput {
+            screen_size: vec2(400.0, 300.0),
+            time: frame as f64,
+            ..Default::default()
+        };
+        let _ = ctx.begin_frame(raw_input);
+        SidePanel::left("left", 100.0).show(&ctx, |ui| ui.label("Panel"));
+        window_response = Window::new("Window").show(&ctx, |ui| {
+            ui.label("Hello");
+        });
+        let _ = ctx.end_frame();
+    }
+    assert!(window_response.unwrap().rect.left() >= 100.0);
+    assert_eq!(ctx.available_rect().left(), 100.0);
+}
+
+#[test]
+fn test_resize_side_panel() {
+    // Press the mouse on the edge of the panel, then drag it to the right:
+    let mouse = [
+        (pos2(100.0, 150.0), false),
+        (pos2(100.0, 150.0), true),
+        (pos2(130.0, 150.0), true),
+        (pos2(150.0, 150.0), true),
+        (pos2(150.0, 150.0), false),
+    ];
+    let ctx = super::run_mouse_frames(vec2(400.0, 300.0), &mouse, |ui| {
+        SidePanel::left("left", 100.0).show(ui.ctx(), |ui| ui.label("Panel"));
+    });
+    let state = ctx.memory().panels[&Id::new("left")];
+    assert_eq!(state.size, 150.0);
+}
+
+/// Drag the title bar of a window along `y = 110`, from `x = 220` to `to_x`.
+#[cfg(test)]
+fn drag_window_next_to(to_x: f32, mut add_panels: impl FnMut(&Arc<Context>)) -> Rect {
+    let mouse = [
+        (pos2(220.0, 110.0), false),
+        (pos2(220.0, 110.0), true),
+        (pos2(0.5 * (220.0 + to_x), 110.0), true),
+        (pos2(to_x, 110.0), true),
+        (pos2(to_x, 110.0), false),
+        (pos2(to_x, 110.0), false),
+    ];
+    let mut window_rect = None;
+    super::run_mouse_frames(vec2(400.0, 300.0), &mouse, |ui| {
+        add_panels(ui.ctx());
+        window_rect = Window::new("Window")
+            .default_pos(pos2(200.0, 100.0))
+            .show(ui.ctx(), |ui| {
+                ui.label("Hello");
+            })
+            .map(|response| response.rect);
+    });
+    window_rect.unwrap()
+}
+
+#[test]
+fn test_drag_window_onto_side_panel() {
+    let window_rect = drag_window_next_to(0.0, |ctx| {
+        SidePanel::left("left", 100.0).show(ctx, |ui| ui.label("Panel"));
+    });
+    assert_eq!(window_rect.left(), 100.0);
+
+    let window_rect = drag_window_next_to(400.0, |ctx| {
+        SidePanel::right("right", 100.0).show(ctx, |ui| ui.label("Panel"));
+    });
+    assert_eq!(window_rect.right(), 300.0);
+
+    // Without panels a window may still be dragged partially off screen:
+    let window_rect = drag_window_next_to(0.0, |_| {});
+    assert!(window_rect.left() < 0.0, "{:?}", window_rect);
+    let window_rect = drag_window_next_to(400.0, |_| {});
+    assert!(window_rect.right() > 400.0, "{:?}", window_rect);
+}
+
+#[test]
+fn test_top_panel_follows_contents() {
+    let mut num_lines = 3;
+    let mut heights = vec![];
+    let _ = super::run_mouse_frames(vec2(400.0, 300.0), &[(Pos2::default(), false); 4], |ui| {
+        TopPanel::top("top").show(ui.ctx(), |ui| {
+            for _ in 0..num_lines {
+                ui.label("Line");
+            }
+        });
+        heights.push(ui.ctx().available_rect().top());
+        num_lines = 1;
+    });
+    assert!(
+        heights[1] < heights[0],
+        "shrinks with its contents: {:?}",
+        heights
+    );
+    assert_eq!(heights[1], heights[3]);
+}
@@ -167,7 +167,7 @@ impl Resize {
             .at_least(self.min_size)
             .at_most(self.max_size);
 
-        let position = ui.available().min;
+        let position = ui.available_for_child().min;
 
         let corner_response = if self.resizable {
             // Resize-corner:
@@ -252,10 +252,10 @@ impl Resize {
             state.desired_size = ui.painter().round_vec_to_pixels(state.desired_size);
 
             // We are as large as we look
-            ui.allocate_space(state.desired_size);
+            ui.allocate_child(state.desired_size);
         } else {
             // Probably a window.
-            ui.allocate_space(state.last_content_size);
+            ui.allocate_child(state.last_content_size);
         }
 
         // ------------------------------
@@ -265,7 +265,7 @@ impl Resize {
             let rect = rect.expand(2.0); // breathing room for content
             ui.painter().add(paint::PaintCmd::Rect {
                 rect,
-                corner_radius: 3.0,
+                rounding: paint::Rounding::same(3.0),
                 fill: Default::default(),
                 stroke: ui.style().visuals.widgets.noninteractive.bg_stroke,
             });
@@ -1,35 +1,70 @@
+use std::hash::Hash;
+
 use crate::*;
 
+/// How long it takes to scroll to where `Ui::scroll_to_rect` asks, in seconds.
+const SCROLL_ANIMATION_TIME: f32 = 0.2;
+
+/// Smooth scrolling of one axis.
 #[derive(Clone, Copy, Debug)]
+struct ScrollAnimation {
+    from: f32,
+    to: f32,
+    start_time: f64,
+}
+
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub(crate) struct State {
     /// Positive offset means scrolling down/right
     offset: Vec2,
 
-    show_scroll: bool,
+    /// Show the horizontal (`[0]`) and vertical (`[1]`) scroll bars?
+    show_scroll: [bool; 2],
 
     /// Momentum, used for kinetic scrolling
     #[cfg_attr(feature = "serde", serde(skip))]
     pub vel: Vec2,
+
+    /// Heights measured by [`ScrollArea::show_variable_rows`], so we know where each row is
+    /// without laying it out.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    row_heights: Vec<f32>,
+
+    /// Scrolling towards where `Ui::scroll_to_rect` asked, on each axis.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    animation: [Option<ScrollAnimation>; 2],
+
+    /// Were we scrolled all the way down at the end of the last frame?
+    at_bottom: bool,
 }
 
 impl Default for State {
     fn default() -> Self {
         Self {
             offset: Vec2::zero(),
-            show_scroll: false,
+            show_scroll: [false; 2],
             vel: Vec2::zero(),
+            row_heights: vec![],
+            animation: [None; 2],
+            at_bottom: true,
         }
     }
 }
 
-// TODO: rename VScroll
-/// Add vertical scrolling to a contained `Ui`.
+/// Add vertical and/or horizontal scrolling to a contained `Ui`.
+///
+/// Each scrollable axis gets its own scroll bar.
+/// The mouse wheel scrolls vertically, and horizontally while Shift is held down.
 #[derive(Clone, Debug)]
 pub struct ScrollArea {
-    max_height: f32,
+    /// Can we scroll horizontally (`[0]`) and vertically (`[1]`)?
+    has_bar: [bool; 2],
+    max_size: Vec2,
     always_show_scroll: bool,
+    id_source: Option<Id>,
+    stick_to_bottom: bool,
 }
 
 impl ScrollArea {
@@ -40,25 +75,77 @@ impl ScrollArea {
 
     /// Use `f32::INFINITY` if you want the scroll area to expand to fit the surrounding Ui
     pub fn from_max_height(max_height: f32) -> Self {
+        Self::vertical().max_height(max_height)
+    }
+
+    /// Scroll vertically. The contents are as wide as the surrounding `Ui`.
+    pub fn vertical() -> Self {
+        Self::new([false, true])
+    }
+
+    /// Scroll horizontally. The contents can be as wide as they like.
+    pub fn horizontal() -> Self {
+        Self::new([true, false])
+    }
+
+    /// Scroll both horizontally and vertically.
+    pub fn both() -> Self {
+        Self::new([true, true])
+    }
+
+    fn new(has_bar: [bool; 2]) -> Self {
         Self {
-            max_height,
+            has_bar,
+            max_size: Vec2::splat(f32::INFINITY),
             always_show_scroll: false,
+            id_source: None,
+            stick_to_bottom: false,
         }
     }
 
+    /// Use `f32::INFINITY` (default) to be as wide as the surrounding Ui allows.
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_size.x = max_width;
+        self
+    }
+
+    /// Use `f32::INFINITY` (default) to be as high as the surrounding Ui allows.
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_size.y = max_height;
+        self
+    }
+
     /// If `false` (default), the scroll bar will be hidden when not needed/
     /// If `true`, the scroll bar will always be displayed even if not needed.
     pub fn always_show_scroll(mut self, always_show_scroll: bool) -> Self {
         self.always_show_scroll = always_show_scroll;
         self
     }
+
+    /// If `true`, stay scrolled to the bottom when the contents grow, e.g. for a log.
+    /// This only happens while the user is scrolled all the way down,
+    /// so they can still scroll up to read. Default: `false`.
+    pub fn stick_to_bottom(mut self, stick_to_bottom: bool) -> Self {
+        self.stick_to_bottom = stick_to_bottom;
+        self
+    }
+
+    /// Explicitly set the source of the `Id` of this scroll area.
+    /// You need this if you have more than one scroll area in the same `Ui`.
+    pub fn id_source(mut self, id_source: impl Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
 }
 
 struct Prepared {
     id: Id,
     state: State,
-    current_scroll_bar_width: f32,
+    has_bar: [bool; 2],
+    /// Room taken by the vertical scroll bar (`x`) and the horizontal one (`y`).
+    current_bar_use: Vec2,
     always_show_scroll: bool,
+    stick_to_bottom: bool,
     inner_rect: Rect,
     content_ui: Ui,
 }
@@ -66,13 +153,19 @@ struct Prepared {
 impl ScrollArea {
     fn begin(self, ui: &mut Ui) -> Prepared {
         let Self {
-            max_height,
+            has_bar,
+            max_size,
             always_show_scroll,
+            id_source,
+            stick_to_bottom,
         } = self;
 
         let ctx = ui.ctx().clone();
 
-        let id = ui.make_child_id("scroll_area");
+        let id = match id_source {
+            Some(id_source) => ui.make_child_id(id_source),
+            None => ui.make_child_id("scroll_area"),
+        };
         let state = ctx
             .memory()
             .scroll_areas
@@ -86,38 +179,56 @@ impl ScrollArea {
 
         let max_scroll_bar_width = max_scroll_bar_width_with_margin(ui);
 
-        let current_scroll_bar_width = if always_show_scroll {
-            max_scroll_bar_width
-        } else {
-            max_scroll_bar_width * ui.ctx().animate_bool(id, state.show_scroll)
-        };
+        let mut current_bar_use = Vec2::zero();
+        for d in 0..2 {
+            if has_bar[d] {
+                // The horizontal bar takes up height, and the vertical bar width:
+                current_bar_use[1 - d] = if always_show_scroll {
+                    max_scroll_bar_width
+                } else {
+                    max_scroll_bar_width * ui.ctx().animate_bool(id.with(d), state.show_scroll[d])
+                };
+            }
+        }
 
+        let available = ui.available_for_child();
         let outer_size = vec2(
-            ui.available().width(),
-            ui.available().height().at_most(max_height),
+            available.width().at_most(max_size.x),
+            available.height().at_most(max_size.y),
         );
 
-        let inner_size = outer_size - vec2(current_scroll_bar_width, 0.0);
-        let inner_rect = Rect::from_min_size(ui.available().min, inner_size);
+        let inner_size = outer_size - current_bar_use;
+        let inner_rect = Rect::from_min_size(available.min, inner_size);
+
+        let mut content_max_size = inner_size;
+        for d in 0..2 {
+            if has_bar[d] {
+                content_max_size[d] = f32::INFINITY;
+            }
+        }
 
         let mut content_ui = ui.child_ui(
-            Rect::from_min_size(
-                inner_rect.min - state.offset,
-                vec2(inner_size.x, f32::INFINITY),
-            ),
+            Rect::from_min_size(inner_rect.min - state.offset, content_max_size),
             *ui.layout(),
         );
         let mut content_clip_rect = inner_rect.expand(ui.style().visuals.clip_rect_margin);
         content_clip_rect = content_clip_rect.intersect(ui.clip_rect());
-        content_clip_rect.max.x = ui.clip_rect().max.x - current_scroll_bar_width; // Nice handling of forced resizing beyond the possible
+        for d in 0..2 {
+            if !has_bar[d] {
+                // Nice handling of forced resizing beyond the possible
+                content_clip_rect.max[d] = ui.clip_rect().max[d] - current_bar_use[d];
+            }
+        }
         content_ui.set_clip_rect(content_clip_rect);
 
         Prepared {
             id,
             state,
+            has_bar,
             always_show_scroll,
+            stick_to_bottom,
             inner_rect,
-            current_scroll_bar_width,
+            current_bar_use,
             content_ui,
         }
     }
@@ -128,44 +239,221 @@ impl ScrollArea {
         prepared.end(ui);
         ret
     }
+
+    /// Efficiently show many rows of the same height.
+    ///
+    /// `add_contents` is only given the range of rows that are visible, and should add just those.
+    /// The scroll bar is sized as if all `num_rows` rows were there.
+    /// `row_height` does not include `item_spacing`.
+    ///
+    /// ``` ignore
+    /// ScrollArea::from_max_height(200.0).show_rows(ui, row_height, lines.len(), |ui, rows| {
+    ///     for line in &lines[rows] {
+    ///         ui.label(line);
+    ///     }
+    /// });
+    /// ```
+    pub fn show_rows<R>(
+        self,
+        ui: &mut Ui,
+        row_height: f32,
+        num_rows: usize,
+        add_contents: impl FnOnce(&mut Ui, std::ops::Range<usize>) -> R,
+    ) -> R {
+        let row_height_with_spacing = row_height + ui.style().spacing.item_spacing.y;
+        let mut prepared = self.begin(ui);
+        let viewport = prepared.viewport();
+
+        let rows = visible_rows(viewport.y_range(), row_height_with_spacing, num_rows);
+
+        let content_ui = &mut prepared.content_ui;
+        let top_left = content_ui.max_rect().min;
+        let mut rows_ui = content_ui.child_ui(
+            Rect::from_min_size(
+                top_left + vec2(0.0, rows.start as f32 * row_height_with_spacing),
+                content_ui.max_rect().size(),
+            ),
+            *content_ui.layout(),
+        );
+        let ret = add_contents(&mut rows_ui, rows);
+
+        // Make room for all the rows, so the scroll bar knows how far we can scroll:
+        let total_height = (num_rows as f32 * row_height_with_spacing
+            - ui.style().spacing.item_spacing.y)
+            .at_least(0.0);
+        content_ui.allocate_space(vec2(rows_ui.min_size().x, total_height));
+
+        prepared.end(ui);
+        ret
+    }
+
+    /// Efficiently show many rows of different heights.
+    ///
+    /// `add_row` is called for each visible row, and should add just that row.
+    /// The height of each row is remembered in `Memory` once it has been shown.
+    /// Until then it is assumed to be `estimated_row_height`.
+    ///
+    /// Returns what `add_row` returned for each row that was shown, from the top down.
+    pub fn show_variable_rows<R>(
+        self,
+        ui: &mut Ui,
+        estimated_row_height: f32,
+        num_rows: usize,
+        mut add_row: impl FnMut(&mut Ui, usize) -> R,
+    ) -> Vec<R> {
+        let spacing = ui.style().spacing.item_spacing.y;
+        let mut prepared = self.begin(ui);
+        let viewport = prepared.viewport();
+
+        let Prepared {
+            state, content_ui, ..
+        } = &mut prepared;
+        state.row_heights.resize(num_rows, estimated_row_height);
+
+        // Skip the rows above the viewport:
+        let mut row = 0;
+        let mut y = 0.0;
+        while row < num_rows && y + state.row_heights[row] < viewport.top() {
+            y += state.row_heights[row] + spacing;
+            row += 1;
+        }
+
+        let top_left = content_ui.max_rect().min;
+        let mut width = 0.0_f32;
+        let mut ret = vec![];
+        while row < num_rows && y < viewport.bottom() {
+            let mut row_ui = content_ui.child_ui(
+                Rect::from_min_size(top_left + vec2(0.0, y), content_ui.max_rect().size()),
+                *content_ui.layout(),
+            );
+            ret.push(add_row(&mut row_ui, row));
+            let size = row_ui.min_size();
+            state.row_heights[row] = size.y;
+            width = width.max(size.x);
+            y += size.y + spacing;
+            row += 1;
+        }
+
+        let total_height = (state.row_heights.iter().sum::<f32>() + spacing * num_rows as f32
+            - spacing)
+            .at_least(0.0);
+        content_ui.allocate_space(vec2(width, total_height));
+
+        prepared.end(ui);
+        ret
+    }
 }
 
 impl Prepared {
+    /// The visible part of the contents, relative to their top left corner.
+    fn viewport(&self) -> Rect {
+        Rect::from_min_size(
+            pos2(self.state.offset.x, self.state.offset.y),
+            self.inner_rect.size(),
+        )
+    }
+
     fn end(self, ui: &mut Ui) {
         let Prepared {
             id,
             mut state,
+            has_bar,
             inner_rect,
             always_show_scroll,
-            mut current_scroll_bar_width,
+            stick_to_bottom,
+            mut current_bar_use,
             content_ui,
         } = self;
 
         let content_size = content_ui.min_size();
 
-        let inner_rect = Rect::from_min_size(
-            inner_rect.min,
-            vec2(
-                inner_rect.width().max(content_size.x), // Expand width to fit content
-                inner_rect.height(),
-            ),
-        );
+        let mut inner_rect = inner_rect;
+        if !has_bar[0] {
+            // Expand width to fit content:
+            inner_rect.max.x = inner_rect.max.x.max(inner_rect.min.x + content_size.x);
+        }
+        if !has_bar[1] {
+            // Only scrolling sideways, so be as high as the content:
+            inner_rect.max.y = inner_rect.min.y + content_size.y;
+        }
 
-        let outer_rect = Rect::from_min_size(
-            inner_rect.min,
-            inner_rect.size() + vec2(current_scroll_bar_width, 0.0),
-        );
+        let outer_rect = Rect::from_min_size(inner_rect.min, inner_rect.size() + current_bar_use);
 
-        let content_is_too_small = content_size.y > inner_rect.height();
+        let max_offset = content_size - inner_rect.size();
 
-        if content_is_too_small {
+        // Did someone inside us ask to be scrolled to?
+        let content_rect = Rect::from_min_size(inner_rect.min - state.offset, content_size);
+        let scroll_target = {
+            let mut memory = ui.memory();
+            match memory.scroll_target {
+                Some((rect, _)) if content_rect.contains(rect.center()) => {
+                    memory.scroll_target.take()
+                }
+                _ => None,
+            }
+        };
+        if let Some((target, align)) = scroll_target {
+            for d in 0..2 {
+                if has_bar[d] {
+                    let target_min = target.min[d] - content_rect.min[d];
+                    let target_max = target.max[d] - content_rect.min[d];
+                    let to = match align {
+                        Align::Min => target_min,
+                        Align::Center => 0.5 * (target_min + target_max - inner_rect.size()[d]),
+                        Align::Max => target_max - inner_rect.size()[d],
+                    };
+                    state.animation[d] = Some(ScrollAnimation {
+                        from: state.offset[d],
+                        to: clamp(to, 0.0..=max_offset[d].at_least(0.0)),
+                        start_time: ui.input().time,
+                    });
+                }
+            }
+        }
+
+        for d in 0..2 {
+            if let Some(animation) = state.animation[d] {
+                let t = (ui.input().time - animation.start_time) as f32 / SCROLL_ANIMATION_TIME;
+                let t = clamp(t, 0.0..=1.0);
+                state.offset[d] = lerp(animation.from..=animation.to, ease_in_ease_out(t));
+                if t < 1.0 {
+                    ui.ctx().request_repaint();
+                } else {
+                    state.animation[d] = None;
+                }
+            }
+        }
+
+        if stick_to_bottom && state.at_bottom && state.animation[1].is_none() {
+            let bottom = max_offset.y.at_least(0.0);
+            if state.offset.y != bottom {
+                state.offset.y = bottom;
+                ui.ctx().request_repaint(); // The contents were laid out with the old offset
+            }
+        }
+
+        // If the user scrolls, stop any animation:
+        let offset_before_user_input = state.offset;
+
+        let content_is_too_large = [
+            has_bar[0] && content_size.x > inner_rect.width(),
+            has_bar[1] && content_size.y > inner_rect.height(),
+        ];
+
+        if content_is_too_large[0] || content_is_too_large[1] {
             // Drag contents to scroll (for touch screens mostly):
             let content_response = ui.interact(inner_rect, id.with("area"), Sense::drag());
 
             let input = ui.input();
             if content_response.active {
-                state.offset.y -= input.mouse.delta.y;
-                state.vel = input.mouse.velocity;
+                for (d, &too_large) in content_is_too_large.iter().enumerate() {
+                    if too_large {
+                        state.offset[d] -= input.mouse.delta[d];
+                        state.vel[d] = input.mouse.velocity[d];
+                    } else {
+                        state.vel[d] = 0.0;
+                    }
+                }
             } else {
                 let stop_speed = 20.0; // Pixels per second.
                 let friction_coeff = 1000.0; // Pixels per second squared.
@@ -178,7 +466,11 @@ impl Prepared {
                     state.vel -= friction * state.vel.normalized();
                     // Offset has an inverted coordinate system compared to
                     // the velocity, so we subtract it instead of adding it
-                    state.offset.y -= state.vel.y * dt;
+                    for (d, &too_large) in content_is_too_large.iter().enumerate() {
+                        if too_large {
+                            state.offset[d] -= state.vel[d] * dt;
+                        }
+                    }
                     ui.ctx().request_repaint();
                 }
             }
@@ -186,50 +478,72 @@ impl Prepared {
 
         // TODO: check that nothing else is being interacted with
         if ui.contains_mouse(outer_rect) {
-            state.offset.y -= ui.input().scroll_delta.y;
+            let mut scroll_delta = ui.input().scroll_delta;
+            if has_bar[0] && scroll_delta.x == 0.0 && ui.input().key_down(Key::Shift) {
+                // Most mice only have a vertical wheel, so Shift turns it sideways.
+                // Touchpads can already scroll sideways, so leave them be:
+                scroll_delta = vec2(scroll_delta.y, 0.0);
+            }
+            for d in 0..2 {
+                if has_bar[d] {
+                    state.offset[d] -= scroll_delta[d];
+                }
+            }
         }
 
-        let show_scroll_this_frame = content_is_too_small || always_show_scroll;
+        let show_scroll_this_frame = [
+            content_is_too_large[0] || (always_show_scroll && has_bar[0]),
+            content_is_too_large[1] || (always_show_scroll && has_bar[1]),
+        ];
 
         let max_scroll_bar_width = max_scroll_bar_width_with_margin(ui);
 
-        if show_scroll_this_frame && current_scroll_bar_width <= 0.0 {
-            // Avoid frame delay; start showing scroll bar right away:
-            current_scroll_bar_width = max_scroll_bar_width * ui.ctx().animate_bool(id, true);
+        for d in 0..2 {
+            if show_scroll_this_frame[d] && current_bar_use[1 - d] <= 0.0 {
+                // Avoid frame delay; start showing scroll bar right away:
+                current_bar_use[1 - d] =
+                    max_scroll_bar_width * ui.ctx().animate_bool(id.with(d), true);
+            }
         }
 
-        if current_scroll_bar_width > 0.0 {
-            let animation_t = current_scroll_bar_width / max_scroll_bar_width;
+        for d in 0..2 {
+            // The other axis, across the scroll bar:
+            let o = 1 - d;
+            if current_bar_use[o] <= 0.0 {
+                continue;
+            }
+
+            let animation_t = current_bar_use[o] / max_scroll_bar_width;
             // margin between contents and scroll bar
             let margin = animation_t * ui.style().spacing.item_spacing.x;
-            let left = inner_rect.right() + margin;
-            let right = outer_rect.right();
-            let corner_radius = (right - left) / 2.0;
-            let top = inner_rect.top();
-            let bottom = inner_rect.bottom();
-
-            let outer_scroll_rect = Rect::from_min_max(
-                pos2(left, inner_rect.top()),
-                pos2(right, inner_rect.bottom()),
-            );
+
+            let mut min = inner_rect.min;
+            let mut max = inner_rect.max;
+            min[o] = inner_rect.max[o] + margin;
+            max[o] = outer_rect.max[o];
+            let outer_scroll_rect = Rect::from_min_max(min, max);
+            let corner_radius = (max[o] - min[o]) / 2.0;
 
             let from_content =
-                |content_y| remap_clamp(content_y, 0.0..=content_size.y, top..=bottom);
+                |content| remap_clamp(content, 0.0..=content_size[d], min[d]..=max[d]);
+            let handle_rect_for = |offset: f32| {
+                let (mut handle_min, mut handle_max) = (min, max);
+                handle_min[d] = from_content(offset);
+                handle_max[d] = from_content(offset + inner_rect.size()[d]);
+                Rect::from_min_max(handle_min, handle_max)
+            };
 
-            let handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
-            );
+            let handle_rect = handle_rect_for(state.offset[d]);
 
             // intentionally use same id for inside and outside of handle
-            let interact_id = id.with("vertical");
+            let interact_id = id.with(if d == 0 { "horizontal" } else { "vertical" });
             let mut response = ui.interact(handle_rect, interact_id, Sense::click_and_drag());
 
             if let Some(mouse_pos) = ui.input().mouse.pos {
                 if response.active {
-                    if inner_rect.top() <= mouse_pos.y && mouse_pos.y <= inner_rect.bottom() {
-                        state.offset.y +=
-                            ui.input().mouse.delta.y * content_size.y / inner_rect.height();
+                    if inner_rect.min[d] <= mouse_pos[d] && mouse_pos[d] <= inner_rect.max[d] {
+                        state.offset[d] +=
+                            ui.input().mouse.delta[d] * content_size[d] / inner_rect.size()[d];
                     }
                 } else {
                     // Check for mouse down outside handle:
@@ -238,36 +552,32 @@ impl Prepared {
 
                     if scroll_bg_response.active {
                         // Center scroll at mouse pos:
-                        let mpos_top = mouse_pos.y - handle_rect.height() / 2.0;
-                        state.offset.y = remap(mpos_top, top..=bottom, 0.0..=content_size.y);
+                        let mpos_top = mouse_pos[d] - handle_rect.size()[d] / 2.0;
+                        state.offset[d] = remap(mpos_top, min[d]..=max[d], 0.0..=content_size[d]);
                     }
 
                     response = response.union(scroll_bg_response);
                 }
             }
 
-            state.offset.y = state.offset.y.max(0.0);
-            state.offset.y = state.offset.y.min(content_size.y - inner_rect.height());
+            state.offset[d] = state.offset[d].max(0.0);
+            state.offset[d] = state.offset[d].min(content_size[d] - inner_rect.size()[d]);
 
             // Avoid frame-delay by calculating a new handle rect:
-            let mut handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
-            );
-            let min_handle_height = (2.0 * corner_radius).max(8.0);
-            if handle_rect.size().y < min_handle_height {
-                handle_rect = Rect::from_center_size(
-                    handle_rect.center(),
-                    vec2(handle_rect.size().x, min_handle_height),
-                );
+            let mut handle_rect = handle_rect_for(state.offset[d]);
+            let min_handle_length = (2.0 * corner_radius).max(8.0);
+            if handle_rect.size()[d] < min_handle_length {
+                let mut size = handle_rect.size();
+                size[d] = min_handle_length;
+                handle_rect = Rect::from_center_size(handle_rect.center(), size);
             }
 
             let visuals = ui.style().interact(&response);
 
             ui.painter().add(paint::PaintCmd::Rect {
                 rect: outer_scroll_rect,
-                corner_radius,
-                fill: ui.style().visuals.dark_bg_color,
+                rounding: corner_radius.into(),
+                fill: ui.style().visuals.dark_bg_color.into(),
                 stroke: Default::default(),
                 // fill: visuals.bg_fill,
                 // stroke: visuals.bg_stroke,
@@ -275,30 +585,245 @@ impl Prepared {
 
             ui.painter().add(paint::PaintCmd::Rect {
                 rect: handle_rect.expand(-2.0),
-                corner_radius,
-                fill: visuals.fg_fill,
+                rounding: corner_radius.into(),
+                fill: visuals.fg_fill.into(),
                 stroke: visuals.fg_stroke,
             });
         }
 
-        let size = vec2(
-            outer_rect.size().x,
-            outer_rect.size().y.min(content_size.y), // shrink if content is so small that we don't need scroll bars
-        );
-        ui.allocate_space(size);
+        if state.offset != offset_before_user_input {
+            state.animation = [None; 2];
+        }
+
+        let mut size = outer_rect.size();
+        for d in 0..2 {
+            if has_bar[d] {
+                // shrink if content is so small that we don't need scroll bars
+                size[d] = size[d].min(content_size[d] + current_bar_use[d]);
+            }
+        }
+        ui.allocate_child(size);
 
         if show_scroll_this_frame != state.show_scroll {
             ui.ctx().request_repaint();
         }
 
-        state.offset.y = state.offset.y.min(content_size.y - inner_rect.height());
-        state.offset.y = state.offset.y.max(0.0);
+        for d in 0..2 {
+            state.offset[d] = state.offset[d].min(content_size[d] - inner_rect.size()[d]);
+            state.offset[d] = state.offset[d].max(0.0);
+        }
         state.show_scroll = show_scroll_this_frame;
+        state.at_bottom = state.offset.y >= max_offset.y - 1.0;
 
         ui.memory().scroll_areas.insert(id, state);
     }
 }
 
+/// The rows that are (at least partially) within `y_range`,
+/// for rows that are `row_pitch` apart and start at zero.
+pub(crate) fn visible_rows(
+    y_range: std::ops::RangeInclusive<f32>,
+    row_pitch: f32,
+    num_rows: usize,
+) -> std::ops::Range<usize> {
+    if row_pitch.is_nan() || row_pitch <= 0.0 {
+        return 0..0; // Rows without any height can't be seen
+    }
+    let first = (y_range.start() / row_pitch).floor().at_least(0.0) as usize;
+    // Float-to-int casts saturate, so huge or infinite ranges are fine:
+    let last = (y_range.end() / row_pitch).ceil() as usize;
+    first.min(num_rows)..last.min(num_rows)
+}
+
 fn max_scroll_bar_width_with_margin(ui: &Ui) -> f32 {
     ui.style().spacing.item_spacing.x + 16.0
 }
+
+#[cfg(test)]
+fn scroll_with_shift(scroll_area: ScrollArea, scroll_delta: Vec2) -> Vec2 {
+    let mut ctx = Context::new();
+    let mut positions = vec![];
+    for frame in 0..3 {
+        let mut raw_input = RawInput {
+            mouse_pos: Some(pos2(10.0, 10.0)),
+            screen_size: vec2(200.0, 200.0),
+            time: frame as f64,
+            ..Default::default()
+        };
+        if frame == 1 {
+            raw_input.events.push(Event::Key {
+                key: Key::Shift,
+                pressed: true,
+            });
+            raw_input.scroll_delta = scroll_delta;
+        }
+        let mut ui = ctx.begin_frame(raw_input);
+        scroll_area.clone().show(&mut ui, |ui| {
+            ui.horizontal(|ui| {
+                let rect = ui.allocate_space(vec2(1000.0, 1000.0));
+                positions.push(rect.min);
+            });
+        });
+        let _ = ctx.end_frame();
+    }
+    // The scrolling shows up the frame after:
+    assert_eq!(positions[1], positions[0]);
+    positions[2] - positions[0]
+}
+
+#[test]
+fn test_shift_wheel_scrolls_horizontally() {
+    assert_eq!(
+        scroll_with_shift(ScrollArea::horizontal(), vec2(0.0, -50.0)),
+        vec2(-50.0, 0.0)
+    );
+    // A touchpad that scrolls diagonally:
+    assert_eq!(
+        scroll_with_shift(ScrollArea::horizontal(), vec2(-30.0, -50.0)),
+        vec2(-30.0, 0.0)
+    );
+    // Nothing to scroll sideways, so the wheel keeps scrolling down:
+    assert_eq!(
+        scroll_with_shift(ScrollArea::vertical(), vec2(0.0, -50.0)),
+        vec2(0.0, -50.0)
+    );
+}
+
+#[test]
+fn test_visible_rows() {
+    assert_eq!(visible_rows(0.0..=100.0, 24.0, 1000), 0..5);
+    assert_eq!(visible_rows(30.0..=50.0, 24.0, 1000), 1..3);
+    assert_eq!(visible_rows(-50.0..=50.0, 24.0, 1000), 0..3);
+    assert_eq!(visible_rows(0.0..=100.0, 24.0, 3), 0..3);
+    assert_eq!(visible_rows(0.0..=f32::INFINITY, 24.0, 3), 0..3);
+    assert_eq!(
+        visible_rows(1e30..=1e31, 1e-3, usize::MAX),
+        usize::MAX..usize::MAX
+    );
+    assert_eq!(visible_rows(0.0..=100.0, 0.0, 1000), 0..0);
+}
+
+#[test]
+fn test_show_rows() {
+    let mut ctx = Context::new();
+    let mut ranges = vec![];
+    for frame in 0..3 {
+        let raw_input = RawInput {
+            mouse_pos: Some(pos2(10.0, 10.0)),
+            scroll_delta: vec2(0.0, if frame == 1 { -230.0 } else { 0.0 }),
+            screen_size: vec2(200.0, 200.0),
+            time: frame as f64,
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        let row_height = 20.0;
+        let row_height_with_spacing = row_height + ui.style().spacing.item_spacing.y;
+        let offset = if frame == 2 { 230.0 } else { 0.0 };
+        let content_top = ui.available().top() - offset;
+        ScrollArea::from_max_height(100.0).show_rows(&mut ui, row_height, 1000, |ui, rows| {
+            for row in rows.clone() {
+                let rect = ui.allocate_space(vec2(50.0, row_height));
+                // Each row is where it would be if all rows were laid out:
+                assert_eq!(
+                    rect.top(),
+                    content_top + row as f32 * row_height_with_spacing
+                );
+            }
+            ranges.push(rows);
+        });
+        let _ = ctx.end_frame();
+    }
+    assert!(ranges[0].start == 0 && ranges[0].len() < 10, "{:?}", ranges);
+    // Scrolled down ten rows:
+    assert!(
+        ranges[2].start == 10 && ranges[2].len() < 10,
+        "{:?}",
+        ranges
+    );
+}
+
+#[test]
+fn test_show_variable_rows() {
+    let mut ctx = Context::new();
+    let mut shown = vec![];
+    for frame in 0..2 {
+        let raw_input = RawInput {
+            screen_size: vec2(200.0, 200.0),
+            time: frame as f64,
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        shown = ScrollArea::from_max_height(100.0).show_variable_rows(
+            &mut ui,
+            10.0,
+            1000,
+            |ui, row| {
+                // Every other row is twice as high:
+                ui.allocate_space(vec2(50.0, if row % 2 == 0 { 20.0 } else { 40.0 }));
+                row
+            },
+        );
+        let _ = ctx.end_frame();
+    }
+    assert!(shown.len() < 10, "{:?}", shown);
+
+    let memory = ctx.memory();
+    let state = memory.scroll_areas.values().next().unwrap();
+    assert_eq!(&state.row_heights[..4], &[20.0, 40.0, 20.0, 40.0]);
+    assert_eq!(state.row_heights[999], 10.0);
+}
+
+#[test]
+fn test_scroll_to_me() {
+    let mut ctx = Context::new();
+    let mut tops = vec![];
+    for frame in 0..5 {
+        let raw_input = RawInput {
+            screen_size: vec2(200.0, 200.0),
+            time: frame as f64 * 0.1,
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        let area_top = ui.available().top();
+        ScrollArea::from_max_height(100.0).show(&mut ui, |ui| {
+            for i in 0..100 {
+                let rect = ui.allocate_space(vec2(50.0, 20.0));
+                if i == 50 {
+                    if frame == 0 {
+                        ui.scroll_to_rect(rect, Align::Min);
+                    }
+                    tops.push(rect.top() - area_top);
+                }
+            }
+        });
+        let _ = ctx.end_frame();
+    }
+    // Animated, and the new offset shows up the frame after:
+    assert!(tops[2] < tops[0] && tops[2] > 0.0, "{:?}", tops);
+    assert_eq!(tops[4], 0.0);
+}
+
+#[test]
+fn test_stick_to_bottom() {
+    let mut ctx = Context::new();
+    for num_rows in 0..20 {
+        let raw_input = RawInput {
+            screen_size: vec2(200.0, 200.0),
+            time: num_rows as f64,
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        ScrollArea::from_max_height(100.0)
+            .stick_to_bottom(true)
+            .show(&mut ui, |ui| {
+                for _ in 0..num_rows {
+                    ui.allocate_space(vec2(50.0, 20.0));
+                }
+            });
+        let _ = ctx.end_frame();
+    }
+    let memory = ctx.memory();
+    let state = memory.scroll_areas.values().next().unwrap();
+    assert!(state.at_bottom);
+    assert!(state.offset.y > 0.0);
+}
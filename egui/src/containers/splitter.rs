@@ -0,0 +1,316 @@
+//! Two `Ui`s next to each other, with a handle between them that can be dragged.
+
+use std::{hash::Hash, ops::RangeInclusive};
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+enum Side {
+    First,
+    Second,
+}
+
+/// State that is persisted between frames
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct State {
+    /// How much of the space the first side gets, in 0-1.
+    fraction: f32,
+
+    /// The side that was collapsed by double-clicking the handle, if any.
+    /// `fraction` is kept so we can go back to it.
+    collapsed: Option<Side>,
+}
+
+/// Splits the available space in two, e.g. for a file list and a preview of the selected file.
+///
+/// Drag the handle between the two sides to change how much space each gets,
+/// or double-click it to collapse the smaller side (and again to bring it back).
+/// How the space is shared is remembered in [`Memory`].
+///
+/// The splitter fills the available space, so put it in something with a size,
+/// like a [`Resize`] or a panel.
+///
+/// ``` ignore
+/// egui::Splitter::horizontal("files").min_size(100.0, 100.0).show(ui, |left, right| {
+///     left.label("File list");
+///     right.label("Preview");
+/// });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Splitter {
+    id_source: Id,
+    horizontal: bool,
+    default_fraction: f32,
+    min_size: [f32; 2],
+}
+
+impl Splitter {
+    /// The two sides are next to each other, the first one to the left.
+    pub fn horizontal(id_source: impl Hash) -> Self {
+        Self::new(id_source, true)
+    }
+
+    /// The two sides are above each other, the first one at the top.
+    pub fn vertical(id_source: impl Hash) -> Self {
+        Self::new(id_source, false)
+    }
+
+    fn new(id_source: impl Hash, horizontal: bool) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            horizontal,
+            default_fraction: 0.5,
+            min_size: [0.0; 2],
+        }
+    }
+
+    /// How much of the space the first side gets to begin with, in 0-1. Default: `0.5`.
+    pub fn default_fraction(mut self, default_fraction: f32) -> Self {
+        self.default_fraction = default_fraction;
+        self
+    }
+
+    /// Dragging the handle won't make the sides smaller than this (width or height).
+    /// A side can still be collapsed by double-clicking the handle.
+    pub fn min_size(mut self, first: f32, second: f32) -> Self {
+        self.min_size = [first, second];
+        self
+    }
+
+    /// `add_contents` is given the `Ui` of the first and the second side.
+    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui, &mut Ui) -> R) -> R {
+        let Self {
+            id_source,
+            horizontal,
+            default_fraction,
+            min_size,
+        } = self;
+
+        let id = ui.make_child_id(id_source);
+        let mut state = ui.memory().splitters.get(&id).copied().unwrap_or(State {
+            fraction: default_fraction,
+            collapsed: None,
+        });
+
+        let rect = ui.available_finite();
+        let split = SplitHandle::new(ui, rect, horizontal);
+        let range = split.fraction_range(min_size);
+        let shown_fraction = |state: &State| match state.collapsed {
+            Some(Side::First) => 0.0,
+            Some(Side::Second) => 1.0,
+            None => clamp(state.fraction, range.clone()),
+        };
+
+        let mut fraction = shown_fraction(&state);
+        let response = split.interact(ui, id.with("handle"), &mut fraction, range.clone());
+        if response.double_clicked {
+            state.collapsed = match state.collapsed {
+                Some(_) => None,
+                None if fraction < 0.5 => Some(Side::First),
+                None => Some(Side::Second),
+            };
+        } else if fraction != shown_fraction(&state) {
+            state.fraction = fraction;
+            state.collapsed = None;
+        }
+
+        let (first_rect, second_rect) = split.sides(ui, shown_fraction(&state), &response);
+        let mut first_ui = ui.child_ui(first_rect, *ui.layout());
+        first_ui.set_clip_rect(first_rect.intersect(ui.clip_rect()));
+        let mut second_ui = ui.child_ui(second_rect, *ui.layout());
+        second_ui.set_clip_rect(second_rect.intersect(ui.clip_rect()));
+        let ret = add_contents(&mut first_ui, &mut second_ui);
+
+        ui.allocate_space(rect.size());
+        ui.memory().splitters.insert(id, state);
+        ret
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The handle between the two sides of a split, which can be dragged to share the space differently.
+/// Used by [`Splitter`] and [`DockArea`].
+///
+/// Where the handle is, is given as the fraction of the space (not counting the handle) that the first side gets.
+pub(crate) struct SplitHandle {
+    /// Both sides and the handle.
+    rect: Rect,
+    /// 0 if the sides are next to each other, 1 if they are above each other.
+    axis: usize,
+    /// The space between the two sides.
+    thickness: f32,
+}
+
+impl SplitHandle {
+    pub(crate) fn new(ui: &Ui, rect: Rect, horizontal: bool) -> Self {
+        let axis = if horizontal { 0 } else { 1 };
+        Self {
+            rect,
+            axis,
+            thickness: ui.style().spacing.item_spacing[axis],
+        }
+    }
+
+    /// The space shared by the two sides.
+    fn total(&self) -> f32 {
+        (self.rect.size()[self.axis] - self.thickness).at_least(0.0)
+    }
+
+    /// The fractions that give each side at least its min size (width or height).
+    /// If there isn't room for both, the space is shared in proportion to the min sizes.
+    pub(crate) fn fraction_range(&self, min_size: [f32; 2]) -> RangeInclusive<f32> {
+        let total = self.total();
+        if min_size[0] + min_size[1] <= total {
+            (min_size[0] / total)..=(1.0 - min_size[1] / total)
+        } else if min_size[0] + min_size[1] > 0.0 {
+            let fraction = min_size[0] / (min_size[0] + min_size[1]);
+            fraction..=fraction
+        } else {
+            0.0..=1.0
+        }
+    }
+
+    fn handle_rect(&self, fraction: f32) -> Rect {
+        let mut handle_rect = self.rect;
+        handle_rect.min[self.axis] = self.rect.min[self.axis] + fraction * self.total();
+        handle_rect.max[self.axis] = handle_rect.min[self.axis] + self.thickness;
+        handle_rect
+    }
+
+    /// Interact with the handle at `fraction`, and move it to the mouse while it is being dragged,
+    /// keeping it within `range`.
+    pub(crate) fn interact(
+        &self,
+        ui: &Ui,
+        id: Id,
+        fraction: &mut f32,
+        range: RangeInclusive<f32>,
+    ) -> Response {
+        // Easy to grab, also when the space between the sides is thin, like the edge of a panel:
+        let center = self.handle_rect(*fraction).center()[self.axis];
+        let grab_radius = ui.style().interaction.resize_grab_radius_side;
+        let mut grab_rect = self.rect;
+        grab_rect.min[self.axis] = center - grab_radius;
+        grab_rect.max[self.axis] = center + grab_radius;
+        let response = ui.interact(grab_rect, id, Sense::click_and_drag());
+        let total = self.total();
+        if response.active && !ui.input().mouse.could_be_click && total > 0.0 {
+            if let Some(mouse_pos) = ui.input().mouse.pos {
+                let mouse_fraction =
+                    (mouse_pos[self.axis] - self.rect.min[self.axis] - 0.5 * self.thickness)
+                        / total;
+                *fraction = clamp(mouse_fraction, range);
+            }
+        }
+        if response.hovered || response.active {
+            ui.ctx().output().cursor_icon = if self.axis == 0 {
+                CursorIcon::ResizeHorizontal
+            } else {
+                CursorIcon::ResizeVertical
+            };
+        }
+        response
+    }
+
+    /// Paint the handle at `fraction` and return the rectangles of the two sides.
+    pub(crate) fn sides(&self, ui: &Ui, fraction: f32, response: &Response) -> (Rect, Rect) {
+        let handle_rect = self.handle_rect(fraction);
+        let stroke = if response.hovered || response.active {
+            ui.style().interact(response).bg_stroke
+        } else {
+            ui.style().visuals.widgets.noninteractive.bg_stroke
+        };
+        let center = handle_rect.center()[self.axis];
+        let mut line = [handle_rect.min, handle_rect.max];
+        line[0][self.axis] = center;
+        line[1][self.axis] = center;
+        ui.painter().line_segment(line, stroke);
+
+        let mut first_rect = self.rect;
+        first_rect.max[self.axis] = handle_rect.min[self.axis];
+        let mut second_rect = self.rect;
+        second_rect.min[self.axis] = handle_rect.max[self.axis];
+        (first_rect, second_rect)
+    }
+}
+
+#[cfg(test)]
+fn run_splitter(mouse: &[(Pos2, bool)], splitter: Splitter) -> (Rect, Rect) {
+    let mut rects = (Rect::nothing(), Rect::nothing());
+    super::run_mouse_frames(vec2(200.0, 200.0), mouse, |ui| {
+        rects = splitter.show(ui, |first, second| (first.max_rect(), second.max_rect()));
+    });
+    rects
+}
+
+#[test]
+fn test_splitter_min_size() {
+    // Drag the handle (in the middle) all the way to the left:
+    let mouse = [
+        (pos2(100.0, 100.0), false),
+        (pos2(100.0, 100.0), true),
+        (pos2(50.0, 100.0), true),
+        (pos2(0.0, 100.0), true),
+        (pos2(0.0, 100.0), false),
+    ];
+    let splitter = Splitter::horizontal("splitter").min_size(40.0, 40.0);
+    let (first, second) = run_splitter(&mouse, splitter);
+    assert_eq!(first.width(), 40.0);
+    assert_eq!(second.right(), 200.0);
+}
+
+#[test]
+fn test_splitter_grab_radius() {
+    // The handle is 8 wide, centered at 100, but can be grabbed from further away:
+    let mouse = [
+        (pos2(91.0, 100.0), false),
+        (pos2(91.0, 100.0), true),
+        (pos2(70.0, 100.0), true),
+        (pos2(50.0, 100.0), true),
+        (pos2(50.0, 100.0), false),
+    ];
+    let splitter = Splitter::horizontal("splitter");
+    let (first, _) = run_splitter(&mouse, splitter);
+    assert_eq!(first.width(), 46.0);
+
+    let mut style = Style::default();
+    style.interaction.resize_grab_radius_side = 2.0;
+    let mut rects = (Rect::nothing(), Rect::nothing());
+    super::run_mouse_frames(vec2(200.0, 200.0), &mouse, |ui| {
+        ui.ctx().set_style(style.clone());
+        rects = splitter.show(ui, |first, second| (first.max_rect(), second.max_rect()));
+    });
+    assert_eq!(rects.0.width(), 96.0, "too far away to grab");
+}
+
+#[test]
+fn test_splitter_collapse() {
+    let double_click = |pos| {
+        vec![
+            (pos, false),
+            (pos, true),
+            (pos, false),
+            (pos, true),
+            (pos, false),
+        ]
+    };
+    let splitter = Splitter::horizontal("splitter").default_fraction(0.3);
+
+    // Double-click the handle, which is left of center:
+    let mut mouse = double_click(pos2(60.0, 100.0));
+    let (first, second) = run_splitter(&mouse, splitter);
+    assert_eq!(first.width(), 0.0, "the smaller side collapses");
+    assert_eq!(second.left(), 8.0);
+
+    // Wait, so the next click is not a triple-click, then double-click the handle where it is now:
+    mouse.extend(vec![(pos2(4.0, 100.0), false); 10]);
+    mouse.extend(double_click(pos2(4.0, 100.0)));
+    let (first, _) = run_splitter(&mouse, splitter);
+    assert!(
+        (first.width() - 0.3 * 192.0).abs() < 1.0,
+        "back to where it was"
+    );
+}
@@ -0,0 +1,526 @@
+use std::{collections::BTreeSet, hash::Hash};
+
+use crate::{paint::PaintCmd, *};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct State {
+    col_widths: Vec<f32>,
+
+    /// Column index and order.
+    sort: Option<(usize, SortOrder)>,
+
+    /// Indices of the selected rows.
+    selected: BTreeSet<usize>,
+
+    /// The last row clicked without Shift. Shift-click selects from here.
+    anchor: Option<usize>,
+}
+
+impl State {
+    /// Click a header: sort by it, or flip the order if we already do.
+    fn click_header(&mut self, col: usize) {
+        self.sort = match self.sort {
+            Some((sort_col, SortOrder::Ascending)) if sort_col == col => {
+                Some((col, SortOrder::Descending))
+            }
+            _ => Some((col, SortOrder::Ascending)),
+        };
+        // The rows are about to move around, so the indices no longer mean the same rows:
+        self.selected.clear();
+        self.anchor = None;
+    }
+
+    /// Click a row, maybe while holding Shift and/or Ctrl (Cmd on Mac).
+    fn click_row(&mut self, row: usize, shift: bool, ctrl: bool) {
+        match self.anchor {
+            Some(anchor) if shift => {
+                if !ctrl {
+                    self.selected.clear();
+                }
+                self.selected.extend(anchor.min(row)..=anchor.max(row));
+            }
+            _ => {
+                if ctrl {
+                    if !self.selected.remove(&row) {
+                        self.selected.insert(row);
+                    }
+                } else {
+                    self.selected.clear();
+                    self.selected.insert(row);
+                }
+                self.anchor = Some(row);
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A column of a [`Table`].
+#[derive(Clone, Debug)]
+pub struct Column {
+    title: String,
+    initial_width: f32,
+    min_width: f32,
+    sortable: bool,
+}
+
+impl Column {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            initial_width: 100.0,
+            min_width: 20.0,
+            sortable: true,
+        }
+    }
+
+    /// Width before the user has resized the column. Default: `100.0`.
+    pub fn initial_width(mut self, initial_width: f32) -> Self {
+        self.initial_width = initial_width;
+        self
+    }
+
+    /// The user can't make the column narrower than this. Default: `20.0`.
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// If `true` (default), clicking the header sorts by this column.
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// Rows of data under a header of column titles.
+///
+/// The user can resize the columns by dragging the dividers in the header,
+/// sort by clicking a header, and select rows by clicking them (Shift and Ctrl for more than one).
+/// The header stays put while the rows scroll under it,
+/// and only the rows that are scrolled into view are laid out, so a table can have very many rows.
+///
+/// The table doesn't sort your data, but tells you how when the user asks for it:
+///
+/// ``` ignore
+/// egui::Table::new("files")
+///     .column(egui::Column::new("Name").initial_width(200.0))
+///     .column(egui::Column::new("Size"))
+///     .show(ui, |body| {
+///         if body.sort_changed() {
+///             if let Some((col, order)) = body.sort() {
+///                 sort_files(&mut files, col, order);
+///             }
+///         }
+///         body.rows(files.len(), |row| {
+///             let file = &files[row.index()];
+///             row.col(|ui| ui.label(&file.name));
+///             row.col(|ui| ui.label(format!("{} bytes", file.size)));
+///         });
+///     });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Table {
+    id_source: Id,
+    columns: Vec<Column>,
+    row_height: Option<f32>,
+    max_height: f32,
+    striped: bool,
+    selectable: bool,
+}
+
+impl Table {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            columns: vec![],
+            row_height: None,
+            max_height: f32::INFINITY,
+            striped: true,
+            selectable: true,
+        }
+    }
+
+    /// Add a column. Add them in the order they should be shown.
+    pub fn column(mut self, column: Column) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Height of the header and of each row. Default: `style.spacing.interact_size.y`.
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// Scroll the rows if they are higher than this, not counting the header.
+    /// Default: as high as the surrounding `Ui` allows.
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    /// Paint every other row with `style.visuals.faint_bg_color`. Default: `true`.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// If `true` (default), the user can select rows by clicking them.
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, add_body: impl FnOnce(&mut TableBody<'_>) -> R) -> R {
+        let Self {
+            id_source,
+            columns,
+            row_height,
+            max_height,
+            striped,
+            selectable,
+        } = self;
+
+        let id = ui.make_child_id(id_source);
+        let mut state = ui.memory().tables.get(&id).cloned().unwrap_or_default();
+        state.col_widths.truncate(columns.len());
+        for column in &columns[state.col_widths.len()..] {
+            state.col_widths.push(column.initial_width);
+        }
+
+        let row_height = row_height.unwrap_or(ui.style().spacing.interact_size.y);
+        let sort_changed = header_ui(ui, id, &columns, row_height, &mut state);
+
+        let ret = ScrollArea::from_max_height(max_height)
+            .id_source(id)
+            .show(ui, |ui| {
+                let mut body = TableBody {
+                    ui,
+                    id,
+                    state: &mut state,
+                    row_height,
+                    striped,
+                    selectable,
+                    sort_changed,
+                };
+                add_body(&mut body)
+            });
+
+        ui.memory().tables.insert(id, state);
+        ret
+    }
+}
+
+/// Returns `true` if the sorting changed.
+fn header_ui(ui: &mut Ui, id: Id, columns: &[Column], height: f32, state: &mut State) -> bool {
+    let top_left = ui.available().min;
+    let grab_width = ui.style().spacing.item_spacing.x;
+
+    // Interact with the dividers first, so they take precedence over the headers,
+    // and so that we paint the headers with this frame's widths.
+    let mut dividers = vec![];
+    let mut right = top_left.x;
+    for (i, column) in columns.iter().enumerate() {
+        right += state.col_widths[i];
+        let grab_rect = Rect::from_min_max(
+            pos2(right - 0.5 * grab_width, top_left.y),
+            pos2(right + 0.5 * grab_width, top_left.y + height),
+        );
+        let response = ui.interact(grab_rect, id.with("divider").with(i), Sense::drag());
+        if response.active {
+            if let Some(mouse_pos) = ui.input().mouse.pos {
+                let left = right - state.col_widths[i];
+                state.col_widths[i] = (mouse_pos.x - left).max(column.min_width);
+                right = left + state.col_widths[i];
+            }
+        }
+        if response.hovered || response.active {
+            ui.ctx().output().cursor_icon = CursorIcon::ResizeHorizontal;
+        }
+        dividers.push((right, response));
+    }
+
+    let mut sort_changed = false;
+    let mut left = top_left.x;
+    for (i, column) in columns.iter().enumerate() {
+        let rect = Rect::from_min_size(pos2(left, top_left.y), vec2(state.col_widths[i], height));
+        left = rect.right();
+
+        let sense = if column.sortable {
+            Sense::click()
+        } else {
+            Sense::nothing()
+        };
+        let response = ui.interact(rect, id.with("header").with(i), sense);
+        if response.clicked {
+            state.click_header(i);
+            sort_changed = true;
+        }
+
+        let visuals = if column.sortable {
+            ui.style().interact(&response)
+        } else {
+            ui.style().visuals.noninteractive()
+        };
+        let painter = ui.painter().sub_region(rect);
+        painter.rect_filled(rect, 0.0, visuals.bg_fill_in(rect));
+
+        let padding = 0.5 * ui.style().spacing.item_spacing.x;
+        let text_color = ui.style().visuals.text_color();
+        painter.text(
+            pos2(rect.left() + padding, rect.center().y),
+            (Align::Min, Align::Center),
+            &column.title,
+            TextStyle::Button,
+            text_color,
+        );
+
+        if let Some((sort_col, order)) = state.sort {
+            if sort_col == i {
+                let icon_width = ui.style().spacing.icon_width;
+                let icon_rect = Rect::from_center_size(
+                    pos2(rect.right() - padding - 0.5 * icon_width, rect.center().y),
+                    Vec2::splat(0.5 * icon_width),
+                );
+                paint_sort_icon(&painter, icon_rect, order, visuals.fg_stroke.color);
+            }
+        }
+    }
+
+    for (x, response) in dividers {
+        let stroke = if response.hovered || response.active {
+            ui.style().interact(&response).fg_stroke
+        } else {
+            ui.style().visuals.widgets.noninteractive.bg_stroke
+        };
+        ui.painter()
+            .line_segment([pos2(x, top_left.y), pos2(x, top_left.y + height)], stroke);
+    }
+
+    ui.allocate_space(vec2(right - top_left.x, height));
+    sort_changed
+}
+
+/// A triangle pointing up for ascending and down for descending.
+fn paint_sort_icon(painter: &Painter, rect: Rect, order: SortOrder, color: Srgba) {
+    let points = match order {
+        SortOrder::Ascending => vec![rect.left_bottom(), rect.right_bottom(), rect.center_top()],
+        SortOrder::Descending => vec![rect.left_top(), rect.right_top(), rect.center_bottom()],
+    };
+    painter.add(PaintCmd::Path {
+        points,
+        closed: true,
+        fill: color.into(),
+        stroke: Default::default(),
+    });
+}
+
+// ----------------------------------------------------------------------------
+
+/// Passed to the closure of [`Table::show`]. Add the rows with [`Self::rows`].
+pub struct TableBody<'a> {
+    ui: &'a mut Ui,
+    id: Id,
+    state: &'a mut State,
+    row_height: f32,
+    striped: bool,
+    selectable: bool,
+    sort_changed: bool,
+}
+
+impl<'a> TableBody<'a> {
+    /// Which column to sort by, and in what order. `None` until the user clicks a header.
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.state.sort
+    }
+
+    /// Did the user click a header this frame? If so, sort your rows before adding them.
+    pub fn sort_changed(&self) -> bool {
+        self.sort_changed
+    }
+
+    /// Indices of the selected rows.
+    /// The selection is cleared when the sorting changes.
+    pub fn selected(&self) -> &BTreeSet<usize> {
+        &self.state.selected
+    }
+
+    /// Add `num_rows` rows. `add_row_contents` is only called for the rows that are visible.
+    pub fn rows(&mut self, num_rows: usize, mut add_row_contents: impl FnMut(&mut TableRow<'_>)) {
+        let Self {
+            ui,
+            id,
+            state,
+            row_height,
+            striped,
+            selectable,
+            ..
+        } = self;
+        let (id, row_height) = (*id, *row_height);
+
+        let top_left = ui.available().min;
+        let width: f32 = state.col_widths.iter().sum();
+
+        let clip_rect = ui.clip_rect();
+        let visible = (clip_rect.top() - top_left.y)..=(clip_rect.bottom() - top_left.y);
+        for index in super::scroll_area::visible_rows(visible, row_height, num_rows) {
+            let rect = Rect::from_min_size(
+                pos2(top_left.x, top_left.y + index as f32 * row_height),
+                vec2(width, row_height),
+            );
+            let background = ui.painter().add(PaintCmd::Noop);
+
+            let mut row = TableRow {
+                ui,
+                rect,
+                col_widths: &state.col_widths,
+                col_idx: 0,
+                left: rect.left(),
+                index,
+                selected: state.selected.contains(&index),
+            };
+            add_row_contents(&mut row);
+
+            // After the contents, so that clicking e.g. a button in a row doesn't select it:
+            let sense = if *selectable {
+                Sense::click()
+            } else {
+                Sense::nothing()
+            };
+            let response = ui.interact(rect, id.with("row").with(index), sense);
+            if response.clicked {
+                let input = ui.input();
+                let shift = input.key_down(Key::Shift);
+                let ctrl = input.key_down(Key::Control) || input.key_down(Key::Logo);
+                state.click_row(index, shift, ctrl);
+            }
+
+            let visuals = &ui.style().visuals;
+            let fill = if state.selected.contains(&index) {
+                Some(visuals.selection_bg_color.into())
+            } else if *selectable && response.hovered {
+                Some(visuals.widgets.hovered.bg_fill_in(rect))
+            } else if *striped && index % 2 == 1 {
+                Some(visuals.faint_bg_color.into())
+            } else {
+                None
+            };
+            if let Some(fill) = fill {
+                ui.painter()
+                    .set(background, PaintCmd::rect_filled(rect, 0.0, fill));
+            }
+        }
+
+        ui.allocate_space(vec2(width, num_rows as f32 * row_height));
+    }
+}
+
+/// Passed to the closure of [`TableBody::rows`]. Add one cell per column with [`Self::col`].
+pub struct TableRow<'a> {
+    ui: &'a mut Ui,
+    rect: Rect,
+    col_widths: &'a [f32],
+    col_idx: usize,
+    /// Left side of the next cell.
+    left: f32,
+    index: usize,
+    selected: bool,
+}
+
+impl<'a> TableRow<'a> {
+    /// Which row this is, counted from the top.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn selected(&self) -> bool {
+        self.selected
+    }
+
+    /// Add the contents of the next cell. Contents wider than the column are clipped.
+    pub fn col<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        let width = self.col_widths.get(self.col_idx).copied().unwrap_or(0.0);
+        let rect = Rect::from_min_size(
+            pos2(self.left, self.rect.top()),
+            vec2(width, self.rect.height()),
+        );
+        self.left += width;
+        self.col_idx += 1;
+
+        let padding = 0.5 * self.ui.style().spacing.item_spacing.x;
+        let mut cell_ui = self.ui.child_ui(
+            rect.expand2(vec2(-padding, 0.0)),
+            Layout::horizontal(Align::Center),
+        );
+        cell_ui.set_clip_rect(rect.intersect(self.ui.clip_rect()));
+        add_contents(&mut cell_ui)
+    }
+}
+
+#[test]
+fn test_row_selection() {
+    let mut state = State::default();
+    state.click_row(2, false, false);
+    state.click_row(5, true, false);
+    assert_eq!(state.selected, (2..=5).collect());
+
+    state.click_row(8, false, true);
+    state.click_row(4, false, true);
+    assert_eq!(state.selected, vec![2, 3, 5, 8].into_iter().collect());
+
+    // Shift-click from the last Ctrl-clicked row:
+    state.click_row(6, true, false);
+    assert_eq!(state.selected, (4..=6).collect());
+
+    state.click_header(0);
+    assert!(state.selected.is_empty());
+    assert_eq!(state.sort, Some((0, SortOrder::Ascending)));
+    state.click_header(0);
+    assert_eq!(state.sort, Some((0, SortOrder::Descending)));
+}
+
+#[test]
+fn test_only_visible_rows_are_laid_out() {
+    let mut ctx = Context::new();
+    for (frame, mouse_down) in [false, true, false].iter().enumerate() {
+        let raw_input = RawInput {
+            mouse_down: *mouse_down,
+            mouse_pos: Some(pos2(50.0, 70.0)),
+            screen_size: vec2(800.0, 600.0),
+            time: frame as f64,
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        let mut laid_out = vec![];
+        let selected = Table::new("table")
+            .column(Column::new("Name"))
+            .row_height(20.0)
+            .max_height(100.0)
+            .show(&mut ui, |body| {
+                body.rows(1000, |row| {
+                    laid_out.push(row.index());
+                    row.col(|ui| ui.label("Hello"));
+                });
+                body.selected().clone()
+            });
+        let _ = ctx.end_frame();
+
+        // Five rows fit, and the clip rect margin lets a sixth peek out:
+        assert_eq!(laid_out, (0..6).collect::<Vec<_>>());
+        if frame == 2 {
+            // The header is 20 high, so we clicked the third row:
+            assert_eq!(selected, vec![2].into_iter().collect());
+        }
+    }
+}
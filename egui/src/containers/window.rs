@@ -311,14 +311,14 @@ impl<'open> Window<'open> {
                     &mut area_content_ui,
                     outer_rect,
                     interaction,
-                    ctx.style().visuals.widgets.active,
+                    &ctx.style().visuals.widgets.active,
                 );
             } else if let Some(hover_interaction) = hover_interaction {
                 paint_frame_interaction(
                     &mut area_content_ui,
                     outer_rect,
                     hover_interaction,
-                    ctx.style().visuals.widgets.hovered,
+                    &ctx.style().visuals.widgets.hovered,
                 );
             }
         }
@@ -546,7 +546,7 @@ fn paint_frame_interaction(
     ui: &mut Ui,
     rect: Rect,
     interaction: WindowInteraction,
-    visuals: style::WidgetVisuals,
+    visuals: &style::WidgetVisuals,
 ) {
     use paint::tessellator::path::add_circle_quadrant;
 
@@ -13,6 +13,7 @@ use crate::{animation_manager::AnimationManager, paint::*, *};
 #[derive(Clone, Copy, Default)]
 struct PaintStats {
     num_jobs: usize,
+    num_merged_jobs: usize,
     num_primitives: usize,
     num_vertices: usize,
     num_triangles: usize,
@@ -53,6 +54,10 @@ pub struct Context {
 
     paint_stats: Mutex<PaintStats>,
 
+    /// What is left of the screen after the panels added so far this frame.
+    /// `None` until the first panel.
+    available_rect: Mutex<Option<Rect>>,
+
     /// While positive, keep requesting repaints. Decrement at the end of each frame.
     repaint_requests: AtomicU32,
 }
@@ -69,6 +74,7 @@ impl Clone for Context {
             output: Mutex::new(self.output.lock().clone()),
             used_ids: Mutex::new(self.used_ids.lock().clone()),
             paint_stats: Mutex::new(*self.paint_stats.lock()),
+            available_rect: Mutex::new(*self.available_rect.lock()),
             repaint_requests: self.repaint_requests.load(SeqCst).into(),
         }
     }
@@ -83,6 +89,17 @@ impl Context {
         Rect::from_min_size(pos2(0.0, 0.0), self.input.screen_size)
     }
 
+    /// The part of the screen not yet taken by any panel this frame.
+    /// This is where windows are placed.
+    pub fn available_rect(&self) -> Rect {
+        self.available_rect.lock().unwrap_or_else(|| self.rect())
+    }
+
+    /// Called by panels when they take space from the screen.
+    pub(crate) fn set_available_rect(&self, rect: Rect) {
+        *self.available_rect.lock() = Some(rect);
+    }
+
     pub fn memory(&self) -> MutexGuard<'_, Memory> {
         lock(&self.memory, "memory")
     }
@@ -138,6 +155,15 @@ impl Context {
         lock(&self.options, "options").style = style.into();
     }
 
+    pub fn paint_options(&self) -> paint::PaintOptions {
+        lock(&self.options, "options").paint_options
+    }
+
+    /// Controls the tessellator, e.g. for backends that need `cpu_clipping`.
+    pub fn set_paint_options(&self, paint_options: paint::PaintOptions) {
+        lock(&self.options, "options").paint_options = paint_options;
+    }
+
     pub fn pixels_per_point(&self) -> f32 {
         self.input.pixels_per_point()
     }
@@ -181,6 +207,7 @@ impl Context {
         self.memory().begin_frame(&self.input);
 
         self.used_ids.lock().clear();
+        *self.available_rect.lock() = None;
 
         self.input = std::mem::take(&mut self.input).begin_frame(new_raw_input);
         let mut font_definitions = lock(&self.options, "options").font_definitions.clone();
@@ -198,6 +225,16 @@ impl Context {
     /// Returns what has happened this frame (`Output`) as well as what you need to paint.
     #[must_use]
     pub fn end_frame(&self) -> (Output, PaintJobs) {
+        let (output, paint_commands) = self.end_frame_commands();
+        (output, self.tessellate(paint_commands))
+    }
+
+    /// Like `end_frame`, but returns the paint commands before they are tessellated.
+    ///
+    /// Use this to e.g. export the frame with `paint::svg::paint_commands_to_svg`,
+    /// and call `tessellate` to get what you need to paint.
+    #[must_use]
+    pub fn end_frame_commands(&self) -> (Output, Vec<(Rect, PaintCmd)>) {
         if self.input.wants_repaint() {
             self.request_repaint();
         }
@@ -210,8 +247,8 @@ impl Context {
             output.needs_repaint = true;
         }
 
-        let paint_jobs = self.paint();
-        (output, paint_jobs)
+        let paint_commands = self.drain_paint_lists();
+        (output, paint_commands)
     }
 
     fn drain_paint_lists(&self) -> Vec<(Rect, PaintCmd)> {
@@ -219,17 +256,23 @@ impl Context {
         self.graphics().drain(memory.areas.order()).collect()
     }
 
-    fn paint(&self) -> PaintJobs {
+    /// Tessellate the paint commands of a frame (from `end_frame_commands`) into triangles, ready for painting.
+    pub fn tessellate(&self, paint_commands: Vec<(Rect, PaintCmd)>) -> PaintJobs {
         let mut paint_options = lock(&self.options, "options").paint_options;
         paint_options.aa_size = 1.0 / self.pixels_per_point();
-        let paint_commands = self.drain_paint_lists();
         let num_primitives = paint_commands.len();
-        let paint_jobs =
+        #[cfg(not(feature = "rayon"))]
+        let mut paint_jobs =
             tessellator::tessellate_paint_commands(paint_commands, paint_options, self.fonts());
+        #[cfg(feature = "rayon")]
+        let mut paint_jobs =
+            tessellator::tessellate_paint_commands_par(paint_commands, paint_options, self.fonts());
+        let num_merged_jobs = tessellator::merge_adjacent_jobs(&mut paint_jobs);
 
         {
             let mut stats = PaintStats::default();
             stats.num_jobs = paint_jobs.len();
+            stats.num_merged_jobs = num_merged_jobs;
             stats.num_primitives = num_primitives;
             for (_, triangles) in &paint_jobs {
                 stats.num_vertices += triangles.vertices.len();
@@ -630,6 +673,8 @@ impl paint::PaintOptions {
             coarse_tessellation_culling,
             debug_paint_clip_rects,
             debug_ignore_clip_rects,
+            cpu_clipping,
+            tolerance,
         } = self;
         ui.checkbox(anti_alias, "Antialias");
         ui.checkbox(
@@ -638,6 +683,14 @@ impl paint::PaintOptions {
         );
         ui.checkbox(debug_paint_clip_rects, "Paint clip rectangles (debug)");
         ui.checkbox(debug_ignore_clip_rects, "Ignore clip rectangles (debug)");
+        ui.checkbox(cpu_clipping, "Clip on the CPU")
+            .on_hover_text("Clip triangles in the tessellator instead of with scissor rectangles, and merge them into fewer meshes");
+        ui.add(
+            Slider::f32(tolerance, 0.01..=10.0)
+                .logarithmic(true)
+                .text("Curve tolerance"),
+        )
+        .on_hover_text("Maximum distance between a curve and its approximation, in points");
     }
 }
 
@@ -645,6 +698,8 @@ impl PaintStats {
     pub fn ui(&self, ui: &mut Ui) {
         ui.label(format!("Jobs: {}", self.num_jobs))
             .on_hover_text("Number of separate clip rectangles");
+        ui.label(format!("Merged jobs: {}", self.num_merged_jobs))
+            .on_hover_text("Jobs with the same clip rectangle and texture as the one before, merged to save draw calls");
         ui.label(format!("Primitives: {}", self.num_primitives))
             .on_hover_text("Boxes, circles, text areas etc");
         ui.label(format!("Vertices: {}", self.num_vertices));
@@ -108,8 +108,8 @@ impl FrameHistory {
 
         let mut cmds = vec![PaintCmd::Rect {
             rect,
-            corner_radius: style.corner_radius,
-            fill: ui.style().visuals.dark_bg_color,
+            rounding: style.rounding,
+            fill: ui.style().visuals.dark_bg_color.into(),
             stroke: ui.style().noninteractive().bg_stroke,
         }];
 
@@ -211,8 +211,17 @@ impl DemoApp {
             self.previous_web_location_hash = env.web_location_hash.clone();
         }
 
-        show_menu_bar(ui, &mut self.open_windows, env);
-        self.windows(ui.ctx(), env);
+        let ctx = ui.ctx().clone();
+        TopPanel::top("menu_bar").show(&ctx, |ui| {
+            show_menu_bar(ui, &mut self.open_windows, env);
+        });
+        if self.open_windows.side_panel {
+            SidePanel::left("side_panel", 160.0).show(&ctx, |ui| {
+                ui.heading("Side panel");
+                ui.label("Drag my right edge to resize me. Windows stay out of my way.");
+            });
+        }
+        self.windows(&ctx, env);
     }
 
     /// Show the open windows.
@@ -438,6 +447,7 @@ struct OpenWindows {
     inspection: bool,
     memory: bool,
     resize: bool,
+    side_panel: bool,
 }
 
 impl Default for OpenWindows {
@@ -459,6 +469,7 @@ impl OpenWindows {
             inspection: false,
             memory: false,
             resize: false,
+            side_panel: false,
         }
     }
 }
@@ -485,6 +496,7 @@ fn show_menu_bar(ui: &mut Ui, windows: &mut OpenWindows, env: &DemoEnvironment)
                 inspection,
                 memory,
                 resize,
+                side_panel,
             } = windows;
             ui.checkbox(demo, "Demo");
             ui.checkbox(fractal_clock, "Fractal Clock");
@@ -493,6 +505,8 @@ fn show_menu_bar(ui: &mut Ui, windows: &mut OpenWindows, env: &DemoEnvironment)
             ui.checkbox(inspection, "Inspection");
             ui.checkbox(memory, "Memory");
             ui.checkbox(resize, "Resize examples");
+            ui.separator();
+            ui.checkbox(side_panel, "Side panel");
         });
         menu::menu(ui, "About", |ui| {
             ui.label("This is Egui");
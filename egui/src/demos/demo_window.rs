@@ -9,6 +9,9 @@ pub struct DemoWindow {
     widgets: Widgets,
     colors: ColorWidgets,
     layout: LayoutDemo,
+    grid: GridDemo,
+    table: TableDemo,
+    scroll_to: ScrollToDemo,
     tree: Tree,
     box_painting: BoxPainting,
     painting: Painting,
@@ -22,6 +25,9 @@ impl Default for DemoWindow {
             widgets: Default::default(),
             colors: Default::default(),
             layout: Default::default(),
+            grid: Default::default(),
+            table: Default::default(),
+            scroll_to: Default::default(),
             tree: Tree::demo(),
             box_painting: Default::default(),
             painting: Default::default(),
@@ -56,6 +62,47 @@ impl DemoWindow {
             .default_open(false)
             .show(ui, |ui| self.layout.ui(ui));
 
+        CollapsingHeader::new("Grid")
+            .default_open(false)
+            .show(ui, |ui| self.grid.ui(ui));
+
+        CollapsingHeader::new("Table")
+            .default_open(false)
+            .show(ui, |ui| self.table.ui(ui));
+
+        CollapsingHeader::new("Dock area")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label("Drag a tab onto another group, or onto the side of a group to split it.");
+                Resize::default()
+                    .default_size(vec2(360.0, 200.0))
+                    .show(ui, |ui| {
+                        DockArea::new("dock_demo")
+                            .tabs(&["Scene", "Inspector", "Console", "Assets"])
+                            .show(ui, |ui, tab| {
+                                ui.label(format!("This is the {} tab.", tab));
+                            });
+                    });
+            });
+
+        CollapsingHeader::new("Splitter")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label("Drag the handle in the middle, or double-click it to collapse a side.");
+                Resize::default()
+                    .default_size(vec2(360.0, 120.0))
+                    .show(ui, |ui| {
+                        Splitter::horizontal("splitter_demo")
+                            .min_size(60.0, 60.0)
+                            .show(ui, |left, right| {
+                                for file in &["main.rs", "lib.rs", "Cargo.toml"] {
+                                    left.label(*file);
+                                }
+                                right.label("A preview of the selected file would go here.");
+                            });
+                    });
+            });
+
         CollapsingHeader::new("Tree")
             .default_open(false)
             .show(ui, |ui| self.tree.ui(ui));
@@ -82,6 +129,54 @@ impl DemoWindow {
                 ScrollArea::from_max_height(200.0).show(ui, |ui| {
                     ui.label(LOREM_IPSUM_LONG);
                 });
+
+                self.scroll_to.ui(ui);
+
+                ui.label("Scroll sideways with Shift + mouse wheel:");
+                ScrollArea::horizontal()
+                    .id_source("horizontal")
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            for i in 0..50 {
+                                let _ = ui.button(format!("Button {}", i));
+                            }
+                        });
+                    });
+
+                ui.label("100 000 rows, of which only the visible ones are laid out:");
+                let row_height = ui.fonts()[TextStyle::Body].height();
+                ScrollArea::from_max_height(100.0)
+                    .id_source("rows")
+                    .show_rows(ui, row_height, 100_000, |ui, rows| {
+                        for row in rows {
+                            ui.label(format!("Row {}", row));
+                        }
+                    });
+
+                ui.label("Rows of different heights:");
+                ScrollArea::from_max_height(100.0)
+                    .id_source("variable_rows")
+                    .show_variable_rows(ui, row_height, 1_000, |ui, row| {
+                        if row % 3 == 0 {
+                            ui.label(format!("Row {} is a bit\ntaller than the others", row));
+                        } else {
+                            ui.label(format!("Row {}", row));
+                        }
+                    });
+
+                ui.label("Both ways:");
+                ScrollArea::both()
+                    .id_source("both")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        for row in 0..30 {
+                            ui.horizontal(|ui| {
+                                for col in 0..20 {
+                                    ui.label(format!("({}, {})", col, row));
+                                }
+                            });
+                        }
+                    });
             });
 
         CollapsingHeader::new("Painting")
@@ -253,8 +348,8 @@ impl BoxPainting {
         let mut cmds = vec![];
         for i in 0..self.num_boxes {
             cmds.push(paint::PaintCmd::Rect {
-                corner_radius: self.corner_radius,
-                fill: Srgba::gray(64),
+                rounding: self.corner_radius.into(),
+                fill: Srgba::gray(64).into(),
                 rect: Rect::from_min_size(
                     pos2(10.0 + pos.x + (i as f32) * (self.size.x * 1.1), pos.y),
                     self.size,
@@ -340,6 +435,216 @@ impl Painting {
 
 // ----------------------------------------------------------------------------
 
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct GridDemo {
+    striped: bool,
+    right_align_labels: bool,
+    name: String,
+    age: i32,
+    volume: f32,
+    visible: bool,
+}
+
+impl Default for GridDemo {
+    fn default() -> Self {
+        Self {
+            striped: true,
+            right_align_labels: true,
+            name: "Ferris".to_owned(),
+            age: 42,
+            volume: 0.5,
+            visible: true,
+        }
+    }
+}
+
+impl GridDemo {
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.striped, "Striped");
+        ui.checkbox(&mut self.right_align_labels, "Right-align labels");
+
+        let label_align = if self.right_align_labels {
+            Align::Max
+        } else {
+            Align::Min
+        };
+
+        let Self {
+            striped,
+            name,
+            age,
+            volume,
+            visible,
+            ..
+        } = self;
+
+        Grid::new("demo_grid")
+            .striped(*striped)
+            .col_align(0, label_align)
+            .show(ui, |grid| {
+                grid.row(|row| {
+                    row.col(|ui| ui.label("Name"));
+                    row.col(|ui| ui.text_edit(name));
+                });
+                grid.row(|row| {
+                    row.col(|ui| ui.label("Age"));
+                    row.col(|ui| ui.add(DragValue::i32(age)));
+                });
+                grid.row(|row| {
+                    row.col(|ui| ui.label("Volume"));
+                    row.col(|ui| ui.add(Slider::f32(volume, 0.0..=1.0)));
+                });
+                grid.row(|row| {
+                    row.col(|ui| ui.label("Visible"));
+                    row.col(|ui| ui.checkbox(visible, ""));
+                });
+            });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct TableDemo {
+    num_rows: usize,
+    /// The order to show the rows in.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    order: Vec<usize>,
+}
+
+impl Default for TableDemo {
+    fn default() -> Self {
+        Self {
+            num_rows: 10_000,
+            order: vec![],
+        }
+    }
+}
+
+impl TableDemo {
+    fn name(row: usize) -> String {
+        format!("file_{:05}.txt", row)
+    }
+
+    /// Made up, but stable.
+    fn size(row: usize) -> usize {
+        (row * 7919) % 100_000
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.add(Slider::usize(&mut self.num_rows, 0..=100_000).text("rows"));
+        if self.order.len() != self.num_rows {
+            self.order = (0..self.num_rows).collect();
+        }
+
+        let order = &mut self.order;
+        let num_selected = Table::new("demo_table")
+            .column(Column::new("Name").initial_width(140.0))
+            .column(Column::new("Size").initial_width(80.0))
+            .column(Column::new("Notes").sortable(false))
+            .max_height(200.0)
+            .show(ui, |body| {
+                if body.sort_changed() {
+                    if let Some((col, sort_order)) = body.sort() {
+                        match col {
+                            0 => order.sort_by_key(|&row| Self::name(row)),
+                            _ => order.sort_by_key(|&row| Self::size(row)),
+                        }
+                        if sort_order == SortOrder::Descending {
+                            order.reverse();
+                        }
+                    }
+                }
+                body.rows(order.len(), |row| {
+                    let data_row = order[row.index()];
+                    let selected = row.selected();
+                    row.col(|ui| ui.label(Self::name(data_row)));
+                    row.col(|ui| ui.label(format!("{} kB", Self::size(data_row))));
+                    row.col(|ui| {
+                        if selected {
+                            ui.label("Selected");
+                        }
+                    });
+                });
+                body.selected().len()
+            });
+        ui.label(format!(
+            "{} rows selected. Shift-click to select a range, Ctrl-click to add or remove one.",
+            num_selected
+        ));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct ScrollToDemo {
+    item: usize,
+    num_log_lines: usize,
+}
+
+impl Default for ScrollToDemo {
+    fn default() -> Self {
+        Self {
+            item: 42,
+            num_log_lines: 10,
+        }
+    }
+}
+
+impl ScrollToDemo {
+    pub fn ui(&mut self, ui: &mut Ui) {
+        let mut scroll_to = None;
+        ui.horizontal(|ui| {
+            ui.label("Scroll to item");
+            ui.add(Slider::usize(&mut self.item, 0..=99));
+            for &(name, align) in &[
+                ("top", Align::Min),
+                ("center", Align::Center),
+                ("bottom", Align::Max),
+            ] {
+                if ui.button(name).clicked {
+                    scroll_to = Some(align);
+                }
+            }
+        });
+
+        let item = self.item;
+        ScrollArea::from_max_height(100.0)
+            .id_source("scroll_to")
+            .show(ui, |ui| {
+                for i in 0..100 {
+                    let response = ui.label(format!("Item {}", i));
+                    if i == item {
+                        if let Some(align) = scroll_to {
+                            response.scroll_to_me(align);
+                        }
+                    }
+                }
+            });
+
+        ui.horizontal(|ui| {
+            ui.label("A log that sticks to the bottom while you are scrolled down:");
+            if ui.button("Add line").clicked {
+                self.num_log_lines += 1;
+            }
+        });
+        ScrollArea::from_max_height(100.0)
+            .id_source("log")
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for i in 0..self.num_log_lines {
+                    ui.label(format!("Log line {}", i));
+                }
+            });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 use crate::layout::*;
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -348,6 +653,7 @@ struct LayoutDemo {
     dir: Direction,
     align: Option<Align>, // None == justified
     reversed: bool,
+    wrap: bool,
 }
 
 impl Default for LayoutDemo {
@@ -356,18 +662,16 @@ impl Default for LayoutDemo {
             dir: Direction::Vertical,
             align: Some(Align::Center),
             reversed: false,
+            wrap: false,
         }
     }
 }
 
 impl LayoutDemo {
     fn layout(&self) -> Layout {
-        let layout = Layout::from_dir_align(self.dir, self.align);
-        if self.reversed {
-            layout.reverse()
-        } else {
-            layout
-        }
+        Layout::from_dir_align(self.dir, self.align)
+            .with_reversed(self.reversed)
+            .with_wrap(self.wrap)
     }
 
     pub fn ui(&mut self, ui: &mut Ui) {
@@ -398,6 +702,8 @@ impl LayoutDemo {
         }
 
         ui.checkbox(&mut self.reversed, "Reversed");
+        ui.checkbox(&mut self.wrap, "Wrap")
+            .on_hover_text("Start a new row when the next widget doesn't fit (horizontal only)");
 
         ui.separator();
 
@@ -1,5 +1,7 @@
 //! The input needed by Egui.
 
+use std::collections::HashSet;
+
 use crate::{math::*, History};
 
 /// If mouse moves more than this, it is no longer a click (but maybe a drag)
@@ -82,6 +84,9 @@ pub struct InputState {
 
     /// In-order events received this frame
     pub events: Vec<Event>,
+
+    /// Which keys are currently held down, e.g. `Key::Shift`.
+    pub keys_down: HashSet<Key>,
 }
 
 /// What egui maintains
@@ -165,7 +170,7 @@ pub enum Event {
 }
 
 /// Keyboard key name. Only covers keys used by Egui.
-#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Key {
     Alt,
     Backspace,
@@ -195,6 +200,16 @@ impl InputState {
     pub fn begin_frame(self, new: RawInput) -> InputState {
         let mouse = self.mouse.begin_frame(&new);
         let unstable_dt = (new.time - self.raw.time) as f32;
+        let mut keys_down = self.keys_down;
+        for event in &new.events {
+            if let Event::Key { key, pressed } = event {
+                if *pressed {
+                    keys_down.insert(*key);
+                } else {
+                    keys_down.remove(key);
+                }
+            }
+        }
         InputState {
             mouse,
             scroll_delta: new.scroll_delta,
@@ -204,6 +219,7 @@ impl InputState {
             unstable_dt,
             predicted_dt: 1.0 / 60.0,   // TODO: remove this hack
             events: new.events.clone(), // TODO: remove clone() and use raw.events
+            keys_down,
             raw: new,
         }
     }
@@ -242,6 +258,11 @@ impl InputState {
         })
     }
 
+    /// Is the given key currently held down?
+    pub fn key_down(&self, desired_key: Key) -> bool {
+        self.keys_down.contains(&desired_key)
+    }
+
     /// Also known as device pixel ratio, > 1 for HDPI screens.
     pub fn pixels_per_point(&self) -> f32 {
         self.pixels_per_point.unwrap_or(1.0)
@@ -370,6 +391,7 @@ impl InputState {
             unstable_dt,
             predicted_dt,
             events,
+            keys_down,
         } = self;
 
         ui.collapsing("Raw Input", |ui| raw.ui(ui));
@@ -394,6 +416,7 @@ impl InputState {
         ui.label(format!("expected dt: {:.1} ms", 1e3 * predicted_dt));
         ui.label(format!("events: {:?}", events))
             .on_hover_text("key presses etc");
+        ui.label(format!("keys_down: {:?}", keys_down));
     }
 }
 
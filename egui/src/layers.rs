@@ -1,6 +1,10 @@
 use ahash::AHashMap;
 
-use crate::{math::Rect, paint::PaintCmd, Id};
+use crate::{
+    math::{pos2, Rect},
+    paint::{PaintCmd, Rounding},
+    Id,
+};
 
 /// Different layer categories
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -40,6 +44,13 @@ impl Layer {
 #[derive(Clone, Copy, PartialEq)]
 pub struct PaintCmdIdx(usize);
 
+impl PaintCmdIdx {
+    /// Is this the first `PaintCmd` of its layer?
+    pub(crate) fn is_first(self) -> bool {
+        self.0 == 0
+    }
+}
+
 /// Each `PaintCmd` is paired with a clip rectangle.
 #[derive(Clone, Default)]
 pub struct PaintList(Vec<(Rect, PaintCmd)>);
@@ -67,6 +78,65 @@ impl PaintList {
         assert!(idx.0 < self.0.len());
         self.0[idx.0] = (clip_rect, cmd);
     }
+
+    /// Clip all commands added after `idx` to a rounded rectangle (see `PaintCmd::Clipped`).
+    ///
+    /// Useful for clipping the contents of a frame once we know how large the frame is.
+    /// Commands whose clip rectangle keeps them away from the rounded corners are left as they are.
+    /// Existing `PaintCmdIdx` stay valid.
+    pub fn clip_after(&mut self, idx: PaintCmdIdx, rect: Rect, rounding: Rounding) {
+        let mut group_start: Option<usize> = None;
+        for i in (idx.0 + 1)..self.0.len() {
+            let clip_rect = self.0[i].0;
+            let bounds = match &self.0[i].1 {
+                PaintCmd::Clipped { rect, .. } => clip_rect.intersect(*rect),
+                _ => clip_rect,
+            };
+            if let PaintCmd::Noop = self.0[i].1 {
+                // Leave room for anyone that still wants to `set` this:
+                group_start = None;
+                continue;
+            }
+            if is_within_rounded_rect(bounds, rect, rounding) {
+                group_start = None;
+                continue;
+            }
+
+            // Group consecutive commands with the same clip rect into the first of them:
+            let cmd = std::mem::replace(&mut self.0[i].1, PaintCmd::Noop);
+            match group_start {
+                Some(start) if self.0[start].0 == clip_rect => {
+                    if let PaintCmd::Clipped { commands, .. } = &mut self.0[start].1 {
+                        commands.push(cmd);
+                    }
+                }
+                _ => {
+                    self.0[i].1 = PaintCmd::Clipped {
+                        rect,
+                        rounding,
+                        commands: vec![cmd],
+                    };
+                    group_start = Some(i);
+                }
+            }
+        }
+    }
+}
+
+/// Is all of `bounds` inside the rounded rectangle, so there is no need to clip to it?
+fn is_within_rounded_rect(bounds: Rect, rect: Rect, rounding: Rounding) -> bool {
+    let Rect { min, max } = rect;
+    let Rounding { nw, ne, sw, se } = rounding;
+    let corners = [
+        Rect::from_min_max(min, pos2(min.x + nw, min.y + nw)),
+        Rect::from_min_max(pos2(max.x - ne, min.y), pos2(max.x, min.y + ne)),
+        Rect::from_min_max(pos2(min.x, max.y - sw), pos2(min.x + sw, max.y)),
+        Rect::from_min_max(pos2(max.x - se, max.y - se), max),
+    ];
+    rect.union(bounds) == rect
+        && corners
+            .iter()
+            .all(|corner| corner.width() <= 0.0 || !corner.intersects(bounds))
 }
 
 // TODO: improve GraphicLayers
@@ -97,3 +167,46 @@ impl GraphicLayers {
         all_commands.into_iter()
     }
 }
+
+#[test]
+fn test_clip_after() {
+    use crate::paint::{color, Stroke};
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+    let inside = Rect::from_min_max(pos2(10.0, 10.0), pos2(90.0, 90.0));
+    let circle = |clip_rect: Rect| {
+        (
+            clip_rect,
+            PaintCmd::circle_stroke(clip_rect.center(), 5.0, Stroke::new(1.0, color::WHITE)),
+        )
+    };
+
+    let mut list = PaintList::default();
+    let idx = list.add(rect, PaintCmd::Noop);
+    list.0.push(circle(inside));
+    list.0.push(circle(rect));
+    list.0.push(circle(rect));
+    // A nested clipped frame, inside the corners:
+    list.0.push((
+        rect,
+        PaintCmd::Clipped {
+            rect: inside,
+            rounding: Rounding::same(4.0),
+            commands: vec![circle(inside).1],
+        },
+    ));
+    list.clip_after(idx, rect, Rounding::same(8.0));
+
+    let is_clipped: Vec<bool> = list
+        .0
+        .iter()
+        .map(|(_, cmd)| match cmd {
+            PaintCmd::Clipped { rounding, .. } => rounding.nw == 8.0,
+            _ => false,
+        })
+        .collect();
+    assert_eq!(is_clipped, vec![false, false, true, false, false]);
+    match &list.0[2].1 {
+        PaintCmd::Clipped { commands, .. } => assert_eq!(commands.len(), 2),
+        _ => unreachable!(),
+    }
+}
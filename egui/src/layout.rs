@@ -34,6 +34,9 @@ pub struct Layout {
 
     /// Lay out things in reversed order, i.e. from the right or bottom-up.
     reversed: bool,
+
+    /// Horizontal layouts only: start a new row when the next thing doesn't fit on the current one.
+    wrap: bool,
 }
 
 impl Default for Layout {
@@ -42,6 +45,7 @@ impl Default for Layout {
             dir: Direction::Vertical,
             align: Some(Align::Min),
             reversed: false,
+            wrap: false,
         }
     }
 }
@@ -53,6 +57,7 @@ impl Layout {
             dir,
             align,
             reversed: false,
+            wrap: false,
         }
     }
 
@@ -61,6 +66,7 @@ impl Layout {
             dir: Direction::Vertical,
             align: Some(align),
             reversed: false,
+            wrap: false,
         }
     }
 
@@ -69,6 +75,7 @@ impl Layout {
             dir: Direction::Horizontal,
             align: Some(align),
             reversed: false,
+            wrap: false,
         }
     }
 
@@ -79,15 +86,15 @@ impl Layout {
             dir,
             align: None,
             reversed: false,
+            wrap: false,
         }
     }
 
     #[must_use]
     pub fn reverse(self) -> Self {
         Self {
-            dir: self.dir,
-            align: self.align,
             reversed: !self.reversed,
+            ..self
         }
     }
 
@@ -100,6 +107,16 @@ impl Layout {
         }
     }
 
+    /// Wrap a horizontal layout onto a new row when the next widget doesn't fit,
+    /// like the words of a paragraph.
+    /// Each row is as tall as the tallest widget in it.
+    /// Rows below the first one are top-aligned, as they have no height until filled.
+    /// Has no effect on vertical layouts.
+    #[must_use]
+    pub fn with_wrap(self, wrap: bool) -> Self {
+        Self { wrap, ..self }
+    }
+
     pub fn dir(self) -> Direction {
         self.dir
     }
@@ -112,6 +129,10 @@ impl Layout {
         self.reversed
     }
 
+    pub fn is_wrapping(self) -> bool {
+        self.wrap && self.dir == Direction::Horizontal
+    }
+
     pub fn initial_cursor(self, max_rect: Rect) -> Pos2 {
         match self.dir {
             Direction::Horizontal => {
@@ -143,6 +164,16 @@ impl Layout {
                 } else {
                     rect.min.x = cursor.x;
                 }
+                if self.is_wrapping() {
+                    // Anything up to a full row fits, if need be on the next row:
+                    if self.reversed {
+                        rect.min.x = rect.min.x.min(cursor.x - max_rect.width());
+                    } else {
+                        rect.max.x = rect.max.x.max(cursor.x + max_rect.width());
+                    }
+                    // Rows below `max_rect` have no height of their own to align within:
+                    rect.max.y = rect.max.y.max(cursor.y);
+                }
             }
             Direction::Vertical => {
                 rect.min.x = cursor.x;
@@ -205,6 +236,33 @@ impl Layout {
         rect
     }
 
+    /// For wrapping layouts: if a child of `child_size` doesn't fit on the current row of `max_rect`,
+    /// move the cursor to the start of the next row, `item_spacing` below `row_bottom`.
+    /// The first child of a row always stays, even if it is too wide.
+    pub fn wrap_cursor(
+        self,
+        cursor: &mut Pos2,
+        max_rect: Rect,
+        row_bottom: f32,
+        child_size: Vec2,
+        item_spacing: Vec2,
+    ) {
+        if !self.is_wrapping() {
+            return;
+        }
+
+        let row_start = self.initial_cursor(max_rect).x;
+        let fits = if self.reversed {
+            cursor.x - child_size.x >= max_rect.left()
+        } else {
+            cursor.x + child_size.x <= max_rect.right()
+        };
+        let at_row_start = (cursor.x - row_start).abs() < 1.0;
+        if !fits && !at_row_start {
+            *cursor = pos2(row_start, row_bottom + item_spacing.y);
+        }
+    }
+
     /// Reserve this much space and move the cursor.
     /// Returns where to put the widget.
     ///
@@ -311,3 +369,62 @@ impl Layout {
         painter.text(cursor, align, "cursor", TextStyle::Monospace, color);
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_horizontal_wrapping() {
+    use crate::*;
+    let mut ctx = Context::new();
+    let raw_input = RawInput {
+        screen_size: vec2(200.0, 200.0),
+        ..Default::default()
+    };
+    let mut ui = ctx.begin_frame(raw_input);
+    let spacing = ui.style().spacing.item_spacing;
+    let mut rects = vec![];
+    ui.horizontal_wrapped(|ui| {
+        for &height in &[20.0, 30.0, 20.0] {
+            rects.push(ui.allocate_space(vec2(80.0, height)));
+        }
+    });
+    let _ = ctx.end_frame();
+
+    assert_eq!(rects[1].top(), rects[0].top(), "two fit on the first row");
+    assert_eq!(rects[2].left(), rects[0].left(), "the third wraps");
+    assert_eq!(
+        rects[2].top(),
+        rects[1].bottom() + spacing.y,
+        "below the tallest"
+    );
+}
+
+#[test]
+fn test_horizontal_wrapping_nested() {
+    use crate::*;
+    let mut ctx = Context::new();
+    let raw_input = RawInput {
+        screen_size: vec2(200.0, 200.0),
+        ..Default::default()
+    };
+    let mut ui = ctx.begin_frame(raw_input);
+    let spacing = ui.style().spacing.item_spacing;
+    let (mut first, mut inner, mut reserved) = (Rect::nothing(), Rect::nothing(), Rect::nothing());
+    let mut max_rect = Rect::nothing();
+    ui.horizontal_wrapped(|ui| {
+        max_rect = ui.max_rect();
+        first = ui.allocate_space(vec2(120.0, 20.0));
+        reserved = ui
+            .vertical(|ui| {
+                inner = ui.allocate_space(vec2(120.0, 20.0));
+            })
+            .1
+            .rect;
+    });
+    let _ = ctx.end_frame();
+
+    assert_eq!(inner, reserved, "the contents are where their space is");
+    assert_eq!(inner.left(), first.left(), "on the next row");
+    assert_eq!(inner.top(), first.bottom() + spacing.y);
+    assert!(inner.right() <= max_rect.right());
+}
@@ -1,4 +1,4 @@
-use std::ops::{Add, AddAssign, RangeInclusive, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Index, IndexMut, RangeInclusive, Sub, SubAssign};
 
 use crate::math::*;
 
@@ -92,6 +92,29 @@ impl PartialEq for Pos2 {
 }
 impl Eq for Pos2 {}
 
+/// `v[0] == v.x`, `v[1] == v.y`
+impl Index<usize> for Pos2 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Pos2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
 impl AddAssign<Vec2> for Pos2 {
     fn add_assign(&mut self, rhs: Vec2) {
         *self = Pos2 {
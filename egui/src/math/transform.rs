@@ -0,0 +1,161 @@
+use std::ops::Mul;
+
+use crate::math::*;
+
+/// A 2D affine transform: rotation, scaling and skewing followed by a translation.
+///
+/// A point `p` is mapped to `translation + p.x * x_axis + p.y * y_axis`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Transform {
+    /// Where the x axis `(1, 0)` ends up.
+    pub x_axis: Vec2,
+    /// Where the y axis `(0, 1)` ends up.
+    pub y_axis: Vec2,
+    /// Where the origin ends up.
+    pub translation: Vec2,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Self {
+            x_axis: vec2(1.0, 0.0),
+            y_axis: vec2(0.0, 1.0),
+            translation: Vec2::zero(),
+        }
+    }
+
+    pub fn translation(translation: Vec2) -> Self {
+        Self {
+            translation,
+            ..Self::identity()
+        }
+    }
+
+    /// Scale each axis independently, around the origin.
+    pub fn scale(scale: Vec2) -> Self {
+        Self {
+            x_axis: vec2(scale.x, 0.0),
+            y_axis: vec2(0.0, scale.y),
+            translation: Vec2::zero(),
+        }
+    }
+
+    /// Rotate around the origin.
+    /// Positive angles (in radians) rotate clockwise on screen, since y goes down.
+    pub fn rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            x_axis: vec2(cos, sin),
+            y_axis: vec2(-sin, cos),
+            translation: Vec2::zero(),
+        }
+    }
+
+    /// Rotate around the given point.
+    pub fn rotation_around(angle: f32, pivot: Pos2) -> Self {
+        Self::translation(-pivot.to_vec2())
+            .then(Self::rotation(angle))
+            .then(Self::translation(pivot.to_vec2()))
+    }
+
+    /// Skew (shear) around the origin.
+    /// `skew.x` moves points horizontally by this much per unit of `y`, and vice versa.
+    pub fn skew(skew: Vec2) -> Self {
+        Self {
+            x_axis: vec2(1.0, skew.y),
+            y_axis: vec2(skew.x, 1.0),
+            translation: Vec2::zero(),
+        }
+    }
+
+    /// First apply `self`, then `next`.
+    pub fn then(self, next: Transform) -> Self {
+        Self {
+            x_axis: next.transform_vec2(self.x_axis),
+            y_axis: next.transform_vec2(self.y_axis),
+            translation: next.transform_vec2(self.translation) + next.translation,
+        }
+    }
+
+    pub fn transform_pos(&self, pos: Pos2) -> Pos2 {
+        let v = self.translation + pos.x * self.x_axis + pos.y * self.y_axis;
+        pos2(v.x, v.y)
+    }
+
+    /// Transforms a direction or size. Ignores the translation.
+    pub fn transform_vec2(&self, v: Vec2) -> Vec2 {
+        v.x * self.x_axis + v.y * self.y_axis
+    }
+
+    /// The smallest rectangle containing the transformed `rect`.
+    pub fn transform_rect(&self, rect: Rect) -> Rect {
+        let mut bounds = Rect::nothing();
+        for &corner in &[
+            rect.left_top(),
+            rect.right_top(),
+            rect.left_bottom(),
+            rect.right_bottom(),
+        ] {
+            bounds.extend_with(self.transform_pos(corner));
+        }
+        bounds
+    }
+
+    /// Returns `None` if the transform squashes everything onto a line or a point.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let x_axis = vec2(self.y_axis.y, -self.x_axis.y) / det;
+        let y_axis = vec2(-self.y_axis.x, self.x_axis.x) / det;
+        let linear = Self {
+            x_axis,
+            y_axis,
+            translation: Vec2::zero(),
+        };
+        Some(Self {
+            translation: -linear.transform_vec2(self.translation),
+            ..linear
+        })
+    }
+}
+
+/// `a * b` applies `b` first, then `a`.
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+    fn mul(self, rhs: Transform) -> Transform {
+        rhs.then(self)
+    }
+}
+
+impl Mul<Pos2> for Transform {
+    type Output = Pos2;
+    fn mul(self, pos: Pos2) -> Pos2 {
+        self.transform_pos(pos)
+    }
+}
+
+#[test]
+fn test_transform() {
+    let pivot = pos2(10.0, 20.0);
+    let t = Transform::rotation_around(TAU / 4.0, pivot);
+    assert_eq!(t.transform_pos(pivot), pivot);
+
+    let p = t.transform_pos(pos2(11.0, 20.0));
+    assert!((p - pos2(10.0, 21.0)).length() < 1e-5);
+
+    let t = Transform::skew(vec2(0.5, 0.0))
+        .then(Transform::scale(vec2(2.0, 3.0)))
+        .then(Transform::translation(vec2(5.0, 7.0)));
+    let p = pos2(3.0, 4.0);
+    let round_trip = t.inverse().unwrap().transform_pos(t.transform_pos(p));
+    assert!((round_trip - p).length() < 1e-5);
+}
@@ -1,4 +1,6 @@
-use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, RangeInclusive, Sub, SubAssign};
+use std::ops::{
+    Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, RangeInclusive, Sub, SubAssign,
+};
 
 use crate::math::*;
 
@@ -152,6 +154,29 @@ impl PartialEq for Vec2 {
 }
 impl Eq for Vec2 {}
 
+/// `v[0] == v.x`, `v[1] == v.y`
+impl Index<usize> for Vec2 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Vec2 index out of bounds: {}", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Vec2 index out of bounds: {}", index),
+        }
+    }
+}
+
 impl Neg for Vec2 {
     type Output = Vec2;
 
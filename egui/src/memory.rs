@@ -3,11 +3,11 @@ use std::collections::{HashMap, HashSet};
 use crate::{
     area,
     cache::Cache,
-    collapsing_header, menu,
+    collapsing_header, dock, grid, menu,
     paint::color::{Hsva, Srgba},
-    resize, scroll_area,
+    panel, resize, scroll_area, splitter, table,
     widgets::text_edit,
-    window, Id, Layer, Pos2, Rect,
+    window, Align, Id, Layer, Pos2, Rect,
 };
 
 /// The data that Egui persists between frames.
@@ -25,10 +25,15 @@ pub struct Memory {
 
     // states of various types of widgets
     pub(crate) collapsing_headers: HashMap<Id, collapsing_header::State>,
+    pub(crate) docks: HashMap<Id, dock::State>,
+    pub(crate) grids: HashMap<Id, grid::State>,
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) menu_bar: HashMap<Id, menu::BarState>,
+    pub(crate) panels: HashMap<Id, panel::State>,
     pub(crate) resize: HashMap<Id, resize::State>,
     pub(crate) scroll_areas: HashMap<Id, scroll_area::State>,
+    pub(crate) splitters: HashMap<Id, splitter::State>,
+    pub(crate) tables: HashMap<Id, table::State>,
     pub(crate) text_edit: HashMap<Id, text_edit::State>,
 
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -41,6 +46,10 @@ pub struct Memory {
 
     pub(crate) areas: Areas,
 
+    /// Set by `Ui::scroll_to_rect` for the surrounding `ScrollArea` to act on this frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) scroll_target: Option<(Rect, Align)>,
+
     /// Used by color picker
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) color_cache: Cache<Srgba, Hsva>,
@@ -134,6 +143,7 @@ pub struct Areas {
 impl Memory {
     pub(crate) fn begin_frame(&mut self, prev_input: &crate::input::InputState) {
         self.interaction.begin_frame(prev_input);
+        self.scroll_target = None;
 
         if !prev_input.mouse.down || prev_input.mouse.pos.is_none() {
             // mouse was not down last frame
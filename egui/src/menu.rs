@@ -46,7 +46,7 @@ pub fn bar<R>(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Respo
             style.visuals.widgets.active.bg_stroke = Stroke::none();
             // style.visuals.widgets.hovered.bg_fill = TRANSPARENT;
             style.visuals.widgets.hovered.bg_stroke = Stroke::none();
-            style.visuals.widgets.inactive.bg_fill = TRANSPARENT;
+            style.visuals.widgets.inactive.bg_fill = TRANSPARENT.into();
             style.visuals.widgets.inactive.bg_stroke = Stroke::none();
             ui.set_style(style);
 
@@ -107,7 +107,7 @@ fn menu_impl<'c>(
                 style.visuals.widgets.active.bg_stroke = Stroke::none();
                 // style.visuals.widgets.hovered.bg_fill = TRANSPARENT;
                 style.visuals.widgets.hovered.bg_stroke = Stroke::none();
-                style.visuals.widgets.inactive.bg_fill = TRANSPARENT;
+                style.visuals.widgets.inactive.bg_fill = TRANSPARENT.into();
                 style.visuals.widgets.inactive.bg_stroke = Stroke::none();
                 ui.set_style(style);
                 ui.with_layout(Layout::justified(Direction::Vertical), add_contents);
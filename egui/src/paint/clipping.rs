@@ -0,0 +1,480 @@
+//! Clipping of already tessellated triangles on the CPU.
+//!
+//! The GPU backends clip with a scissor rectangle, which can't do rounded corners,
+//! and some backends can't clip at all.
+
+use super::{
+    color::{Rgba, Srgba},
+    command::Rounding,
+    tessellator::{path, Triangles, Vertex},
+};
+use crate::math::*;
+
+/// Clip `triangles` to a rounded rectangle, appending the result to `out`.
+///
+/// The edge of the rectangle is feathered over a band `feathering` points wide, to anti-alias it.
+/// Colors and texture coordinates of cut triangles are interpolated, just like the GPU would.
+pub fn clip_to_rounded_rect(
+    triangles: &Triangles,
+    rect: Rect,
+    rounding: Rounding,
+    feathering: f32,
+    out: &mut Triangles,
+) {
+    let half_feathering = 0.5 * feathering.at_least(0.0);
+    let rounding = rounding.at_most(0.5 * rect.width().min(rect.height()));
+    let mask = RoundedRect { rect, rounding };
+
+    let outer = ConvexPolygon::rounded_rect(
+        rect.expand(half_feathering),
+        rounding.map(|r| if r > 0.0 { r + half_feathering } else { 0.0 }),
+    );
+    let inner = if half_feathering > 0.0 {
+        let inner_rect = rect.shrink(half_feathering);
+        if inner_rect.width() > 0.0 && inner_rect.height() > 0.0 {
+            Some(ConvexPolygon::rounded_rect(
+                inner_rect,
+                rounding.map(|r| (r - half_feathering).at_least(0.0)),
+            ))
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    let bounds = rect.expand(half_feathering);
+
+    // Triangles that are completely inside can keep sharing vertices:
+    let mut new_index = vec![None; triangles.vertices.len()];
+
+    let mut polygon = Vec::with_capacity(16);
+    let mut scratch = Vec::with_capacity(16);
+    let mut pieces = Vec::new();
+
+    for triangle in triangles.indices.chunks_exact(3) {
+        let vertices = [
+            triangles.vertices[triangle[0] as usize],
+            triangles.vertices[triangle[1] as usize],
+            triangles.vertices[triangle[2] as usize],
+        ];
+
+        let fully_covered = vertices
+            .iter()
+            .all(|v| mask.signed_distance(v.pos) <= -half_feathering);
+        if fully_covered {
+            for &index in triangle {
+                let new = new_index[index as usize].get_or_insert_with(|| {
+                    out.vertices.push(triangles.vertices[index as usize]);
+                    out.vertices.len() as u32 - 1
+                });
+                out.indices.push(*new);
+            }
+            continue;
+        }
+
+        if vertices.iter().all(|v| v.pos.x < bounds.min.x)
+            || vertices.iter().all(|v| v.pos.x > bounds.max.x)
+            || vertices.iter().all(|v| v.pos.y < bounds.min.y)
+            || vertices.iter().all(|v| v.pos.y > bounds.max.y)
+        {
+            continue; // Completely outside
+        }
+
+        polygon.clear();
+        polygon.extend_from_slice(&vertices);
+        outer.clip(&mut polygon, &mut scratch);
+        if polygon.len() < 3 {
+            continue;
+        }
+
+        // Cut the polygon along the inner edge of the feathering,
+        // so the coverage can be linearly interpolated between the vertices of each piece:
+        pieces.clear();
+        match &inner {
+            Some(inner) => inner.split(&polygon, &mut scratch, &mut pieces),
+            None => pieces.push(polygon.clone()),
+        }
+
+        for piece in &mut pieces {
+            if half_feathering > 0.0 {
+                for vertex in piece.iter_mut() {
+                    let distance = mask.signed_distance(vertex.pos);
+                    let coverage = clamp((half_feathering - distance) / feathering, 0.0..=1.0);
+                    if coverage < 1.0 {
+                        vertex.color = Rgba::from(vertex.color).multiply(coverage).into();
+                    }
+                }
+            }
+            add_convex_polygon(piece, out);
+        }
+    }
+}
+
+/// Clip `triangles` to `rect`, appending the result to `out`.
+///
+/// Colors and texture coordinates of cut triangles are interpolated, just like the GPU would.
+pub fn clip_to_rect(triangles: &Triangles, rect: Rect, out: &mut Triangles) {
+    let sides: [&dyn Fn(Pos2) -> f32; 4] = [
+        &|p| p.x - rect.min.x,
+        &|p| rect.max.x - p.x,
+        &|p| p.y - rect.min.y,
+        &|p| rect.max.y - p.y,
+    ];
+    let inside = |p: Pos2| sides.iter().all(|side| side(p) >= 0.0);
+
+    // Triangles that are completely inside can keep sharing vertices:
+    let mut new_index = vec![None; triangles.vertices.len()];
+
+    let mut polygon = Vec::with_capacity(8);
+    let mut scratch = Vec::with_capacity(8);
+
+    for triangle in triangles.indices.chunks_exact(3) {
+        let vertices = [
+            triangles.vertices[triangle[0] as usize],
+            triangles.vertices[triangle[1] as usize],
+            triangles.vertices[triangle[2] as usize],
+        ];
+
+        if vertices.iter().all(|v| inside(v.pos)) {
+            for &index in triangle {
+                let new = new_index[index as usize].get_or_insert_with(|| {
+                    out.vertices.push(triangles.vertices[index as usize]);
+                    out.vertices.len() as u32 - 1
+                });
+                out.indices.push(*new);
+            }
+            continue;
+        }
+
+        polygon.clear();
+        polygon.extend_from_slice(&vertices);
+        for side in &sides {
+            clip_by_half_plane(&polygon, side, &mut scratch);
+            std::mem::swap(&mut polygon, &mut scratch);
+            if polygon.len() < 3 {
+                break;
+            }
+        }
+        add_convex_polygon(&polygon, out);
+    }
+}
+
+fn add_convex_polygon(polygon: &[Vertex], out: &mut Triangles) {
+    if polygon.len() < 3 {
+        return;
+    }
+    let first = out.vertices.len() as u32;
+    out.vertices.extend_from_slice(polygon);
+    for i in 1..polygon.len() as u32 - 1 {
+        out.add_triangle(first, first + i, first + i + 1);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+struct RoundedRect {
+    rect: Rect,
+    rounding: Rounding,
+}
+
+impl RoundedRect {
+    /// Negative inside, positive outside.
+    fn signed_distance(&self, pos: Pos2) -> f32 {
+        let rel = pos - self.rect.center();
+        let half_size = 0.5 * self.rect.size();
+        let Rounding { nw, ne, sw, se } = self.rounding;
+        let radius = match (rel.x >= 0.0, rel.y >= 0.0) {
+            (false, false) => nw,
+            (true, false) => ne,
+            (false, true) => sw,
+            (true, true) => se,
+        };
+        let q = vec2(
+            rel.x.abs() - half_size.x + radius,
+            rel.y.abs() - half_size.y + radius,
+        );
+        vec2(q.x.max(0.0), q.y.max(0.0)).length() + q.x.max(q.y).min(0.0) - radius
+    }
+}
+
+/// A convex polygon, used for cutting up other convex polygons.
+struct ConvexPolygon {
+    points: Vec<Pos2>,
+    /// `1.0` or `-1.0`, so that the inside of every edge is where `side() >= 0`.
+    orientation: f32,
+}
+
+impl ConvexPolygon {
+    fn rounded_rect(rect: Rect, rounding: Rounding) -> Self {
+        let mut points = vec![];
+        path::rounded_rectangle(&mut points, rect, rounding);
+        // Tiny edges have unreliable directions, so skip them:
+        points.dedup_by(|a, b| a.distance_sq(*b) < 1e-6);
+        if points.len() > 1 && points[0].distance_sq(points[points.len() - 1]) < 1e-6 {
+            points.pop();
+        }
+        let orientation = if signed_area(&points) >= 0.0 {
+            1.0
+        } else {
+            -1.0
+        };
+        Self {
+            points,
+            orientation,
+        }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Pos2, Pos2)> + '_ {
+        let n = self.points.len();
+        (0..n).map(move |i| (self.points[i], self.points[(i + 1) % n]))
+    }
+
+    /// Keep only what is inside of this polygon.
+    fn clip(&self, polygon: &mut Vec<Vertex>, scratch: &mut Vec<Vertex>) {
+        for (a, b) in self.edges() {
+            clip_by_half_plane(polygon, |p| self.side(a, b, p), scratch);
+            std::mem::swap(polygon, scratch);
+            if polygon.len() < 3 {
+                return;
+            }
+        }
+    }
+
+    /// Split `polygon` into convex pieces that are each either inside or outside of this polygon.
+    ///
+    /// The outside pieces are also cut where the closest point on this polygon changes from an edge to a corner,
+    /// so that the distance to this polygon is close to linear within each piece.
+    fn split(&self, polygon: &[Vertex], scratch: &mut Vec<Vertex>, pieces: &mut Vec<Vec<Vertex>>) {
+        let mut remaining = polygon.to_vec();
+        let mut outside = vec![];
+        let mut beside = vec![];
+        for (a, b) in self.edges() {
+            clip_by_half_plane(&remaining, |p| -self.side(a, b, p), &mut outside);
+            if outside.len() >= 3 {
+                let dir = b - a;
+                let along = |p: Pos2| (p - a).x * dir.x + (p - a).y * dir.y;
+                let length_sq = dir.length_sq();
+
+                // Before `a`:
+                clip_by_half_plane(&outside, |p| -along(p), &mut beside);
+                if beside.len() >= 3 {
+                    pieces.push(beside.clone());
+                }
+                // After `b`:
+                clip_by_half_plane(&outside, |p| along(p) - length_sq, &mut beside);
+                if beside.len() >= 3 {
+                    pieces.push(beside.clone());
+                }
+                // Next to the edge:
+                clip_by_half_plane(&outside, along, &mut beside);
+                clip_by_half_plane(&beside, |p| length_sq - along(p), scratch);
+                if scratch.len() >= 3 {
+                    pieces.push(scratch.clone());
+                }
+            }
+
+            clip_by_half_plane(&remaining, |p| self.side(a, b, p), scratch);
+            std::mem::swap(&mut remaining, scratch);
+            if remaining.len() < 3 {
+                return;
+            }
+        }
+        pieces.push(remaining);
+    }
+
+    /// Positive on the inside of the edge from `a` to `b`.
+    fn side(&self, a: Pos2, b: Pos2, p: Pos2) -> f32 {
+        self.orientation * cross(b - a, p - a)
+    }
+}
+
+/// Keep the part of the convex `polygon` where `side >= 0`.
+fn clip_by_half_plane(polygon: &[Vertex], side: impl Fn(Pos2) -> f32, out: &mut Vec<Vertex>) {
+    out.clear();
+    let n = polygon.len();
+    for i in 0..n {
+        let a = &polygon[i];
+        let b = &polygon[(i + 1) % n];
+        let side_a = side(a.pos);
+        let side_b = side(b.pos);
+        if side_a >= 0.0 {
+            out.push(*a);
+        }
+        if (side_a >= 0.0) != (side_b >= 0.0) {
+            out.push(lerp_vertex(a, b, side_a / (side_a - side_b)));
+        }
+    }
+}
+
+/// Interpolate position, texture coordinates and color (in linear space, like the GPU does).
+fn lerp_vertex(a: &Vertex, b: &Vertex, t: f32) -> Vertex {
+    Vertex {
+        pos: a.pos + t * (b.pos - a.pos),
+        uv: a.uv + t * (b.uv - a.uv),
+        color: Srgba::from(lerp(Rgba::from(a.color)..=Rgba::from(b.color), t)),
+    }
+}
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+fn signed_area(points: &[Pos2]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| cross(points[i].to_vec2(), points[(i + 1) % n].to_vec2()))
+        .sum::<f32>()
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+fn area(triangles: &Triangles) -> f32 {
+    triangles
+        .indices
+        .chunks_exact(3)
+        .map(|t| {
+            let [a, b, c] = [0, 1, 2].map(|i| triangles.vertices[t[i] as usize].pos);
+            0.5 * cross(b - a, c - a).abs()
+        })
+        .sum()
+}
+
+#[test]
+fn test_clip_to_rounded_rect_inside() {
+    let mut triangles = Triangles::default();
+    triangles.add_colored_rect(
+        Rect::from_min_max(pos2(20.0, 20.0), pos2(80.0, 80.0)),
+        super::color::WHITE,
+    );
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+    let mut out = Triangles::default();
+    clip_to_rounded_rect(&triangles, rect, Rounding::same(10.0), 2.0, &mut out);
+    assert_eq!(out.indices, triangles.indices);
+    let key = |v: &Vertex| (v.pos, v.uv, v.color);
+    assert_eq!(
+        out.vertices.iter().map(key).collect::<Vec<_>>(),
+        triangles.vertices.iter().map(key).collect::<Vec<_>>(),
+        "nothing to cut or fade"
+    );
+}
+
+#[test]
+fn test_clip_to_rounded_rect_corners() {
+    let mut triangles = Triangles::default();
+    triangles.add_colored_rect(
+        Rect::from_min_max(pos2(-50.0, -50.0), pos2(150.0, 150.0)),
+        super::color::WHITE,
+    );
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 50.0));
+    let radius = 10.0;
+    let mask = RoundedRect {
+        rect,
+        rounding: Rounding::same(radius),
+    };
+
+    let mut out = Triangles::default();
+    clip_to_rounded_rect(&triangles, rect, Rounding::same(radius), 0.0, &mut out);
+    for vertex in &out.vertices {
+        assert!(mask.signed_distance(vertex.pos) < 1e-3, "{:?}", vertex.pos);
+        assert_eq!(vertex.color, super::color::WHITE, "no feathering");
+    }
+    let expected = rect.area() - (4.0 - std::f32::consts::PI) * radius * radius;
+    assert!(
+        (area(&out) - expected).abs() < 0.01 * expected,
+        "{} vs {}",
+        area(&out),
+        expected
+    );
+}
+
+#[test]
+fn test_clip_to_rounded_rect_feathering() {
+    let mut triangles = Triangles::default();
+    triangles.add_colored_rect(
+        Rect::from_min_max(pos2(-50.0, -50.0), pos2(150.0, 150.0)),
+        super::color::WHITE,
+    );
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 50.0));
+    let rounding = Rounding::same(10.0);
+    let feathering = 2.0;
+    let mask = RoundedRect { rect, rounding };
+
+    let mut out = Triangles::default();
+    clip_to_rounded_rect(&triangles, rect, rounding, feathering, &mut out);
+    let mut faded = 0;
+    for vertex in &out.vertices {
+        let distance = mask.signed_distance(vertex.pos);
+        assert!(distance < 0.5 * feathering + 1e-3, "{:?}", vertex.pos);
+        let alpha = vertex.color.a();
+        if distance <= -0.5 * feathering + 1e-3 {
+            assert_eq!(alpha, 255, "inside the feathering: {:?}", vertex.pos);
+        } else if distance >= 0.5 * feathering - 1e-3 {
+            assert_eq!(alpha, 0, "outside the feathering: {:?}", vertex.pos);
+            faded += 1;
+        }
+    }
+    assert!(faded > 0, "the outer edge of the feathering is transparent");
+}
+
+#[test]
+fn test_clip_to_rect_interpolation() {
+    use super::color::srgba;
+
+    let corners = [
+        (pos2(0.0, 0.0), pos2(0.0, 0.0), srgba(255, 0, 0, 255)),
+        (pos2(100.0, 0.0), pos2(1.0, 0.0), srgba(0, 255, 0, 255)),
+        (pos2(0.0, 100.0), pos2(0.0, 1.0), srgba(0, 0, 255, 128)),
+    ];
+    let mut triangles = Triangles::default();
+    for &(pos, uv, color) in &corners {
+        triangles.vertices.push(Vertex { pos, uv, color });
+    }
+    triangles.add_triangle(0, 1, 2);
+
+    // Cuts off the corners at the top left and the top right:
+    let rect = Rect::from_min_max(pos2(10.0, -10.0), pos2(80.0, 50.0));
+    let mut out = Triangles::default();
+    clip_to_rect(&triangles, rect, &mut out);
+    assert!(out.vertices.len() > 3, "the triangle was cut");
+
+    for vertex in &out.vertices {
+        assert!(rect.expand(1e-3).contains(vertex.pos), "{:?}", vertex.pos);
+
+        // Barycentric coordinates in the original triangle:
+        let [a, b, c] = corners.map(|(pos, _, _)| pos);
+        let area = cross(b - a, c - a);
+        let w = [
+            cross(c - b, vertex.pos - b) / area,
+            cross(a - c, vertex.pos - c) / area,
+            cross(b - a, vertex.pos - a) / area,
+        ];
+
+        let uv = (0..3).fold(Vec2::zero(), |sum, i| sum + w[i] * corners[i].1.to_vec2());
+        assert!(
+            (vertex.uv.to_vec2() - uv).length() < 1e-4,
+            "{:?}: {:?} vs {:?}",
+            vertex.pos,
+            vertex.uv,
+            uv
+        );
+
+        let mut expected = Rgba::TRANSPARENT;
+        for i in 0..3 {
+            let color = Rgba::from(corners[i].2);
+            for channel in 0..4 {
+                expected[channel] += w[i] * color[channel];
+            }
+        }
+        let expected = Srgba::from(expected);
+        for (actual, expected) in vertex.color.to_array().iter().zip(&expected.to_array()) {
+            assert!(
+                (*actual as i32 - *expected as i32).abs() <= 1,
+                "{:?}: {:?} vs {:?}",
+                vertex.pos,
+                vertex.color,
+                expected
+            );
+        }
+    }
+}
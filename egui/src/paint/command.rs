@@ -1,20 +1,27 @@
 use {
-    super::{font::Galley, fonts::TextStyle, Fonts, Srgba, Triangles},
+    super::{
+        color::{Rgba, TRANSPARENT},
+        font::Galley,
+        fonts::TextStyle,
+        gradient::{LinearGradient, RadialGradient},
+        Fonts, Shadow, Srgba, Triangles,
+    },
     crate::{
         align::{anchor_rect, Align},
-        math::{Pos2, Rect},
+        math::{pos2, vec2, Pos2, Rect, Transform},
     },
 };
 
 // TODO: rename, e.g. `paint::Cmd`?
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum PaintCmd {
     /// Paint nothing. This can be useful as a placeholder.
     Noop,
     Circle {
         center: Pos2,
         radius: f32,
-        fill: Srgba,
+        fill: Fill,
         stroke: Stroke,
     },
     LineSegment {
@@ -24,17 +31,24 @@ pub enum PaintCmd {
     Path {
         points: Vec<Pos2>,
         /// If true, connect the first and last of the points together.
-        /// This is required if `fill != TRANSPARENT`.
+        /// This is required if `fill` is not transparent.
         closed: bool,
-        fill: Srgba,
+        fill: Fill,
         stroke: Stroke,
     },
     Rect {
         rect: Rect,
-        corner_radius: f32,
-        fill: Srgba,
+        rounding: Rounding,
+        fill: Fill,
         stroke: Stroke,
     },
+    /// The soft shadow of a (rounded) rectangle.
+    Shadow {
+        /// The rectangle casting the shadow.
+        rect: Rect,
+        rounding: Rounding,
+        shadow: Shadow,
+    },
     Text {
         /// Top left corner of the first character.
         pos: Pos2,
@@ -43,7 +57,55 @@ pub enum PaintCmd {
         text_style: TextStyle, // TODO: Font?
         color: Srgba,
     },
+    /// A quadratic Bezier curve, defined by a start point, a control point and an end point.
+    QuadraticBezier {
+        points: [Pos2; 3],
+        /// If true, connect the end point back to the start point with a straight line.
+        /// This is required if `fill` is not transparent.
+        closed: bool,
+        fill: Fill,
+        stroke: Stroke,
+    },
+    /// A cubic Bezier curve, defined by a start point, two control points and an end point.
+    CubicBezier {
+        points: [Pos2; 4],
+        /// If true, connect the end point back to the start point with a straight line.
+        /// This is required if `fill` is not transparent.
+        closed: bool,
+        fill: Fill,
+        stroke: Stroke,
+    },
+    /// A circular arc, going clockwise (on screen) from `start_angle` to `end_angle`.
+    ///
+    /// Angles are in radians, with zero pointing to the right.
+    Arc {
+        center: Pos2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        /// If true, connect the ends of the arc with the center, forming a pie slice.
+        /// This is required if `fill` is not transparent.
+        closed: bool,
+        fill: Fill,
+        stroke: Stroke,
+    },
     Triangles(Triangles),
+    /// Paint the given commands moved, rotated, scaled and/or skewed by `transform`.
+    ///
+    /// The transform is applied after tessellation, so e.g. stroke widths and text get transformed too.
+    Transformed {
+        transform: Transform,
+        commands: Vec<PaintCmd>,
+    },
+    /// Paint the given commands, but only the parts inside of a rounded rectangle.
+    ///
+    /// This is done on the CPU by cutting the tessellated triangles, with anti-aliased edges.
+    /// The usual clip rectangle is still applied as well.
+    Clipped {
+        rect: Rect,
+        rounding: Rounding,
+        commands: Vec<PaintCmd>,
+    },
 }
 
 impl PaintCmd {
@@ -54,11 +116,11 @@ impl PaintCmd {
         }
     }
 
-    pub fn circle_filled(center: Pos2, radius: f32, fill_color: impl Into<Srgba>) -> Self {
+    pub fn circle_filled(center: Pos2, radius: f32, fill: impl Into<Fill>) -> Self {
         Self::Circle {
             center,
             radius,
-            fill: fill_color.into(),
+            fill: fill.into(),
             stroke: Default::default(),
         }
     }
@@ -72,19 +134,59 @@ impl PaintCmd {
         }
     }
 
-    pub fn rect_filled(rect: Rect, corner_radius: f32, fill_color: impl Into<Srgba>) -> Self {
+    pub fn rect_filled(rect: Rect, rounding: impl Into<Rounding>, fill: impl Into<Fill>) -> Self {
         Self::Rect {
             rect,
-            corner_radius,
-            fill: fill_color.into(),
+            rounding: rounding.into(),
+            fill: fill.into(),
             stroke: Default::default(),
         }
     }
 
-    pub fn rect_stroke(rect: Rect, corner_radius: f32, stroke: impl Into<Stroke>) -> Self {
+    pub fn rect_stroke(
+        rect: Rect,
+        rounding: impl Into<Rounding>,
+        stroke: impl Into<Stroke>,
+    ) -> Self {
         Self::Rect {
             rect,
-            corner_radius,
+            rounding: rounding.into(),
+            fill: Default::default(),
+            stroke: stroke.into(),
+        }
+    }
+
+    pub fn quadratic_bezier(points: [Pos2; 3], stroke: impl Into<Stroke>) -> Self {
+        Self::QuadraticBezier {
+            points,
+            closed: false,
+            fill: Default::default(),
+            stroke: stroke.into(),
+        }
+    }
+
+    pub fn cubic_bezier(points: [Pos2; 4], stroke: impl Into<Stroke>) -> Self {
+        Self::CubicBezier {
+            points,
+            closed: false,
+            fill: Default::default(),
+            stroke: stroke.into(),
+        }
+    }
+
+    pub fn arc(
+        center: Pos2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        stroke: impl Into<Stroke>,
+    ) -> Self {
+        Self::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            closed: false,
             fill: Default::default(),
             stroke: stroke.into(),
         }
@@ -110,11 +212,18 @@ impl PaintCmd {
     }
 }
 
+/// Describes the outline of a shape.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Stroke {
     pub width: f32,
     pub color: Srgba,
+    /// If set, the stroke is broken up into dashes. If `None` the stroke is solid.
+    pub dash: Option<Dash>,
+    /// How to paint the ends of open paths (and of each dash).
+    pub cap: LineCap,
+    /// How to paint the corners where two line segments meet.
+    pub join: LineJoin,
 }
 
 impl Stroke {
@@ -126,7 +235,49 @@ impl Stroke {
         Self {
             width: width.into(),
             color: color.into(),
+            dash: None,
+            cap: Default::default(),
+            join: Default::default(),
+        }
+    }
+
+    /// Paint dashes of the given `length`, separated by `gap`.
+    pub fn dashed(mut self, length: f32, gap: f32) -> Self {
+        self.dash = Some(Dash {
+            length,
+            gap,
+            offset: 0.0,
+        });
+        self
+    }
+
+    /// Paint round dots, with `spacing` between the centers of consecutive dots.
+    pub fn dotted(mut self, spacing: f32) -> Self {
+        self.dash = Some(Dash {
+            length: 0.0,
+            gap: spacing,
+            offset: 0.0,
+        });
+        self.cap = LineCap::Round;
+        self
+    }
+
+    /// How far into the dash pattern to start. Does nothing for solid strokes.
+    pub fn dash_offset(mut self, offset: f32) -> Self {
+        if let Some(dash) = &mut self.dash {
+            dash.offset = offset;
         }
+        self
+    }
+
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
     }
 }
 
@@ -138,3 +289,248 @@ where
         Stroke::new(width, color)
     }
 }
+
+/// How rounded the corners of a rectangle are, with one radius per corner.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Rounding {
+    /// Radius of the top left corner.
+    pub nw: f32,
+    /// Radius of the top right corner.
+    pub ne: f32,
+    /// Radius of the bottom left corner.
+    pub sw: f32,
+    /// Radius of the bottom right corner.
+    pub se: f32,
+}
+
+impl Rounding {
+    pub fn none() -> Self {
+        Self::same(0.0)
+    }
+
+    /// The same radius for all four corners.
+    pub fn same(radius: f32) -> Self {
+        Self {
+            nw: radius,
+            ne: radius,
+            sw: radius,
+            se: radius,
+        }
+    }
+
+    /// Only round the top corners, e.g. for a tab.
+    pub fn top(radius: f32) -> Self {
+        Self {
+            nw: radius,
+            ne: radius,
+            ..Self::none()
+        }
+    }
+
+    /// Only round the bottom corners, e.g. for a popup attached below something.
+    pub fn bottom(radius: f32) -> Self {
+        Self {
+            sw: radius,
+            se: radius,
+            ..Self::none()
+        }
+    }
+
+    /// Only round the left corners, e.g. for the first of some segmented buttons.
+    pub fn left(radius: f32) -> Self {
+        Self {
+            nw: radius,
+            sw: radius,
+            ..Self::none()
+        }
+    }
+
+    /// Only round the right corners, e.g. for the last of some segmented buttons.
+    pub fn right(radius: f32) -> Self {
+        Self {
+            ne: radius,
+            se: radius,
+            ..Self::none()
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.nw <= 0.0 && self.ne <= 0.0 && self.sw <= 0.0 && self.se <= 0.0
+    }
+
+    /// The largest of the four radii.
+    pub fn max(&self) -> f32 {
+        self.nw.max(self.ne).max(self.sw).max(self.se)
+    }
+
+    /// Make each corner at least this round.
+    pub fn at_least(self, min: f32) -> Self {
+        self.map(|r| r.max(min))
+    }
+
+    /// Make each corner at most this round.
+    pub fn at_most(self, max: f32) -> Self {
+        self.map(|r| r.min(max))
+    }
+
+    /// Apply a function to each of the four radii.
+    pub fn map(self, f: impl Fn(f32) -> f32) -> Self {
+        Self {
+            nw: f(self.nw),
+            ne: f(self.ne),
+            sw: f(self.sw),
+            se: f(self.se),
+        }
+    }
+}
+
+impl From<f32> for Rounding {
+    fn from(radius: f32) -> Self {
+        Self::same(radius)
+    }
+}
+
+/// How to fill the inside of a shape.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Fill {
+    /// A single color.
+    Solid(Srgba),
+    LinearGradient(LinearGradient),
+    RadialGradient(RadialGradient),
+}
+
+impl Default for Fill {
+    fn default() -> Self {
+        Self::Solid(TRANSPARENT)
+    }
+}
+
+impl Fill {
+    /// Is this fill invisible everywhere?
+    pub fn is_transparent(&self) -> bool {
+        match self {
+            Self::Solid(color) => *color == TRANSPARENT,
+            Self::LinearGradient(gradient) => gradient.stops.iter().all(|s| s.color == TRANSPARENT),
+            Self::RadialGradient(gradient) => gradient.stops.iter().all(|s| s.color == TRANSPARENT),
+        }
+    }
+
+    /// The color at the given screen position.
+    pub fn color_at(&self, pos: Pos2) -> Srgba {
+        match self {
+            Self::Solid(color) => *color,
+            Self::LinearGradient(gradient) => gradient.color_at(pos).into(),
+            Self::RadialGradient(gradient) => gradient.color_at(pos).into(),
+        }
+    }
+
+    /// A gradient from `top_color` at the top of whatever is filled to `bottom_color` at the bottom.
+    /// For fills that are given relative to a rectangle, see [`Self::fit_to_rect`].
+    pub fn vertical_gradient(top_color: Srgba, bottom_color: Srgba) -> Self {
+        LinearGradient::vertical(Self::unit_rect(), top_color, bottom_color).into()
+    }
+
+    /// A gradient from `left_color` at the left of whatever is filled to `right_color` at the right.
+    /// For fills that are given relative to a rectangle, see [`Self::fit_to_rect`].
+    pub fn horizontal_gradient(left_color: Srgba, right_color: Srgba) -> Self {
+        LinearGradient::horizontal(Self::unit_rect(), left_color, right_color).into()
+    }
+
+    /// Places a fill that is given relative to a rectangle, like `Frame::fill`
+    /// and `WidgetVisuals::bg_fill`, onto `rect` in screen coordinates.
+    ///
+    /// In a relative fill, `pos2(0.0, 0.0)` is the top left corner of `rect`
+    /// and `pos2(1.0, 1.0)` is the bottom right corner.
+    /// The radius of a radial gradient is relative to the longest side of `rect`.
+    pub fn fit_to_rect(&self, rect: Rect) -> Self {
+        let to_screen = |pos: Pos2| rect.min + vec2(pos.x * rect.width(), pos.y * rect.height());
+        match self {
+            Self::Solid(color) => Self::Solid(*color),
+            Self::LinearGradient(gradient) => Self::LinearGradient(LinearGradient {
+                start: to_screen(gradient.start),
+                end: to_screen(gradient.end),
+                stops: gradient.stops.clone(),
+            }),
+            Self::RadialGradient(gradient) => Self::RadialGradient(RadialGradient {
+                center: to_screen(gradient.center),
+                radius: gradient.radius * rect.width().max(rect.height()),
+                stops: gradient.stops.clone(),
+            }),
+        }
+    }
+
+    fn unit_rect() -> Rect {
+        Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0))
+    }
+}
+
+impl From<Srgba> for Fill {
+    fn from(color: Srgba) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<Rgba> for Fill {
+    fn from(color: Rgba) -> Self {
+        Self::Solid(color.into())
+    }
+}
+
+impl From<LinearGradient> for Fill {
+    fn from(gradient: LinearGradient) -> Self {
+        Self::LinearGradient(gradient)
+    }
+}
+
+impl From<RadialGradient> for Fill {
+    fn from(gradient: RadialGradient) -> Self {
+        Self::RadialGradient(gradient)
+    }
+}
+
+/// A dash pattern for a `Stroke`, in points.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Dash {
+    /// Length of each dash. A length of zero paints dots (when combined with round or square caps).
+    pub length: f32,
+    /// Distance between the end of one dash and the start of the next.
+    pub gap: f32,
+    /// How far into the pattern the path starts.
+    pub offset: f32,
+}
+
+/// How to paint the ends of open paths and dashes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point.
+    #[default]
+    Butt,
+    /// The stroke ends in a half-circle around the end point.
+    Round,
+    /// The stroke is extended by half its width beyond the end point.
+    Square,
+}
+
+/// How to paint the corner where two line segments meet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LineJoin {
+    /// Extend the outer edges until they meet.
+    /// If the corner is so sharp that the miter would be longer than `limit`
+    /// times the stroke width, a bevel is used instead.
+    Miter { limit: f32 },
+    /// Round off the corner with a circular arc.
+    Round,
+    /// Cut off the corner with a straight line.
+    Bevel,
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        Self::Miter { limit: 4.0 }
+    }
+}
@@ -22,6 +22,7 @@ pub struct GalleyCursor {
 
 /// A collection of text locked into place.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Galley {
     /// The full text
     pub text: String,
@@ -36,6 +37,7 @@ pub struct Galley {
 
 /// A typeset piece of text on a single line.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Line {
     /// The start of each character, probably starting at zero.
     /// The last element is the end of the last character.
@@ -0,0 +1,202 @@
+//! Color gradients used for filling shapes.
+
+use crate::math::*;
+
+use super::color::{Rgba, Srgba};
+
+/// A color at a specific place along a gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ColorStop {
+    /// Where along the gradient this color is, usually in the range [0, 1].
+    pub offset: f32,
+    pub color: Srgba,
+}
+
+/// A gradient going along the line from `start` to `end`.
+///
+/// Points before `start` get the color of the first stop,
+/// points after `end` get the color of the last stop.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LinearGradient {
+    /// Where `offset == 0`. In screen coordinates.
+    pub start: Pos2,
+    /// Where `offset == 1`. In screen coordinates.
+    pub end: Pos2,
+    /// Sorted by `offset`.
+    pub stops: Vec<ColorStop>,
+}
+
+impl LinearGradient {
+    pub fn new(start: Pos2, end: Pos2, start_color: Srgba, end_color: Srgba) -> Self {
+        Self {
+            start,
+            end,
+            stops: vec![
+                ColorStop {
+                    offset: 0.0,
+                    color: start_color,
+                },
+                ColorStop {
+                    offset: 1.0,
+                    color: end_color,
+                },
+            ],
+        }
+    }
+
+    /// From the top of the rectangle to the bottom.
+    pub fn vertical(rect: Rect, top_color: Srgba, bottom_color: Srgba) -> Self {
+        Self::new(
+            rect.center_top(),
+            rect.center_bottom(),
+            top_color,
+            bottom_color,
+        )
+    }
+
+    /// From the left side of the rectangle to the right.
+    pub fn horizontal(rect: Rect, left_color: Srgba, right_color: Srgba) -> Self {
+        Self::new(
+            rect.left_center(),
+            rect.right_center(),
+            left_color,
+            right_color,
+        )
+    }
+
+    /// Add another color stop.
+    pub fn stop(mut self, offset: f32, color: Srgba) -> Self {
+        insert_stop(&mut self.stops, ColorStop { offset, color });
+        self
+    }
+
+    /// Where along the gradient this position is.
+    pub fn offset_at(&self, pos: Pos2) -> f32 {
+        let dir = self.end - self.start;
+        let length_sq = dir.length_sq();
+        if length_sq <= 0.0 {
+            0.0
+        } else {
+            let rel = pos - self.start;
+            (rel.x * dir.x + rel.y * dir.y) / length_sq
+        }
+    }
+
+    pub fn color_at(&self, pos: Pos2) -> Rgba {
+        color_at_offset(&self.stops, self.offset_at(pos))
+    }
+}
+
+/// A circular gradient going outwards from `center`.
+///
+/// Points further away than `radius` get the color of the last stop.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RadialGradient {
+    /// Where `offset == 0`. In screen coordinates.
+    pub center: Pos2,
+    /// At this distance from `center`, `offset == 1`.
+    pub radius: f32,
+    /// Sorted by `offset`.
+    pub stops: Vec<ColorStop>,
+}
+
+impl RadialGradient {
+    pub fn new(center: Pos2, radius: f32, inner_color: Srgba, outer_color: Srgba) -> Self {
+        Self {
+            center,
+            radius,
+            stops: vec![
+                ColorStop {
+                    offset: 0.0,
+                    color: inner_color,
+                },
+                ColorStop {
+                    offset: 1.0,
+                    color: outer_color,
+                },
+            ],
+        }
+    }
+
+    /// Add another color stop.
+    pub fn stop(mut self, offset: f32, color: Srgba) -> Self {
+        insert_stop(&mut self.stops, ColorStop { offset, color });
+        self
+    }
+
+    /// Where along the gradient this position is.
+    pub fn offset_at(&self, pos: Pos2) -> f32 {
+        if self.radius <= 0.0 {
+            1.0
+        } else {
+            self.center.distance(pos) / self.radius
+        }
+    }
+
+    pub fn color_at(&self, pos: Pos2) -> Rgba {
+        color_at_offset(&self.stops, self.offset_at(pos))
+    }
+}
+
+fn insert_stop(stops: &mut Vec<ColorStop>, stop: ColorStop) {
+    let idx = stops
+        .iter()
+        .position(|s| s.offset > stop.offset)
+        .unwrap_or(stops.len());
+    stops.insert(idx, stop);
+}
+
+/// The colors are interpolated in linear space, just like the GPU does for vertex colors.
+pub(crate) fn color_at_offset(stops: &[ColorStop], offset: f32) -> Rgba {
+    match stops {
+        [] => Rgba::default(),
+        [first, ..] if offset <= first.offset => first.color.into(),
+        [.., last] if offset >= last.offset => last.color.into(),
+        _ => {
+            for pair in stops.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                if offset <= b.offset {
+                    if b.offset <= a.offset {
+                        return b.color.into();
+                    }
+                    let t = (offset - a.offset) / (b.offset - a.offset);
+                    return lerp(Rgba::from(a.color)..=Rgba::from(b.color), t);
+                }
+            }
+            stops[stops.len() - 1].color.into()
+        }
+    }
+}
+
+#[test]
+fn test_gradient_colors() {
+    let (red, green, blue) = (
+        Srgba::new(255, 0, 0, 255),
+        Srgba::new(0, 255, 0, 255),
+        Srgba::new(0, 0, 255, 255),
+    );
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 10.0));
+    let gradient = LinearGradient::horizontal(rect, red, blue).stop(0.5, green);
+    let offsets: Vec<f32> = gradient.stops.iter().map(|s| s.offset).collect();
+    assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+
+    let assert_close = |color: Rgba, expected: [f32; 4]| {
+        let rgba = [color.r(), color.g(), color.b(), color.a()];
+        let close = (0..4).all(|i| (rgba[i] - expected[i]).abs() < 1e-5);
+        assert!(close, "{:?} != {:?}", rgba, expected);
+    };
+    assert_eq!(gradient.color_at(pos2(-10.0, 5.0)), red.into());
+    assert_eq!(gradient.color_at(pos2(50.0, 0.0)), green.into());
+    assert_eq!(gradient.color_at(pos2(150.0, 10.0)), blue.into());
+    // Interpolated in linear space between the two closest stops:
+    assert_close(gradient.color_at(pos2(25.0, 5.0)), [0.5, 0.5, 0.0, 1.0]);
+    assert_close(gradient.color_at(pos2(90.0, 5.0)), [0.0, 0.2, 0.8, 1.0]);
+
+    let gradient = RadialGradient::new(pos2(10.0, 10.0), 20.0, red, blue);
+    assert_eq!(gradient.color_at(pos2(10.0, 10.0)), red.into());
+    assert_close(gradient.color_at(pos2(20.0, 10.0)), [0.5, 0.0, 0.5, 1.0]);
+    assert_eq!(gradient.color_at(pos2(10.0, 50.0)), blue.into());
+}
@@ -2,17 +2,23 @@
 //!
 //! Handles fonts, textures, color, geometry and tesselation.
 
+mod clipping;
 pub mod color;
 pub mod command;
 pub mod font;
 pub mod fonts;
+pub mod gradient;
+pub mod shadow;
+pub mod svg;
 pub mod tessellator;
 mod texture_atlas;
 
 pub use {
     color::{Rgba, Srgba},
-    command::{PaintCmd, Stroke},
+    command::{Dash, Fill, LineCap, LineJoin, PaintCmd, Rounding, Stroke},
     fonts::{FontDefinitions, Fonts, TextStyle},
+    gradient::{ColorStop, LinearGradient, RadialGradient},
+    shadow::Shadow,
     tessellator::{PaintJobs, PaintOptions, TextureId, Triangles, Vertex, WHITE_UV},
     texture_atlas::Texture,
 };
@@ -0,0 +1,57 @@
+use super::{color, Srgba};
+use crate::math::*;
+
+/// A soft shadow below a rectangle, e.g. a window or a popup.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Shadow {
+    /// Move the shadow by this much relative to the rectangle casting it.
+    pub offset: Vec2,
+    /// Width of the soft edge of the shadow, where it fades out.
+    pub blur: f32,
+    /// Grow the shadow by this much on each side.
+    pub spread: f32,
+    /// Color of the opaque center of the shadow.
+    pub color: Srgba,
+}
+
+impl Shadow {
+    pub fn none() -> Self {
+        Self {
+            offset: Vec2::zero(),
+            blur: 0.0,
+            spread: 0.0,
+            color: color::TRANSPARENT,
+        }
+    }
+
+    /// Subtle shadow for small things like menus and popups.
+    pub fn small() -> Self {
+        Self {
+            offset: vec2(1.0, 2.0),
+            blur: 8.0,
+            spread: 0.0,
+            color: Srgba::black_alpha(64),
+        }
+    }
+
+    /// Shadow for windows.
+    pub fn big() -> Self {
+        Self {
+            offset: vec2(2.0, 4.0),
+            blur: 16.0,
+            spread: 0.0,
+            color: Srgba::black_alpha(96),
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.color != color::TRANSPARENT
+    }
+
+    /// The area covered by the shadow (including the soft edge) of the given rectangle.
+    pub fn visual_rect(&self, rect: Rect) -> Rect {
+        rect.translate(self.offset)
+            .expand(self.spread + 0.5 * self.blur)
+    }
+}
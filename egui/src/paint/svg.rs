@@ -0,0 +1,595 @@
+//! Export paint commands as an SVG document, e.g. for documentation and bug reports.
+//!
+//! Use `Context::end_frame_commands` to get the paint commands of a frame before they are tessellated.
+
+use std::fmt::Write as _;
+
+use {
+    super::{
+        color::{Rgba, Srgba, TRANSPARENT},
+        command::{Fill, LineCap, LineJoin, PaintCmd, Rounding, Stroke},
+        font::Galley,
+        fonts::{FontFamily, Fonts, TextStyle},
+        gradient::ColorStop,
+        Shadow,
+    },
+    crate::math::*,
+};
+
+/// Convert paint commands, in the order they are painted, to an SVG document covering `screen_rect`.
+///
+/// Each command is clipped to its clip rectangle.
+/// Text is kept as selectable SVG text, while `PaintCmd::Triangles` become one polygon per triangle
+/// (textures are not included, only the vertex colors).
+pub fn paint_commands_to_svg(
+    commands: &[(Rect, PaintCmd)],
+    screen_rect: Rect,
+    fonts: &Fonts,
+) -> String {
+    let mut writer = SvgWriter {
+        fonts,
+        defs: String::new(),
+        body: String::new(),
+        next_id: 0,
+    };
+
+    let mut current_clip_rect = None;
+    for (clip_rect, cmd) in commands {
+        if let PaintCmd::Noop = cmd {
+            continue;
+        }
+        let clip_rect = clip_rect.intersect(screen_rect);
+        if current_clip_rect != Some(clip_rect) {
+            if current_clip_rect.is_some() {
+                writer.body += "</g>\n";
+            }
+            let clip_id = writer.clip_path(&rect_path(clip_rect, Rounding::none()));
+            let _ = writeln!(writer.body, r#"<g clip-path="url(#{})">"#, clip_id);
+            current_clip_rect = Some(clip_rect);
+        }
+        writer.cmd(cmd);
+    }
+    if current_clip_rect.is_some() {
+        writer.body += "</g>\n";
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"{x} {y} {w} {h}\">\n\
+         <defs>\n{defs}</defs>\n\
+         {body}\
+         </svg>\n",
+        x = num(screen_rect.min.x),
+        y = num(screen_rect.min.y),
+        w = num(screen_rect.width()),
+        h = num(screen_rect.height()),
+        defs = writer.defs,
+        body = writer.body,
+    )
+}
+
+struct SvgWriter<'a> {
+    fonts: &'a Fonts,
+    /// Clip paths, gradients and filters.
+    defs: String,
+    body: String,
+    next_id: usize,
+}
+
+impl<'a> SvgWriter<'a> {
+    fn new_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}{}", prefix, self.next_id)
+    }
+
+    fn clip_path(&mut self, path: &str) -> String {
+        let id = self.new_id("clip");
+        let _ = writeln!(
+            self.defs,
+            r#"<clipPath id="{}"><path d="{}"/></clipPath>"#,
+            id, path
+        );
+        id
+    }
+
+    fn cmd(&mut self, cmd: &PaintCmd) {
+        match cmd {
+            PaintCmd::Noop => {}
+            PaintCmd::Circle {
+                center,
+                radius,
+                fill,
+                stroke,
+            } => {
+                let fill = self.fill(fill);
+                let _ = writeln!(
+                    self.body,
+                    r#"<circle cx="{}" cy="{}" r="{}" {} {}/>"#,
+                    num(center.x),
+                    num(center.y),
+                    num(*radius),
+                    fill,
+                    stroke_attributes(stroke),
+                );
+            }
+            PaintCmd::LineSegment { points, stroke } => {
+                let _ = writeln!(
+                    self.body,
+                    r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#,
+                    num(points[0].x),
+                    num(points[0].y),
+                    num(points[1].x),
+                    num(points[1].y),
+                    stroke_attributes(stroke),
+                );
+            }
+            PaintCmd::Path {
+                points,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let element = if *closed { "polygon" } else { "polyline" };
+                let fill = if *closed {
+                    self.fill(fill)
+                } else {
+                    r#"fill="none""#.to_owned()
+                };
+                let _ = writeln!(
+                    self.body,
+                    r#"<{} points="{}" {} {}/>"#,
+                    element,
+                    points_list(points),
+                    fill,
+                    stroke_attributes(stroke),
+                );
+            }
+            PaintCmd::Rect {
+                rect,
+                rounding,
+                fill,
+                stroke,
+            } => {
+                let fill = self.fill(fill);
+                let _ = writeln!(
+                    self.body,
+                    r#"<path d="{}" {} {}/>"#,
+                    rect_path(*rect, *rounding),
+                    fill,
+                    stroke_attributes(stroke),
+                );
+            }
+            PaintCmd::Shadow {
+                rect,
+                rounding,
+                shadow,
+            } => self.shadow(*rect, *rounding, shadow),
+            PaintCmd::Text {
+                pos,
+                galley,
+                text_style,
+                color,
+            } => self.text(*pos, galley, *text_style, *color),
+            PaintCmd::QuadraticBezier {
+                points,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let [a, b, c] = *points;
+                let path = format!(
+                    "M {} {} Q {} {} {} {}{}",
+                    num(a.x),
+                    num(a.y),
+                    num(b.x),
+                    num(b.y),
+                    num(c.x),
+                    num(c.y),
+                    if *closed { " Z" } else { "" }
+                );
+                self.path(&path, *closed, fill, stroke);
+            }
+            PaintCmd::CubicBezier {
+                points,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let [a, b, c, d] = *points;
+                let path = format!(
+                    "M {} {} C {} {} {} {} {} {}{}",
+                    num(a.x),
+                    num(a.y),
+                    num(b.x),
+                    num(b.y),
+                    num(c.x),
+                    num(c.y),
+                    num(d.x),
+                    num(d.y),
+                    if *closed { " Z" } else { "" }
+                );
+                self.path(&path, *closed, fill, stroke);
+            }
+            PaintCmd::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let path = arc_path(*center, *radius, *start_angle, *end_angle, *closed);
+                self.path(&path, *closed, fill, stroke);
+            }
+            PaintCmd::Triangles(triangles) => {
+                self.body += "<g>\n";
+                for triangle in triangles.indices.chunks_exact(3) {
+                    let vertices = [
+                        triangles.vertices[triangle[0] as usize],
+                        triangles.vertices[triangle[1] as usize],
+                        triangles.vertices[triangle[2] as usize],
+                    ];
+                    let color = vertices
+                        .iter()
+                        .fold(Rgba::default(), |sum, v| sum + Rgba::from(v.color))
+                        * (1.0 / 3.0);
+                    if color.a() <= 0.0 {
+                        continue;
+                    }
+                    let points: Vec<Pos2> = vertices.iter().map(|v| v.pos).collect();
+                    let _ = writeln!(
+                        self.body,
+                        r#"<polygon points="{}" {}/>"#,
+                        points_list(&points),
+                        color_attributes("fill", color.into()),
+                    );
+                }
+                self.body += "</g>\n";
+            }
+            PaintCmd::Transformed {
+                transform,
+                commands,
+            } => {
+                let _ = writeln!(
+                    self.body,
+                    r#"<g transform="matrix({} {} {} {} {} {})">"#,
+                    num(transform.x_axis.x),
+                    num(transform.x_axis.y),
+                    num(transform.y_axis.x),
+                    num(transform.y_axis.y),
+                    num(transform.translation.x),
+                    num(transform.translation.y),
+                );
+                for cmd in commands {
+                    self.cmd(cmd);
+                }
+                self.body += "</g>\n";
+            }
+            PaintCmd::Clipped {
+                rect,
+                rounding,
+                commands,
+            } => {
+                let clip_id = self.clip_path(&rect_path(*rect, *rounding));
+                let _ = writeln!(self.body, r#"<g clip-path="url(#{})">"#, clip_id);
+                for cmd in commands {
+                    self.cmd(cmd);
+                }
+                self.body += "</g>\n";
+            }
+        }
+    }
+
+    fn path(&mut self, path: &str, closed: bool, fill: &Fill, stroke: &Stroke) {
+        let fill = if closed {
+            self.fill(fill)
+        } else {
+            r#"fill="none""#.to_owned()
+        };
+        let _ = writeln!(
+            self.body,
+            r#"<path d="{}" {} {}/>"#,
+            path,
+            fill,
+            stroke_attributes(stroke),
+        );
+    }
+
+    /// The `fill` attribute(s), adding any gradient to the defs.
+    fn fill(&mut self, fill: &Fill) -> String {
+        match fill {
+            Fill::Solid(color) => color_attributes("fill", *color),
+            Fill::LinearGradient(gradient) => {
+                let id = self.new_id("gradient");
+                let _ = writeln!(
+                    self.defs,
+                    r#"<linearGradient id="{}" gradientUnits="userSpaceOnUse" color-interpolation="linearRGB" x1="{}" y1="{}" x2="{}" y2="{}">{}</linearGradient>"#,
+                    id,
+                    num(gradient.start.x),
+                    num(gradient.start.y),
+                    num(gradient.end.x),
+                    num(gradient.end.y),
+                    gradient_stops(&gradient.stops),
+                );
+                format!(r#"fill="url(#{})""#, id)
+            }
+            Fill::RadialGradient(gradient) => {
+                let id = self.new_id("gradient");
+                let _ = writeln!(
+                    self.defs,
+                    r#"<radialGradient id="{}" gradientUnits="userSpaceOnUse" color-interpolation="linearRGB" cx="{}" cy="{}" r="{}">{}</radialGradient>"#,
+                    id,
+                    num(gradient.center.x),
+                    num(gradient.center.y),
+                    num(gradient.radius),
+                    gradient_stops(&gradient.stops),
+                );
+                format!(r#"fill="url(#{})""#, id)
+            }
+        }
+    }
+
+    fn shadow(&mut self, rect: Rect, rounding: Rounding, shadow: &Shadow) {
+        if !shadow.is_visible() {
+            return;
+        }
+        let rect = rect.translate(shadow.offset).expand(shadow.spread);
+        let rounding = rounding.map(|r| r + shadow.spread);
+        // The tessellator fades the edge linearly over `blur` points,
+        // which is roughly a gaussian with this standard deviation:
+        let std_dev = 0.4 * shadow.blur;
+        let filter_id = self.new_id("blur");
+        let _ = writeln!(
+            self.defs,
+            r#"<filter id="{}" x="-50%" y="-50%" width="200%" height="200%"><feGaussianBlur stdDeviation="{}"/></filter>"#,
+            filter_id,
+            num(std_dev),
+        );
+        let _ = writeln!(
+            self.body,
+            r#"<path d="{}" {} filter="url(#{})"/>"#,
+            rect_path(rect, rounding),
+            color_attributes("fill", shadow.color),
+            filter_id,
+        );
+    }
+
+    /// One `<text>` element per line, with the position of each character given explicitly
+    /// so that the layout matches Egui exactly, while still being selectable.
+    fn text(&mut self, pos: Pos2, galley: &Galley, text_style: TextStyle, color: Srgba) {
+        if color == TRANSPARENT {
+            return;
+        }
+        let font_size = self.fonts[text_style].height();
+        let font_family = match self.fonts.definitions().fonts.get(&text_style) {
+            Some((FontFamily::Monospace, _)) => "ProggyClean, monospace",
+            _ => "Comfortaa, sans-serif",
+        };
+
+        let mut chars = galley.text.chars();
+        for line in &galley.lines {
+            let mut text = String::new();
+            let mut x_offsets = String::new();
+            for x_offset in line.x_offsets.iter().take(line.x_offsets.len() - 1) {
+                let c = chars.next().unwrap();
+                if c == '\n' {
+                    continue;
+                }
+                escape_xml_into(c, &mut text);
+                if !x_offsets.is_empty() {
+                    x_offsets.push(' ');
+                }
+                x_offsets += &num(pos.x + x_offset);
+            }
+            if text.is_empty() {
+                continue;
+            }
+            let _ = writeln!(
+                self.body,
+                r#"<text x="{}" y="{}" dominant-baseline="text-before-edge" font-family="{}" font-size="{}" {} xml:space="preserve">{}</text>"#,
+                x_offsets,
+                num(pos.y + line.y_min),
+                font_family,
+                num(font_size),
+                color_attributes("fill", color),
+                text,
+            );
+        }
+    }
+}
+
+/// Format a number with at most two decimals, which is plenty for points.
+fn num(x: f32) -> String {
+    let s = format!("{:.2}", x);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_owned()
+    } else {
+        s.to_owned()
+    }
+}
+
+fn points_list(points: &[Pos2]) -> String {
+    let points: Vec<String> = points
+        .iter()
+        .map(|p| format!("{},{}", num(p.x), num(p.y)))
+        .collect();
+    points.join(" ")
+}
+
+/// SVG wants colors without premultiplied alpha, and the opacity separately.
+fn color_attributes(attribute: &str, color: Srgba) -> String {
+    let rgba = Rgba::from(color);
+    let alpha = rgba.a();
+    if alpha <= 0.0 {
+        return format!(r#"{}="none""#, attribute);
+    }
+    let opaque = Srgba::from(Rgba::new(
+        rgba.r() / alpha,
+        rgba.g() / alpha,
+        rgba.b() / alpha,
+        1.0,
+    ));
+    let mut attributes = format!(
+        r#"{}="rgb({},{},{})""#,
+        attribute,
+        opaque.r(),
+        opaque.g(),
+        opaque.b()
+    );
+    if alpha < 1.0 {
+        let _ = write!(attributes, r#" {}-opacity="{}""#, attribute, num(alpha));
+    }
+    attributes
+}
+
+fn gradient_stops(stops: &[ColorStop]) -> String {
+    let mut svg = String::new();
+    for stop in stops {
+        let color = color_attributes("stop-color", stop.color);
+        let color = if color.ends_with(r#"="none""#) {
+            r#"stop-color="black" stop-opacity="0""#.to_owned()
+        } else {
+            color
+        };
+        let _ = write!(svg, r#"<stop offset="{}" {}/>"#, num(stop.offset), color);
+    }
+    svg
+}
+
+fn stroke_attributes(stroke: &Stroke) -> String {
+    if stroke.width <= 0.0 || stroke.color == TRANSPARENT {
+        return r#"stroke="none""#.to_owned();
+    }
+    let mut attributes = format!(
+        r#"{} stroke-width="{}""#,
+        color_attributes("stroke", stroke.color),
+        num(stroke.width)
+    );
+    match stroke.cap {
+        LineCap::Butt => {}
+        LineCap::Round => attributes += r#" stroke-linecap="round""#,
+        LineCap::Square => attributes += r#" stroke-linecap="square""#,
+    }
+    match stroke.join {
+        LineJoin::Miter { limit } => {
+            let _ = write!(
+                attributes,
+                r#" stroke-miterlimit="{}""#,
+                num(limit.max(1.0))
+            );
+        }
+        LineJoin::Round => attributes += r#" stroke-linejoin="round""#,
+        LineJoin::Bevel => attributes += r#" stroke-linejoin="bevel""#,
+    }
+    if let Some(dash) = stroke.dash {
+        let _ = write!(
+            attributes,
+            r#" stroke-dasharray="{} {}" stroke-dashoffset="{}""#,
+            num(dash.length),
+            num(dash.gap),
+            num(dash.offset)
+        );
+    }
+    attributes
+}
+
+/// Path data for a rectangle with rounded corners.
+fn rect_path(rect: Rect, rounding: Rounding) -> String {
+    let Rounding { nw, ne, sw, se } = rounding.at_most(0.5 * rect.width().min(rect.height()));
+    let (min, max) = (rect.min, rect.max);
+    let corner = |r: f32, x: f32, y: f32| {
+        if r > 0.0 {
+            format!(" A {} {} 0 0 1 {} {}", num(r), num(r), num(x), num(y))
+        } else {
+            String::new()
+        }
+    };
+    format!(
+        "M {} {} H {}{} V {}{} H {}{} V {}{} Z",
+        num(min.x + nw),
+        num(min.y),
+        num(max.x - ne),
+        corner(ne, max.x, min.y + ne),
+        num(max.y - se),
+        corner(se, max.x - se, max.y),
+        num(min.x + sw),
+        corner(sw, min.x, max.y - sw),
+        num(min.y + nw),
+        corner(nw, min.x + nw, min.y),
+    )
+}
+
+/// Path data for an arc going clockwise (on screen) from `start_angle` to `end_angle`.
+fn arc_path(center: Pos2, radius: f32, start_angle: f32, end_angle: f32, closed: bool) -> String {
+    let point = |angle: f32| center + radius * Vec2::angled(angle);
+    let sweep = (end_angle - start_angle).abs().min(TAU);
+    // Positive angles go clockwise on screen, which is the positive sweep direction in SVG:
+    let sweep_flag = if end_angle >= start_angle { 1 } else { 0 };
+    let direction = if end_angle >= start_angle { 1.0 } else { -1.0 };
+
+    let start = point(start_angle);
+    let mut path = format!("M {} {}", num(start.x), num(start.y));
+    // A single SVG arc command can't do a full circle, so split it in two:
+    let num_pieces = if sweep > 0.5 * TAU { 2 } else { 1 };
+    for i in 1..=num_pieces {
+        let angle = start_angle + direction * sweep * i as f32 / num_pieces as f32;
+        let p = point(angle);
+        let _ = write!(
+            path,
+            " A {} {} 0 0 {} {} {}",
+            num(radius),
+            num(radius),
+            sweep_flag,
+            num(p.x),
+            num(p.y)
+        );
+    }
+    if closed {
+        let _ = write!(path, " L {} {} Z", num(center.x), num(center.y));
+    }
+    path
+}
+
+fn escape_xml_into(c: char, out: &mut String) {
+    match c {
+        '&' => *out += "&amp;",
+        '<' => *out += "&lt;",
+        '>' => *out += "&gt;",
+        '"' => *out += "&quot;",
+        c => out.push(c),
+    }
+}
+
+#[test]
+fn test_paint_commands_to_svg() {
+    use super::FontDefinitions;
+    let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+    let screen_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 50.0));
+    let text_style = TextStyle::Body;
+    let galley = fonts[text_style].layout_multiline("Hello <World>".to_owned(), f32::INFINITY);
+    let commands = vec![
+        (
+            Rect::everything(),
+            PaintCmd::circle_filled(pos2(10.0, 10.0), 5.0, super::color::WHITE),
+        ),
+        (
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(50.0, 50.0)),
+            PaintCmd::Text {
+                pos: pos2(2.0, 20.0),
+                galley,
+                text_style,
+                color: super::color::WHITE,
+            },
+        ),
+    ];
+
+    let svg = paint_commands_to_svg(&commands, screen_rect, &fonts);
+    assert!(svg.starts_with("<svg"));
+    assert!(
+        svg.contains(r#"<circle cx="10" cy="10" r="5" fill="rgb(255,255,255)" stroke="none"/>"#)
+    );
+    assert!(svg.contains(">Hello &lt;World&gt;</text>"));
+    assert_eq!(svg.matches("<clipPath").count(), 2);
+    assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+}
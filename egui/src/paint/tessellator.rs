@@ -7,15 +7,20 @@
 
 use {
     super::{
+        clipping,
         color::{self, srgba, Rgba, Srgba, TRANSPARENT},
+        command::{Dash, Fill, LineCap, LineJoin, Rounding},
         fonts::Fonts,
+        gradient::{LinearGradient, RadialGradient},
         PaintCmd, Stroke,
     },
     crate::math::*,
+    std::borrow::Cow,
 };
 
 /// What texture to use in a `Triangles` mesh.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum TextureId {
     /// The Egui font texture.
     /// If you don't want to use a texture, pick this and the `WHITE_UV` for uv-coord.
@@ -43,6 +48,7 @@ pub const WHITE_UV: Pos2 = pos2(0.0, 0.0);
 /// Should be friendly to send to GPU as is.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Vertex {
     /// Logical pixel coordinates (points).
     /// (0,0) is the top left corner of the screen.
@@ -59,6 +65,7 @@ pub struct Vertex {
 
 /// Textured triangles.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Triangles {
     /// Draw as triangles (i.e. the length is always multiple of three).
     pub indices: Vec<u32>,
@@ -145,34 +152,70 @@ impl Triangles {
 
     /// Rectangle with a texture and color.
     pub fn add_rect_with_uv(&mut self, pos: Rect, uv: Rect, color: Srgba) {
+        self.add_quad_with_uv(
+            [
+                pos.left_top(),
+                pos.right_top(),
+                pos.right_bottom(),
+                pos.left_bottom(),
+            ],
+            uv,
+            color,
+        );
+    }
+
+    /// A quadrilateral with arbitrary corners, showing the `uv` part of the texture.
+    ///
+    /// The corners are given in the order left top, right top, right bottom, left bottom
+    /// (as seen in the texture), so the quad can be rotated, skewed or otherwise distorted.
+    pub fn add_quad_with_uv(&mut self, corners: [Pos2; 4], uv: Rect, color: Srgba) {
+        let [left_top, right_top, right_bottom, left_bottom] = corners;
         let idx = self.vertices.len() as u32;
         self.add_triangle(idx + 0, idx + 1, idx + 2);
         self.add_triangle(idx + 2, idx + 1, idx + 3);
 
-        let right_top = Vertex {
-            pos: pos.right_top(),
-            uv: uv.right_top(),
-            color,
-        };
-        let left_top = Vertex {
-            pos: pos.left_top(),
+        self.vertices.push(Vertex {
+            pos: left_top,
             uv: uv.left_top(),
             color,
-        };
-        let left_bottom = Vertex {
-            pos: pos.left_bottom(),
+        });
+        self.vertices.push(Vertex {
+            pos: right_top,
+            uv: uv.right_top(),
+            color,
+        });
+        self.vertices.push(Vertex {
+            pos: left_bottom,
             uv: uv.left_bottom(),
             color,
-        };
-        let right_bottom = Vertex {
-            pos: pos.right_bottom(),
+        });
+        self.vertices.push(Vertex {
+            pos: right_bottom,
             uv: uv.right_bottom(),
             color,
-        };
-        self.vertices.push(left_top);
-        self.vertices.push(right_top);
-        self.vertices.push(left_bottom);
-        self.vertices.push(right_bottom);
+        });
+    }
+
+    /// Rectangle with a texture and color, rotated by `angle` radians (clockwise) around its center.
+    pub fn add_rotated_rect_with_uv(&mut self, pos: Rect, uv: Rect, color: Srgba, angle: f32) {
+        let transform = Transform::rotation_around(angle, pos.center());
+        self.add_quad_with_uv(
+            [
+                transform * pos.left_top(),
+                transform * pos.right_top(),
+                transform * pos.right_bottom(),
+                transform * pos.left_bottom(),
+            ],
+            uv,
+            color,
+        );
+    }
+
+    /// Move, rotate, scale and/or skew all the vertices.
+    pub fn transform(&mut self, transform: &Transform) {
+        for vertex in &mut self.vertices {
+            vertex.pos = transform.transform_pos(vertex.pos);
+        }
     }
 
     /// Uniformly colored rectangle.
@@ -182,6 +225,8 @@ impl Triangles {
     }
 
     /// This is for platforms that only support 16-bit index buffers.
+    /// Backends that support 32-bit indices (OpenGL, or WebGL with `OES_element_index_uint`)
+    /// should draw each mesh as it is instead.
     ///
     /// Splits this mesh into many smaller meshes (if needed).
     /// All the returned meshes will have indices that fit into a `u16`.
@@ -358,29 +403,36 @@ pub(crate) mod path {
     use super::*;
 
     /// overwrites existing points
-    pub fn rounded_rectangle(path: &mut Vec<Pos2>, rect: Rect, corner_radius: f32) {
+    pub fn rounded_rectangle(path: &mut Vec<Pos2>, rect: Rect, rounding: Rounding) {
         path.clear();
 
         let min = rect.min;
         let max = rect.max;
 
-        let cr = corner_radius
-            .min(rect.width() * 0.5)
-            .min(rect.height() * 0.5);
+        let max_radius = (rect.width() * 0.5).min(rect.height() * 0.5);
+        let rounding = rounding.at_most(max_radius);
 
-        if cr <= 0.0 {
-            let min = rect.min;
-            let max = rect.max;
+        if rounding.is_none() {
             path.reserve(4);
             path.push(pos2(min.x, min.y));
             path.push(pos2(max.x, min.y));
             path.push(pos2(max.x, max.y));
             path.push(pos2(min.x, max.y));
         } else {
-            add_circle_quadrant(path, pos2(max.x - cr, max.y - cr), cr, 0.0);
-            add_circle_quadrant(path, pos2(min.x + cr, max.y - cr), cr, 1.0);
-            add_circle_quadrant(path, pos2(min.x + cr, min.y + cr), cr, 2.0);
-            add_circle_quadrant(path, pos2(max.x - cr, min.y + cr), cr, 3.0);
+            let Rounding { nw, ne, sw, se } = rounding;
+            add_corner(path, pos2(max.x, max.y), vec2(-se, -se), se, 0.0);
+            add_corner(path, pos2(min.x, max.y), vec2(sw, -sw), sw, 1.0);
+            add_corner(path, pos2(min.x, min.y), vec2(nw, nw), nw, 2.0);
+            add_corner(path, pos2(max.x, min.y), vec2(-ne, ne), ne, 3.0);
+        }
+    }
+
+    /// A sharp corner if `radius <= 0`, else a quadrant of a circle centered at `corner + to_center`.
+    fn add_corner(path: &mut Vec<Pos2>, corner: Pos2, to_center: Vec2, radius: f32, quadrant: f32) {
+        if radius <= 0.0 {
+            path.push(corner);
+        } else {
+            add_circle_quadrant(path, corner + to_center, radius, quadrant);
         }
     }
 
@@ -418,6 +470,80 @@ pub(crate) mod path {
             path.push(center + radius * Vec2::angled(angle));
         }
     }
+
+    /// Add a quadratic Bezier curve, flattened into line segments.
+    ///
+    /// No point on the curve will be further than `tolerance` from the resulting line segments.
+    pub fn add_quadratic_bezier(path: &mut Vec<Pos2>, points: [Pos2; 3], tolerance: f32) {
+        let [p0, p1, p2] = points;
+        // The second derivative is constant: B''(t) = 2 * (p0 - 2 p1 + p2).
+        // Splitting into `n` segments gives an error of at most |B''| / (8 n²).
+        let dd = ((p0 - p1) + (p2 - p1)).length();
+        let n = segment_count(2.0 * dd, tolerance);
+        path.reserve(n + 1);
+        for i in 0..=n {
+            let t = i as f32 / n as f32;
+            let mt = 1.0 - t;
+            path.push(p0 + 2.0 * mt * t * (p1 - p0) + t * t * (p2 - p0));
+        }
+    }
+
+    /// Add a cubic Bezier curve, flattened into line segments.
+    ///
+    /// No point on the curve will be further than `tolerance` from the resulting line segments.
+    pub fn add_cubic_bezier(path: &mut Vec<Pos2>, points: [Pos2; 4], tolerance: f32) {
+        let [p0, p1, p2, p3] = points;
+        // B''(t) = 6 * ((1 - t) * (p0 - 2 p1 + p2) + t * (p1 - 2 p2 + p3)),
+        // so |B''| is bounded by six times the largest of the two second differences.
+        let dd0 = ((p0 - p1) + (p2 - p1)).length();
+        let dd1 = ((p1 - p2) + (p3 - p2)).length();
+        let n = segment_count(6.0 * dd0.max(dd1), tolerance);
+        path.reserve(n + 1);
+        for i in 0..=n {
+            let t = i as f32 / n as f32;
+            let mt = 1.0 - t;
+            path.push(
+                p0 + 3.0 * mt * mt * t * (p1 - p0)
+                    + 3.0 * mt * t * t * (p2 - p0)
+                    + t * t * t * (p3 - p0),
+            );
+        }
+    }
+
+    /// Add a circular arc going from `start_angle` to `end_angle` (radians), flattened into line segments.
+    ///
+    /// No point on the arc will be further than `tolerance` from the resulting line segments.
+    pub fn add_arc(
+        path: &mut Vec<Pos2>,
+        center: Pos2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        tolerance: f32,
+    ) {
+        // Going around more than once would only paint over the same circle again:
+        let end_angle = start_angle + clamp(end_angle - start_angle, -TAU..=TAU);
+        let sweep = (end_angle - start_angle).abs();
+        let n = super::arc_segment_count(radius, sweep, tolerance);
+        path.reserve(n + 1);
+        for i in 0..=n {
+            let angle = remap(i as f32, 0.0..=n as f32, start_angle..=end_angle);
+            path.push(center + radius * Vec2::angled(angle));
+        }
+    }
+
+    /// How many line segments are needed to approximate a curve whose
+    /// second derivative never exceeds `max_second_derivative`.
+    fn segment_count(max_second_derivative: f32, tolerance: f32) -> usize {
+        let n = (max_second_derivative / (8.0 * tolerance.max(1e-3)))
+            .sqrt()
+            .ceil();
+        if n.is_finite() {
+            clamp(n as usize, 1..=1024)
+        } else {
+            1
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -442,6 +568,17 @@ pub struct PaintOptions {
     pub debug_paint_clip_rects: bool,
     /// If true, no clipping will be done
     pub debug_ignore_clip_rects: bool,
+    /// Clip the triangles to their clip rectangles on the CPU, instead of leaving it to the backend.
+    ///
+    /// All paint jobs will then have `Rect::everything()` as their clip rectangle,
+    /// so `merge_adjacent_jobs` can merge all consecutive jobs using the same texture,
+    /// and usually everything ends up in a single `Triangles`.
+    /// Useful for backends without scissor support, like a software rasterizer or SVG output.
+    pub cpu_clipping: bool,
+    /// Maximum distance (in points) between a curve (arcs, Bezier curves)
+    /// and the line segments used to approximate it.
+    /// Smaller values give smoother curves, but require more triangles.
+    pub tolerance: f32,
 }
 
 impl Default for PaintOptions {
@@ -451,13 +588,48 @@ impl Default for PaintOptions {
             anti_alias: true,
             debug_paint_clip_rects: false,
             debug_ignore_clip_rects: false,
+            cpu_clipping: false,
             coarse_tessellation_culling: true,
+            tolerance: 0.1,
+        }
+    }
+}
+
+impl PaintOptions {
+    /// Width of the anti-aliased edge of clip masks.
+    fn feathering(&self) -> f32 {
+        if self.anti_alias {
+            self.aa_size
+        } else {
+            0.0
         }
     }
 }
 
 /// Tesselate the given convex area into a polygon.
-fn fill_closed_path(path: &[PathPoint], color: Srgba, options: PaintOptions, out: &mut Triangles) {
+fn fill_closed_path(path: &[PathPoint], fill: &Fill, options: PaintOptions, out: &mut Triangles) {
+    match fill {
+        Fill::Solid(color) => fill_closed_path_with_color(path, *color, options, out),
+        Fill::LinearGradient(gradient) => {
+            let polygon = inset_polygon(path, options);
+            fill_convex_linear_gradient(&polygon, gradient, out);
+            feather_edges(path, &polygon, fill, options, out);
+        }
+        Fill::RadialGradient(gradient) => {
+            let polygon = inset_polygon(path, options);
+            fill_convex_radial_gradient(&polygon, gradient, out);
+            feather_edges(path, &polygon, fill, options, out);
+        }
+    }
+}
+
+/// Tesselate the given convex area into a polygon with a single color.
+fn fill_closed_path_with_color(
+    path: &[PathPoint],
+    color: Srgba,
+    options: PaintOptions,
+    out: &mut Triangles,
+) {
     if color == color::TRANSPARENT {
         return;
     }
@@ -496,7 +668,275 @@ fn fill_closed_path(path: &[PathPoint], color: Srgba, options: PaintOptions, out
     }
 }
 
-/// Tesselate the given path as a stroke with thickness.
+/// The polygon that is filled with full opacity.
+/// With anti-aliasing it is shrunk by half a pixel, to make room for `feather_edges`.
+fn inset_polygon(path: &[PathPoint], options: PaintOptions) -> Vec<Pos2> {
+    let inset = if options.anti_alias {
+        0.5 * options.aa_size
+    } else {
+        0.0
+    };
+    path.iter().map(|p| p.pos - inset * p.normal).collect()
+}
+
+/// Add an anti-aliasing fringe between the `inset_polygon` and the outside of the path.
+fn feather_edges(
+    path: &[PathPoint],
+    inset_polygon: &[Pos2],
+    fill: &Fill,
+    options: PaintOptions,
+    out: &mut Triangles,
+) {
+    if !options.anti_alias || path.len() < 3 {
+        return;
+    }
+
+    let n = path.len() as u32;
+    out.reserve_triangles(2 * n as usize);
+    out.reserve_vertices(2 * n as usize);
+    let idx_inner = out.vertices.len() as u32;
+    let idx_outer = idx_inner + 1;
+    let mut i0 = n - 1;
+    for i1 in 0..n {
+        let p1 = &path[i1 as usize];
+        let inner = inset_polygon[i1 as usize];
+        out.colored_vertex(inner, fill.color_at(inner));
+        out.colored_vertex(p1.pos + 0.5 * options.aa_size * p1.normal, TRANSPARENT);
+        out.add_triangle(idx_inner + i1 * 2, idx_inner + i0 * 2, idx_outer + 2 * i0);
+        out.add_triangle(idx_outer + i0 * 2, idx_outer + i1 * 2, idx_inner + 2 * i1);
+        i0 = i1;
+    }
+}
+
+/// Fill a convex polygon with a linear gradient.
+///
+/// The polygon is cut into slices at each color stop,
+/// so that the colors are linear (and can be interpolated by the GPU) within each slice.
+fn fill_convex_linear_gradient(polygon: &[Pos2], gradient: &LinearGradient, out: &mut Triangles) {
+    let (mut min_offset, mut max_offset) = (f32::INFINITY, f32::NEG_INFINITY);
+    for &p in polygon {
+        let offset = gradient.offset_at(p);
+        min_offset = min_offset.min(offset);
+        max_offset = max_offset.max(offset);
+    }
+
+    let mut cuts = vec![f32::NEG_INFINITY];
+    cuts.extend(
+        gradient
+            .stops
+            .iter()
+            .map(|stop| stop.offset)
+            .filter(|&offset| min_offset < offset && offset < max_offset),
+    );
+    cuts.push(f32::INFINITY);
+
+    let mut above = vec![];
+    let mut slice = vec![];
+    for range in cuts.windows(2) {
+        let (lo, hi) = (range[0], range[1]);
+        clip_convex_polygon(polygon, |p| gradient.offset_at(p) - lo, &mut above);
+        clip_convex_polygon(&above, |p| hi - gradient.offset_at(p), &mut slice);
+        add_convex_polygon(&slice, |p| gradient.color_at(p).into(), out);
+    }
+}
+
+/// Fill a convex polygon with a radial gradient.
+///
+/// Since colors change non-linearly with the distance to the center,
+/// triangles are subdivided until linear interpolation is close enough.
+fn fill_convex_radial_gradient(polygon: &[Pos2], gradient: &RadialGradient, out: &mut Triangles) {
+    let n = polygon.len();
+    if n < 3 {
+        return;
+    }
+    // Fan out from the center of the gradient (if inside) since the distance to the center
+    // changes linearly along each spoke. Otherwise use the centroid, which gives less slivers.
+    let inside = (0..n).all(|i| {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        let (ab, ac) = (b - a, gradient.center - a);
+        ab.x * ac.y - ab.y * ac.x >= 0.0
+    }) || (0..n).all(|i| {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        let (ab, ac) = (b - a, gradient.center - a);
+        ab.x * ac.y - ab.y * ac.x <= 0.0
+    });
+    let fan_center = if inside {
+        gradient.center
+    } else {
+        polygon[0]
+            + polygon
+                .iter()
+                .fold(Vec2::zero(), |sum, &p| sum + (p - polygon[0]))
+                / n as f32
+    };
+    for i in 0..n {
+        subdivide_triangle(
+            [fan_center, polygon[i], polygon[(i + 1) % n]],
+            gradient,
+            0,
+            out,
+        );
+    }
+}
+
+fn subdivide_triangle(
+    [a, b, c]: [Pos2; 3],
+    gradient: &RadialGradient,
+    depth: usize,
+    out: &mut Triangles,
+) {
+    const MAX_DEPTH: usize = 14;
+
+    if depth < MAX_DEPTH && needs_subdivision([a, b, c], gradient) {
+        // Split the longest edge in half:
+        let (a, b, c) =
+            if a.distance_sq(b) >= b.distance_sq(c) && a.distance_sq(b) >= c.distance_sq(a) {
+                (a, b, c)
+            } else if b.distance_sq(c) >= c.distance_sq(a) {
+                (b, c, a)
+            } else {
+                (c, a, b)
+            };
+        // Prefer to split where a color stop crosses the edge, so both halves become smoother:
+        let (ta, tb) = (gradient.offset_at(a), gradient.offset_at(b));
+        let split = gradient
+            .stops
+            .iter()
+            .map(|stop| (stop.offset - ta) / (tb - ta))
+            .find(|t| 0.1 < *t && *t < 0.9)
+            .unwrap_or(0.5);
+        let m = a + split * (b - a);
+        subdivide_triangle([a, m, c], gradient, depth + 1, out);
+        subdivide_triangle([m, b, c], gradient, depth + 1, out);
+    } else {
+        add_convex_polygon(&[a, b, c], |p| gradient.color_at(p).into(), out);
+    }
+}
+
+/// Would linear interpolation between the corner colors give a visibly wrong result?
+fn needs_subdivision([a, b, c]: [Pos2; 3], gradient: &RadialGradient) -> bool {
+    /// Don't bother splitting triangles smaller than this (in points).
+    const MIN_EDGE_LENGTH: f32 = 2.0;
+    /// Always split triangles larger than this if a color stop passes through them.
+    const MAX_EDGE_LENGTH_AT_STOP: f32 = 8.0;
+    /// Maximum difference of any sRGBA channel.
+    const MAX_COLOR_ERROR: i32 = 4;
+
+    let longest_sq = a.distance_sq(b).max(b.distance_sq(c)).max(c.distance_sq(a));
+    if longest_sq < MIN_EDGE_LENGTH * MIN_EDGE_LENGTH {
+        return false;
+    }
+
+    // The colors change abruptly at the stops, so make sure no big triangle has a stop inside:
+    let closest = if triangle_contains([a, b, c], gradient.center) {
+        0.0
+    } else {
+        distance_to_segment(gradient.center, [a, b])
+            .min(distance_to_segment(gradient.center, [b, c]))
+            .min(distance_to_segment(gradient.center, [c, a]))
+    };
+    let furthest = gradient
+        .center
+        .distance(a)
+        .max(gradient.center.distance(b))
+        .max(gradient.center.distance(c));
+    let (min_offset, max_offset) = if gradient.radius > 0.0 {
+        (closest / gradient.radius, furthest / gradient.radius)
+    } else {
+        (1.0, 1.0)
+    };
+    if longest_sq > MAX_EDGE_LENGTH_AT_STOP * MAX_EDGE_LENGTH_AT_STOP
+        && gradient
+            .stops
+            .iter()
+            .any(|stop| min_offset < stop.offset && stop.offset < max_offset)
+    {
+        return true;
+    }
+
+    // Compare what the GPU would interpolate with the real colors:
+    let (ca, cb, cc) = (
+        gradient.color_at(a),
+        gradient.color_at(b),
+        gradient.color_at(c),
+    );
+    let samples = [
+        (a + 0.5 * (b - a), 0.5 * (ca + cb)),
+        (b + 0.5 * (c - b), 0.5 * (cb + cc)),
+        (c + 0.5 * (a - c), 0.5 * (cc + ca)),
+        (a + ((b - a) + (c - a)) / 3.0, (ca + cb + cc) * (1.0 / 3.0)),
+    ];
+    samples.iter().any(|&(pos, interpolated)| {
+        let actual = Srgba::from(gradient.color_at(pos));
+        let interpolated = Srgba::from(interpolated);
+        (0..4).any(|i| (actual[i] as i32 - interpolated[i] as i32).abs() > MAX_COLOR_ERROR)
+    })
+}
+
+fn triangle_contains([a, b, c]: [Pos2; 3], p: Pos2) -> bool {
+    let cross = |o: Pos2, u: Pos2, v: Pos2| (u.x - o.x) * (v.y - o.y) - (u.y - o.y) * (v.x - o.x);
+    let d0 = cross(a, b, p);
+    let d1 = cross(b, c, p);
+    let d2 = cross(c, a, p);
+    let has_neg = d0 < 0.0 || d1 < 0.0 || d2 < 0.0;
+    let has_pos = d0 > 0.0 || d1 > 0.0 || d2 > 0.0;
+    !(has_neg && has_pos)
+}
+
+fn distance_to_segment(p: Pos2, [a, b]: [Pos2; 2]) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.length_sq();
+    if length_sq <= 0.0 {
+        return p.distance(a);
+    }
+    let ap = p - a;
+    let t = clamp((ap.x * ab.x + ap.y * ab.y) / length_sq, 0.0..=1.0);
+    p.distance(a + t * ab)
+}
+
+/// Sutherland-Hodgman: keep the part of the convex `polygon` where `signed_distance >= 0`.
+/// `signed_distance` must be an affine function of the position.
+fn clip_convex_polygon(
+    polygon: &[Pos2],
+    signed_distance: impl Fn(Pos2) -> f32,
+    out: &mut Vec<Pos2>,
+) {
+    out.clear();
+    let n = polygon.len();
+    for i in 0..n {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % n];
+        let da = signed_distance(a);
+        let db = signed_distance(b);
+        if da >= 0.0 {
+            out.push(a);
+        }
+        if (da >= 0.0) != (db >= 0.0) {
+            let t = da / (da - db);
+            out.push(a + t * (b - a));
+        }
+    }
+}
+
+/// Add a triangle fan covering the given convex polygon.
+fn add_convex_polygon(polygon: &[Pos2], color_at: impl Fn(Pos2) -> Srgba, out: &mut Triangles) {
+    let n = polygon.len() as u32;
+    if n < 3 {
+        return;
+    }
+    out.reserve_triangles(n as usize - 2);
+    out.reserve_vertices(n as usize);
+    let idx = out.vertices.len() as u32;
+    for &p in polygon {
+        out.colored_vertex(p, color_at(p));
+    }
+    for i in 2..n {
+        out.add_triangle(idx, idx + i - 1, idx + i);
+    }
+}
+
+/// Tesselate the given path as a stroke with thickness,
+/// applying the dash pattern, line caps and line joins of the `stroke`.
 fn stroke_path(
     path: &[PathPoint],
     path_type: PathType,
@@ -504,10 +944,364 @@ fn stroke_path(
     options: PaintOptions,
     out: &mut Triangles,
 ) {
-    if stroke.width <= 0.0 || stroke.color == color::TRANSPARENT {
+    if stroke.width <= 0.0 || stroke.color == color::TRANSPARENT || path.len() < 2 {
+        return;
+    }
+
+    match stroke.dash {
+        Some(dash) if dash.length + dash.gap > 0.0 => {
+            stroke_dashed(path, path_type, stroke, dash, options, out)
+        }
+        _ => stroke_solid(path, path_type, stroke, options, out),
+    }
+}
+
+/// Stroke a path without gaps.
+fn stroke_solid(
+    path: &[PathPoint],
+    path_type: PathType,
+    stroke: Stroke,
+    options: PaintOptions,
+    out: &mut Triangles,
+) {
+    let mut path = apply_line_join(path, path_type, stroke, options);
+
+    let thin_line = stroke.width <= options.aa_size;
+    let round_caps = stroke.cap == LineCap::Round && !thin_line;
+    let extend_ends = match stroke.cap {
+        LineCap::Butt => false,
+        LineCap::Round => thin_line, // Too thin to see the difference
+        LineCap::Square => true,
+    };
+
+    if path_type == Open && extend_ends {
+        let half_width = 0.5 * stroke.width;
+        let path = path.to_mut();
+        let last = path.len() - 1;
+        let start_dir = path[0].normal.rot90(); // points backwards
+        let end_dir = -path[last].normal.rot90(); // points forwards
+        path[0].pos += half_width * start_dir;
+        path[last].pos += half_width * end_dir;
+    }
+
+    stroke_strip(&path, path_type, stroke, options, out);
+
+    if path_type == Open && round_caps {
+        let first = &path[0];
+        let last = &path[path.len() - 1];
+        add_round_cap(
+            first.pos,
+            first.normal,
+            first.normal.rot90(),
+            stroke,
+            options,
+            out,
+        );
+        add_round_cap(
+            last.pos,
+            -last.normal,
+            -last.normal.rot90(),
+            stroke,
+            options,
+            out,
+        );
+    }
+}
+
+/// Replace corners that are too sharp for a miter join (or all visible corners,
+/// for round and bevel joins) with several points sharing the same position.
+///
+/// Returns the input unchanged (and unallocated) if there are no such corners.
+fn apply_line_join<'p>(
+    path: &'p [PathPoint],
+    path_type: PathType,
+    stroke: Stroke,
+    options: PaintOptions,
+) -> Cow<'p, [PathPoint]> {
+    let half_width = 0.5 * stroke.width;
+    let needs_join = |p: &PathPoint| {
+        let miter_length_sq = p.normal.length_sq();
+        match stroke.join {
+            LineJoin::Miter { limit } => miter_length_sq > limit * limit,
+            LineJoin::Round | LineJoin::Bevel => {
+                // Skip corners where the miter doesn't stick out noticeably:
+                half_width * (miter_length_sq.sqrt() - 1.0) > options.tolerance
+            }
+        }
+    };
+
+    let n = path.len();
+    let corners = if path_type == Closed { 0..n } else { 1..n - 1 };
+    if !path[corners.clone()].iter().any(needs_join) {
+        return Cow::Borrowed(path);
+    }
+
+    let mut joined = Vec::with_capacity(2 * n);
+    for (i, point) in path.iter().enumerate() {
+        if !corners.contains(&i) || !needs_join(point) {
+            joined.push(point.clone());
+            continue;
+        }
+
+        let prev = path[(i + n - 1) % n].pos;
+        let next = path[(i + 1) % n].pos;
+        let n0 = (point.pos - prev).normalized().rot90();
+        let n1 = (next - point.pos).normalized().rot90();
+        if n0 == Vec2::zero() || n1 == Vec2::zero() {
+            joined.push(point.clone());
+            continue;
+        }
+
+        match stroke.join {
+            LineJoin::Miter { .. } | LineJoin::Bevel => {
+                joined.push(PathPoint {
+                    pos: point.pos,
+                    normal: n0,
+                });
+                joined.push(PathPoint {
+                    pos: point.pos,
+                    normal: n1,
+                });
+            }
+            LineJoin::Round => {
+                // Only the outer side is rounded. The inner side stays at the miter point,
+                // so that the join doesn't paint over the two segments (which would show
+                // for translucent strokes). Each point of the arc is then painted as the
+                // middle of the inner miter point and the arc point.
+                let cross = n0.x * n1.y - n0.y * n1.x;
+                let dot = n0.x * n1.x + n0.y * n1.y;
+                let angle = cross.atan2(dot);
+                let to_next = next - point.pos;
+                let inner_side = if to_next.x * n0.x + to_next.y * n0.y > 0.0 {
+                    1.0
+                } else {
+                    -1.0
+                };
+                let v = 0.5 * (n0 + n1);
+                let miter = if v.length_sq() > 1e-6 {
+                    v / v.length_sq()
+                } else {
+                    Vec2::zero() // The path turns back on itself, so there is no inner corner.
+                };
+                let segments = arc_segment_count(half_width, angle.abs(), options.tolerance);
+                for s in 0..=segments {
+                    let a = angle * s as f32 / segments as f32;
+                    let arc_normal = Vec2::angled(a).rotate_other(n0);
+                    joined.push(PathPoint {
+                        pos: point.pos + 0.5 * inner_side * half_width * (miter - arc_normal),
+                        normal: 0.5 * (miter + arc_normal),
+                    });
+                }
+            }
+        }
+    }
+    Cow::Owned(joined)
+}
+
+/// Paint a half-circle at the end of a stroke.
+///
+/// The cap starts at `center + normal * radius` and bulges out in the direction of `outward`.
+fn add_round_cap(
+    center: Pos2,
+    normal: Vec2,
+    outward: Vec2,
+    stroke: Stroke,
+    options: PaintOptions,
+    out: &mut Triangles,
+) {
+    let half_width = 0.5 * stroke.width;
+    let segments = arc_segment_count(half_width, TAU / 2.0, options.tolerance).at_least(2);
+    let dir = |i: usize| {
+        let angle = remap(i as f32, 0.0..=segments as f32, 0.0..=TAU / 2.0);
+        angle.cos() * normal + angle.sin() * outward
+    };
+
+    let idx = out.vertices.len() as u32;
+    out.colored_vertex(center, stroke.color);
+
+    if options.anti_alias {
+        let inner_rad = 0.5 * (stroke.width - options.aa_size);
+        let outer_rad = 0.5 * (stroke.width + options.aa_size);
+        out.reserve_triangles(3 * segments);
+        out.reserve_vertices(2 * segments + 3);
+        for i in 0..=segments {
+            let d = dir(i);
+            out.colored_vertex(center + inner_rad * d, stroke.color);
+            out.colored_vertex(center + outer_rad * d, color::TRANSPARENT);
+        }
+        for i in 0..segments as u32 {
+            let inner0 = idx + 1 + 2 * i;
+            let outer0 = inner0 + 1;
+            let inner1 = inner0 + 2;
+            let outer1 = inner0 + 3;
+            out.add_triangle(idx, inner0, inner1);
+            out.add_triangle(inner0, outer0, inner1);
+            out.add_triangle(outer0, outer1, inner1);
+        }
+    } else {
+        out.reserve_triangles(segments);
+        out.reserve_vertices(segments + 2);
+        for i in 0..=segments {
+            out.colored_vertex(center + half_width * dir(i), stroke.color);
+        }
+        for i in 0..segments as u32 {
+            out.add_triangle(idx, idx + 1 + i, idx + 2 + i);
+        }
+    }
+}
+
+/// Dash patterns that would need more dashes than this for one path are painted solid instead.
+const MAX_DASHES: f32 = 10_000.0;
+
+/// Break up a path into dashes and stroke each of them.
+fn stroke_dashed(
+    path: &[PathPoint],
+    path_type: PathType,
+    stroke: Stroke,
+    dash: Dash,
+    options: PaintOptions,
+    out: &mut Triangles,
+) {
+    let mut points: Vec<Pos2> = path.iter().map(|p| p.pos).collect();
+    if path_type == Closed {
+        points.push(path[0].pos);
+    }
+
+    // A pattern much finer than the stroke or a pixel looks solid anyway,
+    // so scale it up rather than producing a huge number of tiny dashes:
+    let length = dash.length.max(0.0);
+    let gap = dash.gap.max(0.0);
+    let period = length + gap;
+    let min_period = stroke.width.max(options.aa_size);
+    let dash = if period < min_period {
+        Dash {
+            length: length / period * min_period,
+            gap: gap / period * min_period,
+            offset: dash.offset.rem_euclid(period) / period * min_period,
+        }
+    } else {
+        dash
+    };
+
+    let path_length: f32 = points.windows(2).map(|w| w[0].distance(w[1])).sum();
+    let period = dash.length.max(0.0) + dash.gap.max(0.0);
+    if !path_length.is_finite() || path_length / period > MAX_DASHES {
+        stroke_solid(path, path_type, stroke, options, out);
+        return;
+    }
+
+    let mut dash_path = Path::default();
+    for_each_dash(&points, dash, |dash_points, dir| {
+        dash_path.clear();
+        let is_dot = dash_points
+            .iter()
+            .all(|&p| p.distance_sq(dash_points[0]) < 1e-6);
+        if is_dot {
+            // A zero-length dash. Only the caps will be visible.
+            let normal = dir.rot90();
+            dash_path.add_point(dash_points[0], normal);
+            dash_path.add_point(dash_points[0], normal);
+        } else {
+            dash_path.add_open_points(dash_points);
+        }
+        stroke_solid(&dash_path.0, Open, stroke, options, out);
+    });
+}
+
+/// Calls `add_dash` with the points of each dash along the polyline `points`,
+/// together with the direction of the line segment the dash ends in.
+fn for_each_dash(points: &[Pos2], dash: Dash, mut add_dash: impl FnMut(&[Pos2], Vec2)) {
+    let length = dash.length.max(0.0);
+    let gap = dash.gap.max(0.0);
+    let period = length + gap;
+    if period <= 0.0 {
         return;
     }
+    let phase = dash.offset.rem_euclid(period);
+
+    // A zero-length dash at the very start of the pattern is a dot on the first point:
+    let (mut on, mut remaining) = if phase < length || phase == 0.0 {
+        (true, length - phase)
+    } else {
+        (false, period - phase)
+    };
+
+    let mut current: Vec<Pos2> = vec![];
+    let push = |current: &mut Vec<Pos2>, p: Pos2| {
+        if current.last() != Some(&p) {
+            current.push(p);
+        }
+    };
+
+    if on {
+        current.push(points[0]);
+    }
+
+    let mut dir = Vec2::zero();
+    for segment in points.windows(2) {
+        let (a, b) = (segment[0], segment[1]);
+        let segment_length = a.distance(b);
+        if segment_length <= 0.0 {
+            continue;
+        }
+        dir = (b - a) / segment_length;
+
+        let mut t = 0.0;
+        while t + remaining <= segment_length {
+            t += remaining;
+            let p = a + t * dir;
+            if on {
+                push(&mut current, p);
+                if current.len() == 1 {
+                    current.push(p); // zero-length dash
+                }
+                add_dash(&current, dir);
+                current.clear();
+                on = false;
+                remaining = gap;
+            } else {
+                current.push(p);
+                on = true;
+                remaining = length;
+            }
+        }
+        remaining -= segment_length - t;
+
+        if on {
+            push(&mut current, b);
+        }
+    }
 
+    // Only dots may end up as a single point; a dash starting right at the end is not painted:
+    if on && (current.len() > 1 || (current.len() == 1 && length == 0.0)) {
+        if current.len() == 1 {
+            let p = current[0];
+            current.push(p);
+        }
+        add_dash(&current, dir);
+    }
+}
+
+/// How many line segments are needed to approximate an arc with the given radius
+/// and sweep angle (in radians) so that the error stays below `tolerance`.
+fn arc_segment_count(radius: f32, sweep: f32, tolerance: f32) -> usize {
+    // A chord spanning the angle `a` deviates `radius * (1 - cos(a / 2))` from the arc.
+    let max_angle = if tolerance < radius {
+        2.0 * (1.0 - tolerance / radius).acos()
+    } else {
+        TAU / 4.0
+    };
+    clamp((sweep / max_angle).ceil() as usize, 1..=1024)
+}
+
+/// Tesselate the given path as a continuous strip with thickness, without caps.
+fn stroke_strip(
+    path: &[PathPoint],
+    path_type: PathType,
+    stroke: Stroke,
+    options: PaintOptions,
+    out: &mut Triangles,
+) {
     let n = path.len() as u32;
     let idx = out.vertices.len() as u32;
 
@@ -635,6 +1429,45 @@ fn stroke_path(
     }
 }
 
+/// Fill and stroke a flattened curve.
+fn tessellate_curve(
+    points: &[Pos2],
+    closed: bool,
+    fill: &Fill,
+    stroke: Stroke,
+    options: PaintOptions,
+    path: &mut Path,
+    out: &mut Triangles,
+) {
+    if points.len() < 2 {
+        return;
+    }
+
+    if closed {
+        path.add_line_loop(points);
+    } else {
+        path.add_open_points(points);
+    }
+
+    if !fill.is_transparent() {
+        debug_assert!(
+            closed,
+            "You asked to fill a curve that is not closed. That makes no sense."
+        );
+        fill_closed_path(&path.0, fill, options, out);
+    }
+    let typ = if closed { Closed } else { Open };
+    stroke_path(&path.0, typ, stroke, options, out);
+}
+
+fn bounding_rect(points: &[Pos2]) -> Rect {
+    let mut rect = Rect::nothing();
+    for &p in points {
+        rect.extend_with(p);
+    }
+    rect
+}
+
 fn mul_color(color: Srgba, factor: f32) -> Srgba {
     debug_assert!(0.0 <= factor && factor <= 1.0);
     // sRGBA correct fading requires conversion to linear space and back again because of premultiplied alpha
@@ -682,12 +1515,70 @@ fn tessellate_paint_command(
             }
 
             path.add_circle(center, radius);
-            fill_closed_path(&path.0, fill, options, out);
+            fill_closed_path(&path.0, &fill, options, out);
             stroke_path(&path.0, Closed, stroke, options, out);
         }
         PaintCmd::Triangles(triangles) => {
             out.append(&triangles);
         }
+        PaintCmd::Transformed {
+            transform,
+            commands,
+        } => {
+            // We don't know where things end up until after they are transformed:
+            let options = PaintOptions {
+                coarse_tessellation_culling: false,
+                ..options
+            };
+            let first_vertex = out.vertices.len();
+            for command in commands {
+                tessellate_paint_command(
+                    clip_rect,
+                    command,
+                    options,
+                    fonts,
+                    out,
+                    scratchpad_points,
+                    path,
+                );
+            }
+            for vertex in &mut out.vertices[first_vertex..] {
+                vertex.pos = transform.transform_pos(vertex.pos);
+            }
+        }
+        PaintCmd::Clipped {
+            rect,
+            rounding,
+            commands,
+        } => {
+            if options.coarse_tessellation_culling && !rect.intersects(clip_rect) {
+                return;
+            }
+
+            let mut unclipped = Triangles::with_texture(out.texture_id);
+            for command in commands {
+                tessellate_paint_command(
+                    clip_rect,
+                    command,
+                    options,
+                    fonts,
+                    &mut unclipped,
+                    scratchpad_points,
+                    path,
+                );
+            }
+            if options.debug_ignore_clip_rects {
+                out.append(&unclipped);
+            } else {
+                clipping::clip_to_rounded_rect(
+                    &unclipped,
+                    rect,
+                    rounding,
+                    options.feathering(),
+                    out,
+                );
+            }
+        }
         PaintCmd::LineSegment { points, stroke } => {
             path.add_line_segment(points);
             stroke_path(&path.0, Open, stroke, options, out);
@@ -705,20 +1596,91 @@ fn tessellate_paint_command(
                     path.add_open_points(&points);
                 }
 
-                if fill != TRANSPARENT {
+                if !fill.is_transparent() {
                     debug_assert!(
                         closed,
                         "You asked to fill a path that is not closed. That makes no sense."
                     );
-                    fill_closed_path(&path.0, fill, options, out);
+                    fill_closed_path(&path.0, &fill, options, out);
                 }
                 let typ = if closed { Closed } else { Open };
                 stroke_path(&path.0, typ, stroke, options, out);
             }
         }
+        PaintCmd::QuadraticBezier {
+            points,
+            closed,
+            fill,
+            stroke,
+        } => {
+            if options.coarse_tessellation_culling
+                && !bounding_rect(&points)
+                    .expand(stroke.width)
+                    .intersects(clip_rect)
+            {
+                return;
+            }
+
+            scratchpad_points.clear();
+            path::add_quadratic_bezier(scratchpad_points, points, options.tolerance);
+            tessellate_curve(scratchpad_points, closed, &fill, stroke, options, path, out);
+        }
+        PaintCmd::CubicBezier {
+            points,
+            closed,
+            fill,
+            stroke,
+        } => {
+            if options.coarse_tessellation_culling
+                && !bounding_rect(&points)
+                    .expand(stroke.width)
+                    .intersects(clip_rect)
+            {
+                return;
+            }
+
+            scratchpad_points.clear();
+            path::add_cubic_bezier(scratchpad_points, points, options.tolerance);
+            tessellate_curve(scratchpad_points, closed, &fill, stroke, options, path, out);
+        }
+        PaintCmd::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            closed,
+            fill,
+            stroke,
+        } => {
+            if radius <= 0.0 {
+                return;
+            }
+
+            if options.coarse_tessellation_culling
+                && !clip_rect.expand(radius + stroke.width).contains(center)
+            {
+                return;
+            }
+
+            scratchpad_points.clear();
+            if closed {
+                // Start at the center, so that the fan in `fill_closed_path`
+                // is correct for pie slices wider than half a turn.
+                scratchpad_points.push(center);
+            }
+            path::add_arc(
+                scratchpad_points,
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                options.tolerance,
+            );
+            tessellate_curve(scratchpad_points, closed, &fill, stroke, options, path, out);
+        }
         PaintCmd::Rect {
             mut rect,
-            corner_radius,
+            rounding,
             fill,
             stroke,
         } => {
@@ -737,11 +1699,51 @@ fn tessellate_paint_command(
             rect.min = rect.min.at_least(pos2(-1e7, -1e7));
             rect.max = rect.max.at_most(pos2(1e7, 1e7));
 
-            path::rounded_rectangle(scratchpad_points, rect, corner_radius);
+            path::rounded_rectangle(scratchpad_points, rect, rounding);
             path.add_line_loop(scratchpad_points);
-            fill_closed_path(&path.0, fill, options, out);
+            fill_closed_path(&path.0, &fill, options, out);
             stroke_path(&path.0, Closed, stroke, options, out);
         }
+        PaintCmd::Shadow {
+            rect,
+            rounding,
+            shadow,
+        } => {
+            if !shadow.is_visible() {
+                return;
+            }
+
+            let rect = rect.translate(shadow.offset).expand(shadow.spread);
+            if rect.is_empty() || !rect.is_finite() {
+                return;
+            }
+
+            // `rect` is already offset and spread, so only the soft edge is left to add:
+            if options.coarse_tessellation_culling
+                && !rect.expand(0.5 * shadow.blur).intersects(clip_rect)
+            {
+                return;
+            }
+
+            // The shadow is a filled rounded rectangle with a very wide anti-aliasing fringe.
+            // The fringe can't be wider than the rectangle itself, or it would fold over.
+            let blur = shadow
+                .blur
+                .max(options.aa_size)
+                .min(rect.width())
+                .min(rect.height());
+            // Round corners at least as much as the blur, so the inner edge of the fringe stays convex:
+            let rounding = rounding.map(|r| r + shadow.spread).at_least(0.5 * blur);
+
+            path::rounded_rectangle(scratchpad_points, rect, rounding);
+            path.add_line_loop(scratchpad_points);
+            let feather_options = PaintOptions {
+                anti_alias: true,
+                aa_size: blur,
+                ..options
+            };
+            fill_closed_path_with_color(&path.0, shadow.color, feather_options, out);
+        }
         PaintCmd::Text {
             pos,
             galley,
@@ -801,6 +1803,32 @@ fn tessellate_paint_command(
     }
 }
 
+fn contains_triangles(commands: &[PaintCmd]) -> bool {
+    commands.iter().any(|cmd| match cmd {
+        PaintCmd::Triangles(_) => true,
+        PaintCmd::Transformed { commands, .. } | PaintCmd::Clipped { commands, .. } => {
+            contains_triangles(commands)
+        }
+        _ => false,
+    })
+}
+
+/// Tessellate the contents of a `PaintCmd::Transformed` or `PaintCmd::Clipped`.
+fn tessellate_nested(
+    clip_rect: Rect,
+    commands: Vec<PaintCmd>,
+    options: PaintOptions,
+    fonts: &Fonts,
+) -> PaintJobs {
+    let commands = commands.into_iter().map(|cmd| (clip_rect, cmd)).collect();
+    let options = PaintOptions {
+        debug_paint_clip_rects: false,
+        cpu_clipping: false, // done once everything is tessellated
+        ..options
+    };
+    tessellate_paint_commands(commands, options, fonts)
+}
+
 /// Turns `PaintCmd`:s into sets of triangles.
 ///
 /// The given commands will be painted back-to-front (painters algorithm).
@@ -817,6 +1845,60 @@ pub fn tessellate_paint_commands(
     options: PaintOptions,
     fonts: &Fonts,
 ) -> Vec<(Rect, Triangles)> {
+    let mut jobs = tessellate_into_jobs(commands, options, fonts);
+    clip_jobs_on_cpu(&mut jobs, options);
+    finish_jobs(&mut jobs, options, fonts);
+    jobs
+}
+
+/// Like `tessellate_paint_commands`, but splits the commands into chunks and tessellates them in parallel.
+///
+/// The output is deterministic: the jobs come in the same order as the commands.
+/// Jobs may be split at chunk boundaries, but after `merge_adjacent_jobs`
+/// the output is identical to that of `tessellate_paint_commands`.
+#[cfg(feature = "rayon")]
+pub fn tessellate_paint_commands_par(
+    commands: Vec<(Rect, PaintCmd)>,
+    options: PaintOptions,
+    fonts: &Fonts,
+) -> Vec<(Rect, Triangles)> {
+    use rayon::prelude::*;
+
+    // Enough chunks to balance the load, but not so small that the overhead dominates:
+    const MIN_CHUNK_SIZE: usize = 64;
+    let num_chunks = 4 * rayon::current_num_threads();
+    let chunk_size = (commands.len() / num_chunks).at_least(MIN_CHUNK_SIZE);
+
+    let mut chunks = vec![];
+    let mut commands = commands.into_iter();
+    loop {
+        let chunk: Vec<(Rect, PaintCmd)> = commands.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
+
+    let chunk_jobs: Vec<PaintJobs> = chunks
+        .into_par_iter()
+        .map(|chunk| {
+            let mut jobs = tessellate_into_jobs(chunk, options, fonts);
+            clip_jobs_on_cpu(&mut jobs, options);
+            jobs
+        })
+        .collect(); // `collect` keeps the order
+
+    let mut jobs: PaintJobs = chunk_jobs.into_iter().flatten().collect();
+    finish_jobs(&mut jobs, options, fonts);
+    jobs
+}
+
+/// Tessellate, but without any of the post-processing of `finish_jobs`.
+fn tessellate_into_jobs(
+    commands: Vec<(Rect, PaintCmd)>,
+    options: PaintOptions,
+    fonts: &Fonts,
+) -> PaintJobs {
     let mut scratchpad_points = Vec::new();
     let mut scratchpad_path = Path::default();
 
@@ -824,11 +1906,54 @@ pub fn tessellate_paint_commands(
     for (clip_rect, cmd) in commands {
         // TODO: cull(clip_rect, cmd)
 
-        if let PaintCmd::Triangles(triangles) = cmd {
-            // Assume non-Egui texture, which means own paint job:
-            jobs.push((clip_rect, triangles));
-            continue;
-        }
+        let cmd = match cmd {
+            PaintCmd::Triangles(triangles) => {
+                // Assume non-Egui texture, which means own paint job:
+                jobs.push((clip_rect, triangles));
+                continue;
+            }
+            PaintCmd::Transformed {
+                transform,
+                commands,
+            } if contains_triangles(&commands) => {
+                // The commands may use other textures, so they may need several paint jobs:
+                let options = PaintOptions {
+                    coarse_tessellation_culling: false,
+                    ..options
+                };
+                for (clip_rect, mut triangles) in
+                    tessellate_nested(clip_rect, commands, options, fonts)
+                {
+                    triangles.transform(&transform);
+                    jobs.push((clip_rect, triangles));
+                }
+                continue;
+            }
+            PaintCmd::Clipped {
+                rect,
+                rounding,
+                commands,
+            } if contains_triangles(&commands) => {
+                for (clip_rect, triangles) in tessellate_nested(clip_rect, commands, options, fonts)
+                {
+                    if options.debug_ignore_clip_rects {
+                        jobs.push((clip_rect, triangles));
+                        continue;
+                    }
+                    let mut clipped = Triangles::with_texture(triangles.texture_id);
+                    clipping::clip_to_rounded_rect(
+                        &triangles,
+                        rect,
+                        rounding,
+                        options.feathering(),
+                        &mut clipped,
+                    );
+                    jobs.push((clip_rect, clipped));
+                }
+                continue;
+            }
+            cmd => cmd,
+        };
 
         if jobs.is_empty()
             || jobs.last().unwrap().0 != clip_rect
@@ -849,13 +1974,30 @@ pub fn tessellate_paint_commands(
         );
     }
 
+    jobs
+}
+
+fn clip_jobs_on_cpu(jobs: &mut PaintJobs, options: PaintOptions) {
+    if options.cpu_clipping && !options.debug_ignore_clip_rects {
+        for (clip_rect, triangles) in jobs {
+            let mut clipped = Triangles::with_texture(triangles.texture_id);
+            clipping::clip_to_rect(triangles, *clip_rect, &mut clipped);
+            *triangles = clipped;
+        }
+    }
+}
+
+/// Debug painting of clip rectangles, and removing the clip rectangles if they are not to be used.
+fn finish_jobs(jobs: &mut PaintJobs, options: PaintOptions, fonts: &Fonts) {
     if options.debug_paint_clip_rects {
-        for (clip_rect, triangles) in &mut jobs {
+        let mut scratchpad_points = Vec::new();
+        let mut scratchpad_path = Path::default();
+        for (clip_rect, triangles) in jobs.iter_mut() {
             tessellate_paint_command(
                 Rect::everything(),
                 PaintCmd::Rect {
                     rect: *clip_rect,
-                    corner_radius: 0.0,
+                    rounding: Rounding::none(),
                     fill: Default::default(),
                     stroke: Stroke::new(2.0, srgba(150, 255, 150, 255)),
                 },
@@ -868,11 +2010,499 @@ pub fn tessellate_paint_commands(
         }
     }
 
-    if options.debug_ignore_clip_rects {
-        for (clip_rect, _) in &mut jobs {
+    if options.cpu_clipping || options.debug_ignore_clip_rects {
+        for (clip_rect, _) in jobs {
             *clip_rect = Rect::everything();
         }
     }
+}
 
-    jobs
+/// Merge consecutive paint jobs that have the same clip rectangle and texture, to reduce the number of draw calls.
+/// Empty jobs are removed.
+///
+/// The merged jobs paint exactly the same vertices, in the same order.
+/// Returns how many jobs were merged into the job before them.
+pub fn merge_adjacent_jobs(jobs: &mut PaintJobs) -> usize {
+    let mut num_merged = 0;
+    let mut merged = PaintJobs::with_capacity(jobs.len());
+    for (clip_rect, triangles) in jobs.drain(..) {
+        if triangles.is_empty() {
+            continue;
+        }
+        match merged.last_mut() {
+            Some((last_clip_rect, last))
+                if *last_clip_rect == clip_rect && last.texture_id == triangles.texture_id =>
+            {
+                last.append(&triangles);
+                num_merged += 1;
+            }
+            _ => merged.push((clip_rect, triangles)),
+        }
+    }
+    *jobs = merged;
+    num_merged
+}
+
+#[test]
+fn test_merge_adjacent_jobs() {
+    fn quad(pos: Pos2, texture_id: TextureId) -> Triangles {
+        let mut triangles = Triangles::with_texture(texture_id);
+        let rect = Rect::from_min_size(pos, vec2(10.0, 10.0));
+        triangles.add_rect_with_uv(
+            rect,
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            color::WHITE,
+        );
+        triangles
+    }
+
+    /// The corners of all triangles, in paint order.
+    fn painted(jobs: &PaintJobs) -> Vec<(Rect, TextureId, Pos2, Pos2, Srgba)> {
+        let mut painted = vec![];
+        for (clip_rect, triangles) in jobs {
+            assert!(triangles.is_valid());
+            for &index in &triangles.indices {
+                let vertex = triangles.vertices[index as usize];
+                painted.push((
+                    *clip_rect,
+                    triangles.texture_id,
+                    vertex.pos,
+                    vertex.uv,
+                    vertex.color,
+                ));
+            }
+        }
+        painted
+    }
+
+    let clip_a = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+    let clip_b = Rect::from_min_max(pos2(0.0, 0.0), pos2(50.0, 50.0));
+    let user = TextureId::User(42);
+
+    let mut jobs = vec![
+        (clip_a, quad(pos2(0.0, 0.0), TextureId::Egui)),
+        (clip_a, quad(pos2(10.0, 0.0), TextureId::Egui)),
+        (clip_a, Triangles::default()),
+        (clip_a, quad(pos2(20.0, 0.0), TextureId::Egui)),
+        (clip_a, quad(pos2(30.0, 0.0), user)),
+        (clip_a, quad(pos2(40.0, 0.0), user)),
+        (clip_b, quad(pos2(50.0, 0.0), user)),
+        (clip_b, quad(pos2(60.0, 0.0), TextureId::Egui)),
+    ];
+    let before = painted(&jobs);
+
+    assert_eq!(merge_adjacent_jobs(&mut jobs), 3);
+    assert_eq!(jobs.len(), 4);
+    assert_eq!(painted(&jobs), before);
+
+    // Nothing more to merge:
+    assert_eq!(merge_adjacent_jobs(&mut jobs), 0);
+    assert_eq!(painted(&jobs), before);
+}
+
+#[test]
+fn test_merge_tessellated_jobs() {
+    use super::FontDefinitions;
+    let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+    let clip_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+    let user = TextureId::User(7);
+    let uv = Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0));
+
+    let mut commands = vec![];
+    for i in 0..3 {
+        let mut triangles = Triangles::with_texture(user);
+        let rect = Rect::from_min_size(pos2(10.0 * i as f32, 0.0), vec2(10.0, 10.0));
+        triangles.add_rect_with_uv(rect, uv, color::WHITE);
+        commands.push((clip_rect, PaintCmd::Triangles(triangles)));
+    }
+    commands.push((
+        clip_rect,
+        PaintCmd::circle_filled(pos2(50.0, 50.0), 10.0, color::WHITE),
+    ));
+
+    let mut jobs = tessellate_paint_commands(commands, Default::default(), &fonts);
+    assert_eq!(jobs.len(), 4);
+    let vertices_before: Vec<Pos2> = jobs
+        .iter()
+        .flat_map(|(_, t)| t.indices.iter().map(move |&i| t.vertices[i as usize].pos))
+        .collect();
+
+    assert_eq!(merge_adjacent_jobs(&mut jobs), 2);
+    assert_eq!(jobs.len(), 2);
+    let vertices_after: Vec<Pos2> = jobs
+        .iter()
+        .flat_map(|(_, t)| t.indices.iter().map(move |&i| t.vertices[i as usize].pos))
+        .collect();
+    assert_eq!(vertices_before, vertices_after);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_tessellate_paint_commands_par() {
+    use super::FontDefinitions;
+    let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+    let clip_rects = [
+        Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0)),
+        Rect::from_min_max(pos2(50.0, 0.0), pos2(200.0, 100.0)),
+    ];
+
+    let mut commands = vec![];
+    for i in 0..1000 {
+        let clip_rect = clip_rects[(i / 300) % 2];
+        let center = pos2((i % 200) as f32, (i % 100) as f32);
+        commands.push((
+            clip_rect,
+            PaintCmd::circle_filled(center, 5.0, color::WHITE),
+        ));
+    }
+
+    let vertices = |jobs: &PaintJobs| -> Vec<(Rect, Vec<(Pos2, Pos2, Srgba)>)> {
+        jobs.iter()
+            .map(|(clip_rect, t)| {
+                let vertices = t.indices.iter().map(|&i| {
+                    let v = t.vertices[i as usize];
+                    (v.pos, v.uv, v.color)
+                });
+                (*clip_rect, vertices.collect())
+            })
+            .collect()
+    };
+
+    let mut serial = tessellate_paint_commands(commands.clone(), Default::default(), &fonts);
+    let mut parallel = tessellate_paint_commands_par(commands, Default::default(), &fonts);
+    merge_adjacent_jobs(&mut serial);
+    merge_adjacent_jobs(&mut parallel);
+    assert_eq!(vertices(&serial), vertices(&parallel));
+}
+
+#[cfg(test)]
+fn max_distance_to_path(curve: impl Fn(f32) -> Pos2, path: &[Pos2]) -> f32 {
+    let distance_to_segment = |p: Pos2, a: Pos2, b: Pos2| {
+        let ab = b - a;
+        let t = if ab.length_sq() > 0.0 {
+            clamp(
+                ((p - a).x * ab.x + (p - a).y * ab.y) / ab.length_sq(),
+                0.0..=1.0,
+            )
+        } else {
+            0.0
+        };
+        (a + t * ab - p).length()
+    };
+    (0..=1000)
+        .map(|i| {
+            let p = curve(i as f32 / 1000.0);
+            path.windows(2)
+                .map(|w| distance_to_segment(p, w[0], w[1]))
+                .fold(f32::INFINITY, f32::min)
+        })
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn test_bezier_flattening_tolerance() {
+    for &tolerance in &[0.1, 0.5, 2.0] {
+        let [p0, p1, p2] = [pos2(0.0, 0.0), pos2(150.0, 300.0), pos2(300.0, 0.0)];
+        let mut points = vec![];
+        path::add_quadratic_bezier(&mut points, [p0, p1, p2], tolerance);
+        let quadratic = |t: f32| {
+            let mt = 1.0 - t;
+            p0 + 2.0 * mt * t * (p1 - p0) + t * t * (p2 - p0)
+        };
+        let error = max_distance_to_path(quadratic, &points);
+        assert!(error <= tolerance * 1.01, "{} > {}", error, tolerance);
+
+        let [p0, p1, p2, p3] = [
+            pos2(0.0, 0.0),
+            pos2(400.0, 300.0),
+            pos2(-100.0, 300.0),
+            pos2(300.0, 0.0),
+        ];
+        let mut points = vec![];
+        path::add_cubic_bezier(&mut points, [p0, p1, p2, p3], tolerance);
+        let cubic = |t: f32| {
+            let mt = 1.0 - t;
+            p0 + 3.0 * mt * mt * t * (p1 - p0)
+                + 3.0 * mt * t * t * (p2 - p0)
+                + t * t * t * (p3 - p0)
+        };
+        let error = max_distance_to_path(cubic, &points);
+        assert!(error <= tolerance * 1.01, "{} > {}", error, tolerance);
+    }
+}
+
+#[test]
+fn test_arc_flattening_tolerance() {
+    let center = pos2(10.0, 20.0);
+    let radius = 200.0;
+    for &tolerance in &[0.1, 0.5, 2.0] {
+        for &sweep in &[0.1, 1.0, -3.0, TAU, 2.5 * TAU, -4.0 * TAU] {
+            let mut points = vec![];
+            path::add_arc(&mut points, center, radius, 1.0, 1.0 + sweep, tolerance);
+            let swept = clamp(sweep, -TAU..=TAU);
+            let arc = |t: f32| center + radius * Vec2::angled(1.0 + t * swept);
+            let error = max_distance_to_path(arc, &points);
+            assert!(
+                error <= tolerance * 1.01,
+                "sweep {}: {} > {}",
+                sweep,
+                error,
+                tolerance
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+fn dashes(points: &[Pos2], dash: Dash) -> Vec<Vec<Pos2>> {
+    let mut dashes = vec![];
+    for_each_dash(points, dash, |points, _dir| dashes.push(points.to_vec()));
+    dashes
+}
+
+#[test]
+fn test_dash_offset() {
+    let line = [pos2(0.0, 0.0), pos2(12.0, 0.0)];
+    let dash = |offset| Dash {
+        length: 2.0,
+        gap: 3.0,
+        offset,
+    };
+    let starts_and_ends = |dash| -> Vec<(f32, f32)> {
+        dashes(&line, dash)
+            .iter()
+            .map(|d| (d[0].x, d[d.len() - 1].x))
+            .collect()
+    };
+    assert_eq!(
+        starts_and_ends(dash(0.0)),
+        vec![(0.0, 2.0), (5.0, 7.0), (10.0, 12.0)]
+    );
+    // Starting one unit into the pattern, the first dash is shorter:
+    assert_eq!(
+        starts_and_ends(dash(1.0)),
+        vec![(0.0, 1.0), (4.0, 6.0), (9.0, 11.0)]
+    );
+    // Starting in the gap, and the same offset one period later:
+    assert_eq!(starts_and_ends(dash(3.0)), vec![(2.0, 4.0), (7.0, 9.0)]);
+    assert_eq!(starts_and_ends(dash(8.0)), starts_and_ends(dash(3.0)));
+    assert_eq!(starts_and_ends(dash(-2.0)), starts_and_ends(dash(3.0)));
+}
+
+#[test]
+fn test_dash_around_corner() {
+    let corner = [pos2(0.0, 0.0), pos2(4.0, 0.0), pos2(4.0, 4.0)];
+    let dash = Dash {
+        length: 3.0,
+        gap: 3.0,
+        offset: 2.0,
+    };
+    assert_eq!(
+        dashes(&corner, dash),
+        vec![
+            vec![pos2(0.0, 0.0), pos2(1.0, 0.0)],
+            vec![pos2(4.0, 0.0), pos2(4.0, 3.0)],
+        ]
+    );
+}
+
+#[test]
+fn test_dots() {
+    let line = [pos2(0.0, 0.0), pos2(10.0, 0.0)];
+    let dots = Dash {
+        length: 0.0,
+        gap: 5.0,
+        offset: 0.0,
+    };
+    assert_eq!(
+        dashes(&line, dots),
+        vec![
+            vec![pos2(0.0, 0.0), pos2(0.0, 0.0)],
+            vec![pos2(5.0, 0.0), pos2(5.0, 0.0)],
+            vec![pos2(10.0, 0.0), pos2(10.0, 0.0)],
+        ]
+    );
+
+    // Only the round caps of the dots are painted:
+    let stroke = Stroke::new(2.0, color::WHITE)
+        .dotted(5.0)
+        .cap(LineCap::Round);
+    let triangles = stroke_points(&line, stroke, false);
+    assert!(triangles.is_valid());
+    for v in &triangles.vertices {
+        let nearest_dot = pos2((v.pos.x / 5.0).round() * 5.0, 0.0);
+        assert!(v.pos.distance(nearest_dot) <= 1.0 + 1e-4);
+    }
+}
+
+#[test]
+fn test_tiny_dashes_are_bounded() {
+    // These would take forever (or never finish) if every dash was painted:
+    let line = [pos2(0.0, 0.0), pos2(1e6, 0.0)];
+    for &(length, gap) in &[(1e-9, 0.0), (1e-9, 1e-9), (0.0, 1e-3), (0.5, 0.5)] {
+        let stroke = Stroke::new(1.0, color::WHITE).dashed(length, gap);
+        let triangles = stroke_points(&line, stroke, true);
+        assert!(triangles.is_valid());
+        assert!(triangles.indices.len() < 1_000_000);
+    }
+
+    let huge = [
+        pos2(0.0, 0.0),
+        pos2(f32::MAX, 0.0),
+        pos2(f32::MAX, f32::MAX),
+    ];
+    let stroke = Stroke::new(1.0, color::WHITE).dashed(2.0, 2.0);
+    assert!(stroke_points(&huge, stroke, true).is_valid());
+}
+
+#[test]
+fn test_line_caps() {
+    let line = [pos2(10.0, 0.0), pos2(20.0, 0.0)];
+    let bounds = |cap| {
+        let stroke = Stroke::new(4.0, color::WHITE).cap(cap);
+        let triangles = stroke_points(&line, stroke, false);
+        let mut bounds = Rect::nothing();
+        for v in &triangles.vertices {
+            bounds.extend_with(v.pos);
+        }
+        bounds
+    };
+    let butt = bounds(LineCap::Butt);
+    assert_eq!((butt.left(), butt.right()), (10.0, 20.0));
+    let square = bounds(LineCap::Square);
+    assert_eq!((square.left(), square.right()), (8.0, 22.0));
+    let round = bounds(LineCap::Round);
+    let tolerance = PaintOptions::default().tolerance;
+    assert!(8.0 - 1e-4 <= round.left() && round.left() <= 8.0 + tolerance);
+    assert!(22.0 - tolerance <= round.right() && round.right() <= 22.0 + 1e-4);
+    for bounds in &[butt, square, round] {
+        assert_eq!((bounds.top(), bounds.bottom()), (-2.0, 2.0));
+    }
+}
+
+#[test]
+fn test_line_joins_paint_everything_once() {
+    // A translucent stroke must not paint any point twice, or it would be darker there:
+    let corner = [pos2(0.0, 0.0), pos2(40.0, 0.0), pos2(40.0, 40.0)];
+    for &join in &[
+        LineJoin::Round,
+        LineJoin::Bevel,
+        LineJoin::Miter { limit: 1.0 },
+        LineJoin::Miter { limit: 4.0 },
+    ] {
+        let stroke = Stroke::new(10.0, color::WHITE).join(join);
+        let triangles = stroke_points(&corner, stroke, false);
+        // Just inside the outer and inner corners:
+        assert_eq!(coverage(&triangles, pos2(41.3, -2.1)), 1, "{:?}", join);
+        assert_eq!(coverage(&triangles, pos2(34.5, 4.5)), 1, "{:?}", join);
+
+        if join == LineJoin::Round {
+            for x in 0..50 {
+                for y in -10..50 {
+                    let p = pos2(x as f32 + 0.37, y as f32 + 0.61);
+                    assert!(coverage(&triangles, p) <= 1, "{:?} at {:?}", join, p);
+                }
+            }
+            // The outer corner is round:
+            assert_eq!(coverage(&triangles, pos2(44.5, -4.5)), 0);
+            assert_eq!(coverage(&triangles, pos2(43.0, -3.0)), 1);
+        }
+    }
+}
+
+#[cfg(test)]
+fn stroke_points(points: &[Pos2], stroke: Stroke, anti_alias: bool) -> Triangles {
+    let options = PaintOptions {
+        anti_alias,
+        ..Default::default()
+    };
+    let mut path = Path::default();
+    path.add_open_points(points);
+    let mut triangles = Triangles::default();
+    stroke_path(&path.0, Open, stroke, options, &mut triangles);
+    triangles
+}
+
+/// How many of the triangles cover `p`.
+#[cfg(test)]
+fn coverage(triangles: &Triangles, p: Pos2) -> usize {
+    let cross = |a: Pos2, b: Pos2| (b - a).x * (p - a).y - (b - a).y * (p - a).x;
+    triangles
+        .indices
+        .chunks(3)
+        .filter(|t| {
+            let [a, b, c] = [0, 1, 2].map(|i| triangles.vertices[t[i] as usize].pos);
+            let (ab, bc, ca) = (cross(a, b), cross(b, c), cross(c, a));
+            (ab > 0.0 && bc > 0.0 && ca > 0.0) || (ab < 0.0 && bc < 0.0 && ca < 0.0)
+        })
+        .count()
+}
+
+#[test]
+fn test_linear_gradient_slices() {
+    let (red, green, blue) = (
+        srgba(255, 0, 0, 255),
+        srgba(0, 255, 0, 255),
+        srgba(0, 0, 255, 255),
+    );
+    // The gradient is relative to the rectangle, like for a `Frame`:
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 20.0));
+    let fill = match Fill::horizontal_gradient(red, blue) {
+        Fill::LinearGradient(gradient) => Fill::from(gradient.stop(0.3, green)),
+        _ => unreachable!(),
+    };
+    let fill = fill.fit_to_rect(rect);
+    let gradient = match &fill {
+        Fill::LinearGradient(gradient) => gradient.clone(),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        (gradient.start, gradient.end),
+        (pos2(0.0, 10.0), pos2(100.0, 10.0))
+    );
+
+    let polygon = [
+        rect.left_top(),
+        rect.right_top(),
+        rect.right_bottom(),
+        rect.left_bottom(),
+    ];
+    let mut out = Triangles::default();
+    fill_convex_linear_gradient(&polygon, &gradient, &mut out);
+    assert!(out.is_valid());
+
+    // Sliced at the middle stop, so that each triangle is within a linear part of the gradient:
+    assert!(out.vertices.iter().any(|v| v.pos.x == 30.0));
+    for v in &out.vertices {
+        assert_eq!(v.color, fill.color_at(v.pos));
+    }
+
+    // What the GPU would paint inside each triangle is (close to) the gradient:
+    let to_array = |c: Rgba| [c.r(), c.g(), c.b(), c.a()];
+    for x in 0..100 {
+        let p = pos2(x as f32 + 0.5, 7.3);
+        let mut painted = None;
+        for t in out.indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| out.vertices[t[i] as usize]);
+            let area = |p0: Pos2, p1: Pos2, p2: Pos2| {
+                (p1 - p0).x * (p2 - p0).y - (p1 - p0).y * (p2 - p0).x
+            };
+            let total = area(a.pos, b.pos, c.pos);
+            let (wa, wb, wc) = (
+                area(p, b.pos, c.pos) / total,
+                area(a.pos, p, c.pos) / total,
+                area(a.pos, b.pos, p) / total,
+            );
+            if wa >= 0.0 && wb >= 0.0 && wc >= 0.0 {
+                let color =
+                    Rgba::from(a.color) * wa + Rgba::from(b.color) * wb + Rgba::from(c.color) * wc;
+                painted = Some(to_array(color));
+            }
+        }
+        let painted = painted.expect("every point is covered");
+        let expected = to_array(gradient.color_at(p));
+        for i in 0..4 {
+            assert!((painted[i] - expected[i]).abs() < 0.01, "at {:?}", p);
+        }
+    }
 }
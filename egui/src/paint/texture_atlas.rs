@@ -1,5 +1,6 @@
 /// An 8-bit texture containing font data.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Texture {
     /// e.g. a hash of the data. Use this to detect changes!
     /// If the texture changes, this too will change.
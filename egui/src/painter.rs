@@ -4,8 +4,8 @@ use crate::{
     align::{anchor_rect, Align, LEFT_TOP},
     color,
     layers::PaintCmdIdx,
-    math::{Pos2, Rect, Vec2},
-    paint::{font, Fonts, PaintCmd, Stroke, TextStyle},
+    math::{Pos2, Rect, Transform, Vec2},
+    paint::{font, Fill, Fonts, PaintCmd, Rounding, Stroke, TextStyle, TextureId, Triangles},
     Context, Layer, Srgba,
 };
 
@@ -111,6 +111,14 @@ impl Painter {
             .list(self.layer)
             .set(idx, self.clip_rect, cmd)
     }
+
+    /// Clip everything painted to this layer after `idx` to a rounded rectangle.
+    pub fn clip_after(&self, idx: PaintCmdIdx, rect: Rect, rounding: impl Into<Rounding>) {
+        self.ctx
+            .graphics()
+            .list(self.layer)
+            .clip_after(idx, rect, rounding.into())
+    }
 }
 
 /// ## Debug painting
@@ -129,8 +137,8 @@ impl Painter {
         let rect = anchor_rect(Rect::from_min_size(pos, galley.size), LEFT_TOP);
         self.add(PaintCmd::Rect {
             rect: rect.expand(2.0),
-            corner_radius: 0.0,
-            fill: Srgba::black_alpha(240),
+            rounding: Rounding::none(),
+            fill: Srgba::black_alpha(240).into(),
             stroke: Stroke::new(1.0, color::RED),
         });
         self.galley(rect.min, galley, text_style, color::RED);
@@ -174,22 +182,22 @@ impl Painter {
         &self,
         center: Pos2,
         radius: f32,
-        fill_color: impl Into<Srgba>,
+        fill: impl Into<Fill>,
         stroke: impl Into<Stroke>,
     ) {
         self.add(PaintCmd::Circle {
             center,
             radius,
-            fill: fill_color.into(),
+            fill: fill.into(),
             stroke: stroke.into(),
         });
     }
 
-    pub fn circle_filled(&self, center: Pos2, radius: f32, fill_color: impl Into<Srgba>) {
+    pub fn circle_filled(&self, center: Pos2, radius: f32, fill: impl Into<Fill>) {
         self.add(PaintCmd::Circle {
             center,
             radius,
-            fill: fill_color.into(),
+            fill: fill.into(),
             stroke: Default::default(),
         });
     }
@@ -206,35 +214,87 @@ impl Painter {
     pub fn rect(
         &self,
         rect: Rect,
-        corner_radius: f32,
-        fill_color: impl Into<Srgba>,
+        rounding: impl Into<Rounding>,
+        fill: impl Into<Fill>,
         stroke: impl Into<Stroke>,
     ) {
         self.add(PaintCmd::Rect {
             rect,
-            corner_radius,
-            fill: fill_color.into(),
+            rounding: rounding.into(),
+            fill: fill.into(),
             stroke: stroke.into(),
         });
     }
 
-    pub fn rect_filled(&self, rect: Rect, corner_radius: f32, fill_color: impl Into<Srgba>) {
+    pub fn rect_filled(&self, rect: Rect, rounding: impl Into<Rounding>, fill: impl Into<Fill>) {
         self.add(PaintCmd::Rect {
             rect,
-            corner_radius,
-            fill: fill_color.into(),
+            rounding: rounding.into(),
+            fill: fill.into(),
             stroke: Default::default(),
         });
     }
 
-    pub fn rect_stroke(&self, rect: Rect, corner_radius: f32, stroke: impl Into<Stroke>) {
+    pub fn rect_stroke(
+        &self,
+        rect: Rect,
+        rounding: impl Into<Rounding>,
+        stroke: impl Into<Stroke>,
+    ) {
         self.add(PaintCmd::Rect {
             rect,
-            corner_radius,
+            rounding: rounding.into(),
             fill: Default::default(),
             stroke: stroke.into(),
         });
     }
+
+    pub fn quadratic_bezier(&self, points: [Pos2; 3], stroke: impl Into<Stroke>) {
+        self.add(PaintCmd::quadratic_bezier(points, stroke));
+    }
+
+    pub fn cubic_bezier(&self, points: [Pos2; 4], stroke: impl Into<Stroke>) {
+        self.add(PaintCmd::cubic_bezier(points, stroke));
+    }
+
+    /// Paint a circular arc going clockwise from `start_angle` to `end_angle` (radians).
+    pub fn arc(
+        &self,
+        center: Pos2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        stroke: impl Into<Stroke>,
+    ) {
+        self.add(PaintCmd::arc(
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            stroke,
+        ));
+    }
+
+    /// Paint a pie slice: an arc whose ends are connected to the center.
+    pub fn pie(
+        &self,
+        center: Pos2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        fill: impl Into<Fill>,
+        stroke: impl Into<Stroke>,
+    ) {
+        self.add(PaintCmd::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            closed: true,
+            fill: fill.into(),
+            stroke: stroke.into(),
+        });
+    }
 }
 
 /// ## Text
@@ -269,3 +329,62 @@ impl Painter {
         });
     }
 }
+
+/// ## Images and meshes
+impl Painter {
+    /// Paint the `uv` part of a texture in `rect`, multiplied by `tint`.
+    ///
+    /// `uv` is normalized: (0,0) is the top left corner of the texture, (1,1) the bottom right.
+    pub fn image(&self, texture_id: TextureId, rect: Rect, uv: Rect, tint: Srgba) {
+        let mut triangles = Triangles::with_texture(texture_id);
+        triangles.add_rect_with_uv(rect, uv, tint);
+        self.add(PaintCmd::Triangles(triangles));
+    }
+
+    /// Like `image`, but rotated by `angle` radians (clockwise) around the center of `rect`.
+    pub fn image_rotated(
+        &self,
+        texture_id: TextureId,
+        rect: Rect,
+        uv: Rect,
+        tint: Srgba,
+        angle: f32,
+    ) {
+        let mut triangles = Triangles::with_texture(texture_id);
+        triangles.add_rotated_rect_with_uv(rect, uv, tint, angle);
+        self.add(PaintCmd::Triangles(triangles));
+    }
+
+    /// Paint the `uv` part of a texture on a quadrilateral with arbitrary corners.
+    ///
+    /// The corners are in the order left top, right top, right bottom, left bottom (as seen in the texture).
+    pub fn image_quad(&self, texture_id: TextureId, corners: [Pos2; 4], uv: Rect, tint: Srgba) {
+        let mut triangles = Triangles::with_texture(texture_id);
+        triangles.add_quad_with_uv(corners, uv, tint);
+        self.add(PaintCmd::Triangles(triangles));
+    }
+
+    /// Paint some commands, but only the parts inside of the rounded rectangle.
+    ///
+    /// For instance, use `Rounding::same(0.5 * rect.width())` on a square `rect` for a circular avatar.
+    pub fn clipped(
+        &self,
+        rect: Rect,
+        rounding: impl Into<Rounding>,
+        commands: Vec<PaintCmd>,
+    ) -> PaintCmdIdx {
+        self.add(PaintCmd::Clipped {
+            rect,
+            rounding: rounding.into(),
+            commands,
+        })
+    }
+
+    /// Paint some commands moved, rotated, scaled and/or skewed by `transform`.
+    pub fn transformed(&self, transform: Transform, commands: Vec<PaintCmd>) -> PaintCmdIdx {
+        self.add(PaintCmd::Transformed {
+            transform,
+            commands,
+        })
+    }
+}
@@ -3,7 +3,7 @@
 use crate::{
     color::*,
     math::*,
-    paint::{Stroke, TextStyle},
+    paint::{Fill, LinearGradient, RadialGradient, Rounding, Shadow, Stroke, TextStyle},
     types::*,
 };
 
@@ -121,8 +121,19 @@ pub struct Visuals {
     /// needs to look different from other interactive stuff.
     pub dark_bg_color: Srgba, // TODO: remove, rename, or clarify what it is for
 
+    /// Background of every other row in a striped `Grid`.
+    pub faint_bg_color: Srgba,
+
+    /// Background of selected rows in a `Table`.
+    pub selection_bg_color: Srgba,
+
     pub window_corner_radius: f32,
 
+    pub window_shadow: Shadow,
+
+    /// Shadow of menus, combo boxes and other popups.
+    pub popup_shadow: Shadow,
+
     pub resize_corner_size: f32,
 
     /// Blink text cursor by this frequency. If 0, always show the cursor.
@@ -179,18 +190,18 @@ impl Widgets {
 }
 
 /// bg = background, fg = foreground.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct WidgetVisuals {
-    /// Background color of widget
-    pub bg_fill: Srgba,
+    /// Background of widget: a color, or a gradient relative to the widget (see [`Fill::fit_to_rect`]).
+    pub bg_fill: Fill,
 
     /// For surrounding rectangle of things that need it,
     /// like buttons, the box of the checkbox, etc.
     pub bg_stroke: Stroke,
 
     /// Button frames etc
-    pub corner_radius: f32,
+    pub rounding: Rounding,
 
     /// Fill color of the interactive part of a component (slider grab, checkbox, ...)
     /// When you need a fill.
@@ -204,6 +215,11 @@ impl WidgetVisuals {
     pub fn text_color(&self) -> Srgba {
         self.fg_stroke.color
     }
+
+    /// The background fill of a widget covering `rect`.
+    pub fn bg_fill_in(&self, rect: Rect) -> Fill {
+        self.bg_fill.fit_to_rect(rect)
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -250,7 +266,11 @@ impl Default for Visuals {
             override_text_color: None,
             widgets: Default::default(),
             dark_bg_color: Srgba::black_alpha(140),
+            faint_bg_color: Srgba::additive_luminance(12),
+            selection_bg_color: srgba(0, 92, 128, 255),
             window_corner_radius: 10.0,
+            window_shadow: Shadow::big(),
+            popup_shadow: Shadow::small(),
             resize_corner_size: 12.0,
             cursor_blink_hz: 0.0, // 1.0 looks good
             text_cursor_width: 2.0,
@@ -265,37 +285,37 @@ impl Default for Widgets {
     fn default() -> Self {
         Self {
             active: WidgetVisuals {
-                bg_fill: Srgba::black_alpha(128),
+                bg_fill: Srgba::black_alpha(128).into(),
                 bg_stroke: Stroke::new(2.0, WHITE),
-                corner_radius: 4.0,
+                rounding: Rounding::same(4.0),
                 fg_fill: srgba(120, 120, 200, 255),
                 fg_stroke: Stroke::new(2.0, WHITE),
             },
             hovered: WidgetVisuals {
                 bg_fill: Rgba::luminance_alpha(0.06, 0.5).into(),
                 bg_stroke: Stroke::new(1.0, Rgba::white_alpha(0.5)),
-                corner_radius: 4.0,
+                rounding: Rounding::same(4.0),
                 fg_fill: srgba(100, 100, 150, 255),
                 fg_stroke: Stroke::new(1.5, Srgba::gray(240)),
             },
             inactive: WidgetVisuals {
                 bg_fill: Rgba::luminance_alpha(0.04, 0.5).into(),
                 bg_stroke: Stroke::new(1.0, Rgba::white_alpha(0.06)), // default window outline. Should be pretty readable
-                corner_radius: 4.0,
+                rounding: Rounding::same(4.0),
                 fg_fill: srgba(60, 60, 80, 255),
                 fg_stroke: Stroke::new(1.0, Srgba::gray(200)), // Should NOT look grayed out!
             },
             disabled: WidgetVisuals {
-                bg_fill: TRANSPARENT,
+                bg_fill: TRANSPARENT.into(),
                 bg_stroke: Stroke::new(0.5, Srgba::gray(70)),
-                corner_radius: 4.0,
+                rounding: Rounding::same(4.0),
                 fg_fill: srgba(50, 50, 50, 255),
                 fg_stroke: Stroke::new(1.0, Srgba::gray(128)), // Should look grayed out
             },
             noninteractive: WidgetVisuals {
                 bg_stroke: Stroke::new(1.0, Rgba::white_alpha(0.06)),
                 bg_fill: Rgba::luminance_alpha(0.010, 0.975).into(), // window background
-                corner_radius: 4.0,
+                rounding: Rounding::same(4.0),
                 fg_fill: Default::default(),
                 fg_stroke: Stroke::new(1.0, Srgba::gray(160)), // text color
             },
@@ -407,14 +427,14 @@ impl WidgetVisuals {
         let Self {
             bg_fill,
             bg_stroke,
-            corner_radius,
+            rounding,
             fg_fill,
             fg_stroke,
         } = self;
 
-        ui_color(ui, bg_fill, "bg_fill");
+        bg_fill.ui(ui, "bg_fill");
         bg_stroke.ui(ui, "bg_stroke");
-        ui.add(Slider::f32(corner_radius, 0.0..=10.0).text("corner_radius"));
+        rounding.ui(ui, "rounding");
         ui_color(ui, fg_fill, "fg_fill");
         fg_stroke.ui(ui, "fg_stroke (text)");
     }
@@ -430,7 +450,11 @@ impl Visuals {
             override_text_color: _,
             widgets,
             dark_bg_color,
+            faint_bg_color,
+            selection_bg_color,
             window_corner_radius,
+            window_shadow,
+            popup_shadow,
             resize_corner_size,
             cursor_blink_hz,
             text_cursor_width,
@@ -441,7 +465,11 @@ impl Visuals {
 
         ui.collapsing("widgets", |ui| widgets.ui(ui));
         ui_color(ui, dark_bg_color, "dark_bg_color");
+        ui_color(ui, faint_bg_color, "faint_bg_color");
+        ui_color(ui, selection_bg_color, "selection_bg_color");
         ui.add(Slider::f32(window_corner_radius, 0.0..=20.0).text("window_corner_radius"));
+        ui.collapsing("window_shadow", |ui| window_shadow.ui(ui));
+        ui.collapsing("popup_shadow", |ui| popup_shadow.ui(ui));
         ui.add(Slider::f32(resize_corner_size, 0.0..=20.0).text("resize_corner_size"));
         ui.add(Slider::f32(cursor_blink_hz, 0.0..=4.0).text("cursor_blink_hz"));
         ui.add(Slider::f32(text_cursor_width, 0.0..=2.0).text("text_cursor_width"));
@@ -454,7 +482,13 @@ impl Visuals {
 
 impl Stroke {
     pub fn ui(&mut self, ui: &mut crate::Ui, text: &str) {
-        let Self { width, color } = self;
+        let Self {
+            width,
+            color,
+            dash: _,
+            cap: _,
+            join: _,
+        } = self;
         ui.horizontal(|ui| {
             ui.add(DragValue::f32(width).speed(0.1).range(0.0..=5.0))
                 .on_hover_text("Width");
@@ -470,6 +504,65 @@ impl Stroke {
     }
 }
 
+impl Fill {
+    /// Edit a solid color, or the colors of a gradient.
+    pub fn ui(&mut self, ui: &mut crate::Ui, text: &str) {
+        ui.horizontal(|ui| {
+            let mut gradient = !matches!(self, Fill::Solid(_));
+            if ui
+                .checkbox(&mut gradient, "")
+                .on_hover_text("Gradient")
+                .clicked
+            {
+                *self = match self {
+                    Fill::Solid(color) => Fill::vertical_gradient(*color, *color),
+                    _ => Fill::Solid(self.color_at(pos2(0.0, 0.0))),
+                };
+            }
+            match self {
+                Fill::Solid(color) => {
+                    ui.color_edit_button_srgba(color);
+                }
+                Fill::LinearGradient(LinearGradient { stops, .. })
+                | Fill::RadialGradient(RadialGradient { stops, .. }) => {
+                    for stop in stops {
+                        ui.color_edit_button_srgba(&mut stop.color);
+                    }
+                }
+            }
+            ui.label(text);
+        });
+    }
+}
+
+impl Shadow {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            offset,
+            blur,
+            spread,
+            color,
+        } = self;
+        ui_slider_vec2(ui, offset, -20.0..=20.0, "offset");
+        ui.add(Slider::f32(blur, 0.0..=50.0).text("blur"));
+        ui.add(Slider::f32(spread, -10.0..=20.0).text("spread"));
+        ui_color(ui, color, "color");
+    }
+}
+
+impl Rounding {
+    pub fn ui(&mut self, ui: &mut crate::Ui, text: &str) {
+        let Self { nw, ne, sw, se } = self;
+        ui.horizontal(|ui| {
+            for (radius, corner) in [(nw, "nw"), (ne, "ne"), (sw, "sw"), (se, "se")].iter_mut() {
+                ui.add(DragValue::f32(radius).speed(0.1).range(0.0..=20.0))
+                    .on_hover_text(*corner);
+            }
+            ui.label(text);
+        });
+    }
+}
+
 // TODO: improve and standardize ui_slider_vec2
 fn ui_slider_vec2(
     ui: &mut Ui,
@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
-use crate::{math::Rect, Context, Ui};
+use crate::{math::Rect, Align, Context, Ui};
 
 // ----------------------------------------------------------------------------
 
 /// What Egui emits each frame.
 /// The backend should use this.
 #[derive(Clone, Default)]
-// #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Output {
     /// Set the cursor to this icon.
     pub cursor_icon: CursorIcon,
@@ -25,8 +25,8 @@ pub struct Output {
 }
 
 #[derive(Clone, Copy)]
-// #[cfg_attr(feature = "serde", derive(serde::Serialize))]
-// #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum CursorIcon {
     Default,
     /// Pointing hand, used for e.g. web links
@@ -110,6 +110,13 @@ impl Response {
         })
     }
 
+    /// Scroll the surrounding `ScrollArea` so that this item is visible,
+    /// at the top (`Align::Min`), center or bottom (`Align::Max`).
+    /// See [`Ui::scroll_to_rect`].
+    pub fn scroll_to_me(&self, align: Align) {
+        self.ctx.memory().scroll_target = Some((self.rect, align));
+    }
+
     #[deprecated = "Deprecated 2020-10-01: use `on_hover_text` instead."]
     pub fn tooltip_text(self, text: impl Into<String>) -> Self {
         self.on_hover_text(text)
@@ -342,6 +342,33 @@ impl Ui {
     pub fn available_finite(&self) -> Rect {
         self.layout.available(self.cursor, self.max_rect_finite())
     }
+
+    /// The space for a child `Ui` whose size is only known once its contents have been added.
+    /// Allocate it with [`Self::allocate_child`] afterwards.
+    ///
+    /// The contents can't be moved once they have been added, so a wrapping layout
+    /// first moves on to the next row if less than half a row is left, and the child gets the rest of the row.
+    pub(crate) fn available_for_child(&mut self) -> Rect {
+        if !self.layout.is_wrapping() {
+            return self.available();
+        }
+        self.cursor = self.painter().round_pos_to_pixels(self.cursor);
+        let max_rect = self.max_rect_finite();
+        let half_row = vec2(0.5 * max_rect.width(), 0.0);
+        let row_bottom = self.min_rect.bottom();
+        let item_spacing = self.style().spacing.item_spacing;
+        self.layout.wrap_cursor(
+            &mut self.cursor,
+            max_rect,
+            row_bottom,
+            half_row,
+            item_spacing,
+        );
+        let mut rect = self.available();
+        rect.min.x = rect.min.x.max(max_rect.left());
+        rect.max.x = rect.max.x.min(max_rect.right());
+        rect
+    }
 }
 
 /// # `Id` creation
@@ -394,6 +421,18 @@ impl Ui {
     }
 }
 
+/// # Scrolling
+impl Ui {
+    /// Scroll the `ScrollArea` this `Ui` is in so that `rect` is visible,
+    /// at the top (`Align::Min`), center or bottom (`Align::Max`).
+    /// The scrolling is animated.
+    ///
+    /// Must be called inside the `ScrollArea`, before it ends.
+    pub fn scroll_to_rect(&self, rect: Rect, align: Align) {
+        self.memory().scroll_target = Some((rect, align));
+    }
+}
+
 /// # Interaction
 impl Ui {
     pub fn interact(&self, rect: Rect, id: Id, sense: Sense) -> Response {
@@ -438,6 +477,16 @@ impl Ui {
     ///
     /// You may get LESS space than you asked for if the current layout won't fit what you asked for.
     pub fn allocate_space(&mut self, desired_size: Vec2) -> Rect {
+        self.allocate_space_impl(desired_size, true)
+    }
+
+    /// Allocate the space taken by a child `Ui` that was put at [`Self::available_for_child`].
+    /// Unlike [`Self::allocate_space`] this never wraps, since the contents are already in place.
+    pub(crate) fn allocate_child(&mut self, size: Vec2) -> Rect {
+        self.allocate_space_impl(size, false)
+    }
+
+    fn allocate_space_impl(&mut self, desired_size: Vec2, wrap: bool) -> Rect {
         let desired_size = self.painter().round_vec_to_pixels(desired_size);
         self.cursor = self.painter().round_pos_to_pixels(self.cursor);
 
@@ -445,7 +494,7 @@ impl Ui {
         let too_wide = desired_size.x > self.available().width();
         let too_high = desired_size.x > self.available().height();
 
-        let rect = self.reserve_space_impl(desired_size);
+        let rect = self.reserve_space_impl(desired_size, wrap);
 
         if self.style().visuals.debug_widget_rects {
             self.painter.rect_stroke(rect, 0.0, (1.0, LIGHT_BLUE));
@@ -473,12 +522,23 @@ impl Ui {
 
     /// Reserve this much space and move the cursor.
     /// Returns where to put the widget.
-    fn reserve_space_impl(&mut self, child_size: Vec2) -> Rect {
+    fn reserve_space_impl(&mut self, child_size: Vec2, wrap: bool) -> Rect {
+        let item_spacing = self.style().spacing.item_spacing;
+        if wrap {
+            let max_rect = self.max_rect_finite();
+            let row_bottom = self.min_rect.bottom(); // The bottom of the tallest widget on the current row
+            self.layout.wrap_cursor(
+                &mut self.cursor,
+                max_rect,
+                row_bottom,
+                child_size,
+                item_spacing,
+            );
+        }
         let available_size = self.available_finite().size();
         let child_rect = self
             .layout
             .allocate_space(&mut self.cursor, available_size, child_size);
-        let item_spacing = self.style().spacing.item_spacing;
         self.layout.advance_cursor2(&mut self.cursor, item_spacing);
         self.expand_to_include_rect(child_rect);
         self.child_count += 1;
@@ -649,20 +709,20 @@ impl Ui {
     /// After `add_contents` is called the contents of `min_size`
     /// will decide how much space will be used in the parent ui.
     pub fn add_custom_contents(&mut self, size: Vec2, add_contents: impl FnOnce(&mut Ui)) -> Rect {
-        let size = size.at_most(self.available().size());
+        let size = size.at_most(self.available_for_child().size());
         let child_rect = self.layout.rect_from_cursor_size(self.cursor, size);
         let mut child_ui = self.child_ui(child_rect, self.layout);
         add_contents(&mut child_ui);
-        self.allocate_space(child_ui.min_size())
+        self.allocate_child(child_ui.min_size())
     }
 
     /// Create a child ui. You can use this to temporarily change the Style of a sub-region, for instance.
     pub fn add_custom<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
-        let child_rect = self.available();
+        let child_rect = self.available_for_child();
         let mut child_ui = self.child_ui(child_rect, self.layout);
         let ret = add_contents(&mut child_ui);
         let size = child_ui.min_size();
-        let rect = self.allocate_space(size);
+        let rect = self.allocate_child(size);
         (ret, self.interact_hover(rect))
     }
 
@@ -739,8 +799,25 @@ impl Ui {
     /// but can be used for tooltips (`on_hover_text`).
     /// It also contains the `Rect` used by the horizontal layout.
     pub fn horizontal<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> (R, Response) {
+        self.horizontal_impl(false, add_contents)
+    }
+
+    /// Like `horizontal`, but starts a new row when the next widget doesn't fit,
+    /// e.g. for a list of tags or a tool bar that may be narrower than its buttons.
+    pub fn horizontal_wrapped<R>(
+        &mut self,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response) {
+        self.horizontal_impl(true, add_contents)
+    }
+
+    fn horizontal_impl<R>(
+        &mut self,
+        wrap: bool,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response) {
         let initial_size = vec2(
-            self.available().width(),
+            self.available_for_child().width(),
             self.style().spacing.interact_size.y, // Assume there will be something interactive on the horizontal layout
         );
 
@@ -748,7 +825,9 @@ impl Ui {
             (self.layout.dir(), self.layout.align()) == (Direction::Vertical, Some(Align::Max));
 
         self.inner_layout(
-            Layout::horizontal(Align::Center).with_reversed(right_to_left),
+            Layout::horizontal(Align::Center)
+                .with_reversed(right_to_left)
+                .with_wrap(wrap),
             initial_size,
             add_contents,
         )
@@ -766,11 +845,12 @@ impl Ui {
         initial_size: Vec2,
         add_contents: impl FnOnce(&mut Self) -> R,
     ) -> (R, Response) {
+        self.available_for_child(); // Wrap first, if need be
         let child_rect = self.layout.rect_from_cursor_size(self.cursor, initial_size);
         let mut child_ui = self.child_ui(child_rect, layout);
         let ret = add_contents(&mut child_ui);
         let size = child_ui.min_size();
-        let rect = self.allocate_space(size);
+        let rect = self.allocate_child(size);
         (ret, self.interact_hover(rect))
     }
 
@@ -779,10 +859,11 @@ impl Ui {
         layout: Layout,
         add_contents: impl FnOnce(&mut Self) -> R,
     ) -> (R, Response) {
-        let mut child_ui = self.child_ui(self.available(), layout);
+        let child_rect = self.available_for_child();
+        let mut child_ui = self.child_ui(child_rect, layout);
         let ret = add_contents(&mut child_ui);
         let size = child_ui.min_size();
-        let rect = self.allocate_space(size);
+        let rect = self.allocate_child(size);
         (ret, self.interact_hover(rect))
     }
 
@@ -801,7 +882,8 @@ impl Ui {
         // TODO: ensure there is space
         let spacing = self.style().spacing.item_spacing.x;
         let total_spacing = spacing * (num_columns as f32 - 1.0);
-        let column_width = (self.available().width() - total_spacing) / (num_columns as f32);
+        let available_width = self.available_for_child().width();
+        let column_width = (available_width - total_spacing) / (num_columns as f32);
 
         let mut columns: Vec<Self> = (0..num_columns)
             .map(|col_idx| {
@@ -831,8 +913,8 @@ impl Ui {
             max_height = size.y.max(max_height);
         }
 
-        let size = vec2(self.available().width().max(sum_width), max_height);
-        self.allocate_space(size);
+        let size = vec2(available_width.max(sum_width), max_height);
+        self.allocate_child(size);
         result
     }
 }
@@ -47,8 +47,8 @@ fn show_srgba(ui: &mut Ui, srgba: Srgba, desired_size: Vec2) -> Response {
     background_checkers(ui.painter(), rect);
     ui.painter().add(PaintCmd::Rect {
         rect,
-        corner_radius: 2.0,
-        fill: srgba,
+        rounding: Rounding::same(2.0),
+        fill: srgba.into(),
         stroke: Stroke::new(3.0, srgba.to_opaque()),
     });
     ui.interact_hover(rect)
@@ -63,8 +63,8 @@ fn color_button(ui: &mut Ui, color: Srgba) -> Response {
     background_checkers(ui.painter(), rect);
     ui.painter().add(PaintCmd::Rect {
         rect,
-        corner_radius: visuals.corner_radius.at_most(2.0),
-        fill: color,
+        rounding: visuals.rounding.at_most(2.0),
+        fill: color.into(),
         stroke: visuals.fg_stroke,
     });
     response
@@ -122,7 +122,7 @@ fn color_slider_1d(ui: &mut Ui, value: &mut f32, color_at: impl Fn(f32) -> Srgba
                 pos2(x, rect.center().y),
             ],
             closed: true,
-            fill: picked_color,
+            fill: picked_color.into(),
             stroke: Stroke::new(visuals.fg_stroke.width, contrast_color(picked_color)),
         });
     }
@@ -180,7 +180,7 @@ fn color_slider_2d(
     ui.painter().add(PaintCmd::Circle {
         center: pos2(x, y),
         radius: rect.width() / 12.0,
-        fill: picked_color,
+        fill: picked_color.into(),
         stroke: Stroke::new(visuals.fg_stroke.width, contrast_color(picked_color)),
     });
 
@@ -212,7 +212,8 @@ fn color_picker_hsvag_2d(ui: &mut Ui, hsva: &mut HsvaGamma) {
     });
 }
 
-fn color_picker_hsva_2d(ui: &mut Ui, hsva: &mut Hsva) {
+/// Shows a full color picker inline.
+pub fn color_picker_hsva_2d(ui: &mut Ui, hsva: &mut Hsva) {
     let mut hsvag = HsvaGamma::from(*hsva);
     color_picker_hsvag_2d(ui, &mut hsvag);
     *hsva = Hsva::from(hsvag);
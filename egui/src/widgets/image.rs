@@ -55,12 +55,7 @@ impl Widget for Image {
             triangles.add_colored_rect(rect, bg_fill);
             ui.painter().add(PaintCmd::Triangles(triangles));
         }
-        {
-            // TODO: builder pattern for Triangles
-            let mut triangles = Triangles::with_texture(texture_id);
-            triangles.add_rect_with_uv(rect, uv, tint);
-            ui.painter().add(PaintCmd::Triangles(triangles));
-        }
+        ui.painter().image(texture_id, rect, uv, tint);
 
         ui.interact_hover(rect)
     }
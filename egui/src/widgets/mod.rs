@@ -307,13 +307,12 @@ impl Widget for Button {
             response.rect.left() + button_padding.x,
             response.rect.center().y - 0.5 * galley.size.y,
         ); // left-centered
-        let fill = fill.unwrap_or(visuals.bg_fill);
-        ui.painter().rect(
-            response.rect,
-            visuals.corner_radius,
-            fill,
-            visuals.bg_stroke,
-        );
+        let fill = match fill {
+            Some(fill) => fill.into(),
+            None => visuals.bg_fill_in(response.rect),
+        };
+        ui.painter()
+            .rect(response.rect, visuals.rounding, fill, visuals.bg_stroke);
         let text_color = text_color
             .or(ui.style().visuals.override_text_color)
             .unwrap_or_else(|| visuals.text_color());
@@ -385,8 +384,8 @@ impl<'a> Widget for Checkbox<'a> {
         let (small_icon_rect, big_icon_rect) = ui.style().spacing.icon_rectangles(response.rect);
         ui.painter().add(PaintCmd::Rect {
             rect: big_icon_rect,
-            corner_radius: visuals.corner_radius,
-            fill: visuals.bg_fill,
+            rounding: visuals.rounding,
+            fill: visuals.bg_fill_in(big_icon_rect),
             stroke: visuals.bg_stroke,
         });
 
@@ -474,7 +473,7 @@ impl Widget for RadioButton {
         painter.add(PaintCmd::Circle {
             center: big_icon_rect.center(),
             radius: big_icon_rect.width() / 2.0,
-            fill: visuals.bg_fill,
+            fill: visuals.bg_fill_in(big_icon_rect),
             stroke: visuals.bg_stroke,
         });
 
@@ -482,7 +481,7 @@ impl Widget for RadioButton {
             painter.add(PaintCmd::Circle {
                 center: small_icon_rect.center(),
                 radius: small_icon_rect.width() / 3.0,
-                fill: visuals.fg_stroke.color, // Intentional to use stroke and not fill
+                fill: visuals.fg_stroke.color.into(), // Intentional to use stroke and not fill
                 stroke: Default::default(),
                 // fill: visuals.fg_fill,
                 // stroke: visuals.fg_stroke,
@@ -254,15 +254,15 @@ impl<'a> Slider<'a> {
 
             ui.painter().add(PaintCmd::Rect {
                 rect: rail_rect,
-                corner_radius: rail_radius,
-                fill: ui.style().visuals.widgets.inactive.bg_fill,
+                rounding: rail_radius.into(),
+                fill: ui.style().visuals.widgets.inactive.bg_fill_in(rail_rect),
                 stroke: ui.style().visuals.widgets.inactive.bg_stroke,
             });
 
             ui.painter().add(PaintCmd::Circle {
                 center: pos2(marker_center_x, rail_rect.center().y),
                 radius: handle_radius(rect),
-                fill: ui.style().interact(response).fg_fill,
+                fill: ui.style().interact(response).fg_fill.into(),
                 stroke: ui.style().interact(response).fg_stroke,
             });
         }
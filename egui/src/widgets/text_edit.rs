@@ -189,8 +189,8 @@ impl<'t> Widget for TextEdit<'t> {
             let bg_rect = response.rect.expand(2.0); // breathing room for content
             painter.add(PaintCmd::Rect {
                 rect: bg_rect,
-                corner_radius: visuals.corner_radius,
-                fill: ui.style().visuals.dark_bg_color,
+                rounding: visuals.rounding,
+                fill: ui.style().visuals.dark_bg_color.into(),
                 // fill: visuals.bg_fill,
                 stroke: visuals.bg_stroke,
             });
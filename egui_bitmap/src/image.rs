@@ -0,0 +1,138 @@
+use std::path::Path;
+
+use egui::{Rgba, Srgba};
+
+/// An RGBA image that we paint into.
+///
+/// The pixels are stored in linear space with premultiplied alpha,
+/// so that blending is correct and doesn't lose precision.
+#[derive(Clone, Debug)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    /// Row by row, starting at the top.
+    pub pixels: Vec<Rgba>,
+}
+
+impl Image {
+    /// A fully transparent image.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, Rgba::default())
+    }
+
+    pub fn filled(width: usize, height: usize, color: impl Into<Rgba>) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color.into(); width * height],
+        }
+    }
+
+    pub fn clear(&mut self, color: impl Into<Rgba>) {
+        let color = color.into();
+        for pixel in &mut self.pixels {
+            *pixel = color;
+        }
+    }
+
+    /// sRGBA with premultiplied alpha, row by row.
+    pub fn to_srgba(&self) -> Vec<Srgba> {
+        self.pixels
+            .iter()
+            .map(|&pixel| Srgba::from(pixel))
+            .collect()
+    }
+
+    /// sRGBA with unmultiplied alpha (as used by PNG), row by row, four bytes per pixel.
+    pub fn to_srgba_unmultiplied_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 * self.pixels.len());
+        for pixel in &self.pixels {
+            let a = pixel.a();
+            let rgba = if a > 0.0 {
+                Rgba::new(pixel.r() / a, pixel.g() / a, pixel.b() / a, a)
+            } else {
+                Rgba::default()
+            };
+            bytes.extend_from_slice(&Srgba::from(rgba).to_array());
+        }
+        bytes
+    }
+
+    /// Encode the image as a PNG file.
+    pub fn encode_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut png_bytes = vec![];
+        {
+            let mut encoder =
+                png::Encoder::new(&mut png_bytes, self.width as u32, self.height as u32);
+            encoder.set_color(png::ColorType::RGBA);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.to_srgba_unmultiplied_bytes())?;
+        }
+        Ok(png_bytes)
+    }
+
+    /// Decode an 8-bit RGBA or RGB PNG file.
+    pub fn decode_png(png_bytes: &[u8]) -> Result<Self, png::DecodingError> {
+        let decoder = png::Decoder::new(png_bytes);
+        let (info, mut reader) = decoder.read_info()?;
+        let mut bytes = vec![0; info.buffer_size()];
+        reader.next_frame(&mut bytes)?;
+
+        let channels = match (info.color_type, info.bit_depth) {
+            (png::ColorType::RGBA, png::BitDepth::Eight) => 4,
+            (png::ColorType::RGB, png::BitDepth::Eight) => 3,
+            _ => {
+                return Err(png::DecodingError::Other(
+                    "Expected an 8-bit RGBA or RGB PNG".into(),
+                ))
+            }
+        };
+
+        let pixels = bytes
+            .chunks_exact(channels)
+            .map(|p| {
+                let a = if channels == 4 { p[3] } else { 255 };
+                let unmultiplied = Rgba::from(Srgba::new(p[0], p[1], p[2], 255));
+                unmultiplied.multiply(a as f32 / 255.0)
+            })
+            .collect();
+
+        Ok(Self {
+            width: info.width as usize,
+            height: info.height as usize,
+            pixels,
+        })
+    }
+
+    pub fn load_png(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let png_bytes = std::fs::read(path)?;
+        Self::decode_png(&png_bytes)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let png_bytes = self
+            .encode_png()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        std::fs::write(path, png_bytes)
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Image {
+    type Output = Rgba;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Rgba {
+        assert!(x < self.width);
+        assert!(y < self.height);
+        &self.pixels[y * self.width + x]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Image {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Rgba {
+        assert!(x < self.width);
+        assert!(y < self.height);
+        &mut self.pixels[y * self.width + x]
+    }
+}
@@ -0,0 +1,93 @@
+//! A software rasterizer for Egui.
+//!
+//! Paints `PaintJobs` into an RGBA `Image` on the CPU, which can then be saved as a PNG.
+//! This is slow, but doesn't need a GPU, which makes it useful for tests and headless rendering.
+
+#![deny(warnings)]
+#![warn(clippy::all)]
+
+mod image;
+mod painter;
+pub mod snapshot;
+
+pub use {image::Image, painter::Painter};
+
+use egui::{paint::PaintJobs, Srgba, Texture};
+
+/// Paint a frame of Egui output into a new image of the given size (in physical pixels).
+pub fn paint_to_image(
+    jobs: PaintJobs,
+    texture: &Texture,
+    size_in_pixels: (usize, usize),
+    pixels_per_point: f32,
+    clear_color: Srgba,
+) -> Image {
+    let mut image = Image::filled(size_in_pixels.0, size_in_pixels.1, clear_color);
+    Painter::new().paint_jobs(&mut image, jobs, texture, pixels_per_point);
+    image
+}
+
+/// Paint a frame of Egui output and save it as a PNG.
+pub fn paint_to_png(
+    path: impl AsRef<std::path::Path>,
+    jobs: PaintJobs,
+    texture: &Texture,
+    size_in_pixels: (usize, usize),
+    pixels_per_point: f32,
+    clear_color: Srgba,
+) -> std::io::Result<()> {
+    paint_to_image(jobs, texture, size_in_pixels, pixels_per_point, clear_color).save_png(path)
+}
+
+#[test]
+fn test_paint_rect() {
+    use egui::{math::*, paint::*};
+
+    let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+    let commands = vec![
+        (
+            Rect::everything(),
+            PaintCmd::rect_filled(
+                Rect::from_min_max(pos2(2.0, 2.0), pos2(6.0, 6.0)),
+                0.0,
+                Srgba::gray(255),
+            ),
+        ),
+        (
+            // Only the left half of this survives the clip rect:
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(4.0, 10.0)),
+            PaintCmd::rect_filled(
+                Rect::from_min_max(pos2(2.0, 7.0), pos2(6.0, 9.0)),
+                0.0,
+                Srgba::from(Rgba::white_alpha(0.5)),
+            ),
+        ),
+    ];
+    let options = PaintOptions {
+        anti_alias: false,
+        ..Default::default()
+    };
+    let jobs = tessellator::tessellate_paint_commands(commands, options, &fonts);
+    let image = paint_to_image(
+        jobs,
+        &fonts.texture(),
+        (10, 10),
+        1.0,
+        Srgba::black_alpha(255),
+    );
+
+    let black = Rgba::black_alpha(1.0);
+    assert_eq!(image[(1, 1)], black);
+    assert_eq!(image[(2, 2)], Rgba::gray(1.0));
+    assert_eq!(image[(5, 5)], Rgba::gray(1.0));
+    assert_eq!(image[(6, 6)], black);
+
+    // Blending is done in linear space:
+    let half = image[(3, 8)];
+    assert!((half.r() - 0.5).abs() < 0.01, "{:?}", half);
+    assert!((half.a() - 1.0).abs() < 0.01, "{:?}", half);
+    assert_eq!(image[(4, 8)], black); // clipped
+
+    let png = image.encode_png().unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+}
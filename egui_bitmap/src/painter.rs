@@ -0,0 +1,237 @@
+use egui::{
+    math::{clamp, pos2, Pos2, Rect},
+    paint::{PaintJobs, Triangles, Vertex},
+    Rgba, Srgba, TextureId,
+};
+
+use crate::Image;
+
+/// A texture converted to linear space, ready for sampling.
+struct LinearTexture {
+    width: usize,
+    height: usize,
+    texels: Vec<Rgba>,
+}
+
+impl LinearTexture {
+    fn texel(&self, x: isize, y: isize) -> Rgba {
+        let x = clamp(x, 0..=self.width as isize - 1) as usize;
+        let y = clamp(y, 0..=self.height as isize - 1) as usize;
+        self.texels[y * self.width + x]
+    }
+
+    /// Bilinear sampling with clamp-to-edge, like `GL_LINEAR` and `GL_CLAMP_TO_EDGE`.
+    ///
+    /// `uv` is normalized: (0,0) is the top left corner of the texture, (1,1) the bottom right.
+    fn sample(&self, uv: Pos2) -> Rgba {
+        // Texel centers are at half-integer coordinates:
+        let x = uv.x * self.width as f32 - 0.5;
+        let y = uv.y * self.height as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as isize, y0 as isize);
+
+        let top = self.texel(x0, y0) * (1.0 - tx) + self.texel(x0 + 1, y0) * tx;
+        let bottom = self.texel(x0, y0 + 1) * (1.0 - tx) + self.texel(x0 + 1, y0 + 1) * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+}
+
+/// Paints Egui output into an `Image`, on the CPU.
+///
+/// This mimics what the GPU backends do:
+/// vertex colors and textures are converted from sRGB to linear space before interpolation,
+/// and blending is done in linear space with premultiplied alpha.
+#[derive(Default)]
+pub struct Painter {
+    egui_texture: Option<LinearTexture>,
+    egui_texture_version: Option<u64>,
+
+    user_textures: Vec<LinearTexture>,
+}
+
+impl Painter {
+    pub fn new() -> Painter {
+        Default::default()
+    }
+
+    pub fn new_user_texture(&mut self, size: (usize, usize), pixels: &[Srgba]) -> TextureId {
+        assert_eq!(size.0 * size.1, pixels.len());
+        let id = TextureId::User(self.user_textures.len() as u64);
+        self.user_textures.push(LinearTexture {
+            width: size.0,
+            height: size.1,
+            texels: pixels.iter().map(|&srgba| Rgba::from(srgba)).collect(),
+        });
+        id
+    }
+
+    fn upload_egui_texture(&mut self, texture: &egui::Texture) {
+        if self.egui_texture_version == Some(texture.version) {
+            return; // No change
+        }
+
+        self.egui_texture = Some(LinearTexture {
+            width: texture.width,
+            height: texture.height,
+            texels: texture
+                .pixels
+                .iter()
+                .map(|&a| Rgba::white_alpha(a as f32 / 255.0))
+                .collect(),
+        });
+        self.egui_texture_version = Some(texture.version);
+    }
+
+    fn get_texture(&self, texture_id: TextureId) -> &LinearTexture {
+        match texture_id {
+            TextureId::Egui => self.egui_texture.as_ref().unwrap(),
+            TextureId::User(id) => {
+                let id = id as usize;
+                assert!(id < self.user_textures.len());
+                &self.user_textures[id]
+            }
+        }
+    }
+
+    /// Main entry-point for painting a frame.
+    ///
+    /// The size of `target` is in physical pixels, while the paint jobs are in points.
+    pub fn paint_jobs(
+        &mut self,
+        target: &mut Image,
+        jobs: PaintJobs,
+        texture: &egui::Texture,
+        pixels_per_point: f32,
+    ) {
+        self.upload_egui_texture(texture);
+        for (clip_rect, triangles) in jobs {
+            self.paint_job(target, clip_rect, &triangles, pixels_per_point);
+        }
+    }
+
+    fn paint_job(
+        &self,
+        target: &mut Image,
+        clip_rect: Rect,
+        triangles: &Triangles,
+        pixels_per_point: f32,
+    ) {
+        debug_assert!(triangles.is_valid());
+
+        // Transform clip rect to physical pixels, rounded the same way as the GPU backends:
+        let (width, height) = (target.width as f32, target.height as f32);
+        let clip_min_x = clamp(pixels_per_point * clip_rect.min.x, 0.0..=width);
+        let clip_min_y = clamp(pixels_per_point * clip_rect.min.y, 0.0..=height);
+        let clip_max_x = clamp(pixels_per_point * clip_rect.max.x, clip_min_x..=width);
+        let clip_max_y = clamp(pixels_per_point * clip_rect.max.y, clip_min_y..=height);
+        let scissor = Scissor {
+            min_x: clip_min_x.round() as usize,
+            min_y: clip_min_y.round() as usize,
+            max_x: clip_max_x.round() as usize,
+            max_y: clip_max_y.round() as usize,
+        };
+        if scissor.min_x >= scissor.max_x || scissor.min_y >= scissor.max_y {
+            return;
+        }
+
+        let texture = self.get_texture(triangles.texture_id);
+        for indices in triangles.indices.chunks_exact(3) {
+            let vertex = |i: usize| {
+                let v: &Vertex = &triangles.vertices[indices[i] as usize];
+                RasterVertex {
+                    pos: pos2(pixels_per_point * v.pos.x, pixels_per_point * v.pos.y),
+                    uv: v.uv,
+                    color: Rgba::from(v.color),
+                }
+            };
+            rasterize_triangle(target, scissor, texture, [vertex(0), vertex(1), vertex(2)]);
+        }
+    }
+}
+
+/// In physical pixels. `max` is exclusive.
+#[derive(Clone, Copy)]
+struct Scissor {
+    min_x: usize,
+    min_y: usize,
+    max_x: usize,
+    max_y: usize,
+}
+
+struct RasterVertex {
+    /// In physical pixels.
+    pos: Pos2,
+    uv: Pos2,
+    /// Linear space, premultiplied alpha.
+    color: Rgba,
+}
+
+/// Twice the signed area of the triangle `a, b, p`.
+fn edge_function(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// When a pixel center lies exactly on an edge shared by two triangles, only one of them should cover it.
+/// The two triangles traverse the shared edge in opposite directions, and this is true for exactly one direction.
+fn owns_edge(a: Pos2, b: Pos2) -> bool {
+    let d = b - a;
+    d.y > 0.0 || (d.y == 0.0 && d.x < 0.0)
+}
+
+fn rasterize_triangle(
+    target: &mut Image,
+    scissor: Scissor,
+    texture: &LinearTexture,
+    vertices: [RasterVertex; 3],
+) {
+    let [v0, mut v1, mut v2] = vertices;
+    let mut area = edge_function(v0.pos, v1.pos, v2.pos);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    if area < 0.0 {
+        std::mem::swap(&mut v1, &mut v2);
+        area = -area;
+    }
+
+    let min_x = v0.pos.x.min(v1.pos.x).min(v2.pos.x).floor().max(0.0) as usize;
+    let min_y = v0.pos.y.min(v1.pos.y).min(v2.pos.y).floor().max(0.0) as usize;
+    let max_x = v0.pos.x.max(v1.pos.x).max(v2.pos.x).ceil().max(0.0) as usize;
+    let max_y = v0.pos.y.max(v1.pos.y).max(v2.pos.y).ceil().max(0.0) as usize;
+    let min_x = min_x.max(scissor.min_x);
+    let min_y = min_y.max(scissor.min_y);
+    let max_x = max_x.min(scissor.max_x);
+    let max_y = max_y.min(scissor.max_y);
+
+    let owns = [
+        owns_edge(v1.pos, v2.pos),
+        owns_edge(v2.pos, v0.pos),
+        owns_edge(v0.pos, v1.pos),
+    ];
+    let covers = |w: f32, owns: bool| w > 0.0 || (w == 0.0 && owns);
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+            let w0 = edge_function(v1.pos, v2.pos, p);
+            let w1 = edge_function(v2.pos, v0.pos, p);
+            let w2 = edge_function(v0.pos, v1.pos, p);
+            if !(covers(w0, owns[0]) && covers(w1, owns[1]) && covers(w2, owns[2])) {
+                continue;
+            }
+
+            let (b0, b1, b2) = (w0 / area, w1 / area, w2 / area);
+            let color = v0.color * b0 + v1.color * b1 + v2.color * b2;
+            let uv = pos2(
+                b0 * v0.uv.x + b1 * v1.uv.x + b2 * v2.uv.x,
+                b0 * v0.uv.y + b1 * v1.uv.y + b2 * v2.uv.y,
+            );
+            let src = color * texture.sample(uv);
+
+            // Premultiplied alpha:
+            let dst = &mut target[(x, y)];
+            *dst = src + *dst * (1.0 - src.a());
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Golden image tests: paint a `Ui` on the CPU and compare the result to a stored PNG.
+//!
+//! Set the environment variable `UPDATE_SNAPSHOTS=1` to (re)write the stored images instead of comparing.
+
+use std::path::{Path, PathBuf};
+
+use egui::{vec2, Context, RawInput, Srgba, Ui, Vec2};
+
+use crate::{paint_to_image, Image};
+
+/// How to paint and compare a snapshot.
+#[derive(Clone, Copy, Debug)]
+pub struct SnapshotOptions {
+    /// In points.
+    pub screen_size: Vec2,
+
+    pub pixels_per_point: f32,
+
+    /// Painted behind everything.
+    pub clear_color: Srgba,
+
+    /// How many frames to run before painting, so that e.g. windows have time to size themselves.
+    pub num_frames: usize,
+
+    /// Maximum difference in any sRGBA channel (0-255) before a pixel counts as different.
+    pub tolerance: u8,
+
+    /// How many pixels may differ before the test fails.
+    pub max_differing_pixels: usize,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            screen_size: vec2(256.0, 128.0),
+            pixels_per_point: 1.0,
+            clear_color: Srgba::black_alpha(255),
+            num_frames: 3,
+            tolerance: 2,
+            max_differing_pixels: 0,
+        }
+    }
+}
+
+/// Run `add_contents` for `options.num_frames` frames and paint the last one.
+pub fn paint_ui(options: &SnapshotOptions, mut add_contents: impl FnMut(&mut Ui)) -> Image {
+    let mut ctx = Context::new();
+    let mut jobs = Default::default();
+    for frame in 0..options.num_frames.max(1) {
+        let raw_input = RawInput {
+            screen_size: options.screen_size,
+            pixels_per_point: Some(options.pixels_per_point),
+            time: frame as f64, // Long enough for all animations to finish
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        add_contents(&mut ui);
+        jobs = ctx.end_frame().1;
+    }
+
+    let size_in_pixels = options.screen_size * options.pixels_per_point;
+    let size_in_pixels = (
+        size_in_pixels.x.round() as usize,
+        size_in_pixels.y.round() as usize,
+    );
+    paint_to_image(
+        jobs,
+        &ctx.fonts().texture(),
+        size_in_pixels,
+        options.pixels_per_point,
+        options.clear_color,
+    )
+}
+
+/// Compare `image` to the PNG at `path`.
+///
+/// On failure, the new image is written next to it as `<name>.new.png`,
+/// and an image highlighting the differing pixels in red as `<name>.diff.png`.
+pub fn check_snapshot(
+    path: impl AsRef<Path>,
+    image: &Image,
+    options: &SnapshotOptions,
+) -> Result<(), String> {
+    let path = path.as_ref();
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        return image
+            .save_png(path)
+            .map_err(|err| format!("Failed to write {}: {}", path.display(), err));
+    }
+
+    let expected = Image::load_png(path).map_err(|err| {
+        format!(
+            "Failed to load snapshot {}: {}. Run with UPDATE_SNAPSHOTS=1 to create it.",
+            path.display(),
+            err
+        )
+    })?;
+
+    let new_path = sibling_path(path, "new");
+    let diff_path = sibling_path(path, "diff");
+
+    if (expected.width, expected.height) != (image.width, image.height) {
+        image.save_png(&new_path).map_err(|err| err.to_string())?;
+        return Err(format!(
+            "Snapshot {} is {}x{}, but the new image is {}x{}. New image written to {}",
+            path.display(),
+            expected.width,
+            expected.height,
+            image.width,
+            image.height,
+            new_path.display(),
+        ));
+    }
+
+    let expected_bytes = expected.to_srgba_unmultiplied_bytes();
+    let new_bytes = image.to_srgba_unmultiplied_bytes();
+
+    let mut diff = Image::new(image.width, image.height);
+    let mut num_differing_pixels = 0;
+    for (i, (a, b)) in expected_bytes
+        .chunks_exact(4)
+        .zip(new_bytes.chunks_exact(4))
+        .enumerate()
+    {
+        let differs = a
+            .iter()
+            .zip(b)
+            .any(|(a, b)| (*a as i32 - *b as i32).abs() > options.tolerance as i32);
+        diff.pixels[i] = if differs {
+            num_differing_pixels += 1;
+            Srgba::new(255, 0, 0, 255).into()
+        } else {
+            // Faded version of the expected image, for context:
+            Srgba::gray(a[0].max(a[1]).max(a[2]) / 4).into()
+        };
+    }
+
+    if num_differing_pixels <= options.max_differing_pixels {
+        // Clean up after an earlier failure:
+        std::fs::remove_file(&new_path).ok();
+        std::fs::remove_file(&diff_path).ok();
+        Ok(())
+    } else {
+        image.save_png(&new_path).map_err(|err| err.to_string())?;
+        diff.save_png(&diff_path).map_err(|err| err.to_string())?;
+        Err(format!(
+            "{} pixels differ from snapshot {}. New image written to {}, differences to {}",
+            num_differing_pixels,
+            path.display(),
+            new_path.display(),
+            diff_path.display(),
+        ))
+    }
+}
+
+/// Paint `add_contents` and compare it to the stored snapshot at `path`, panicking if they differ.
+pub fn assert_snapshot(
+    path: impl AsRef<Path>,
+    options: &SnapshotOptions,
+    add_contents: impl FnMut(&mut Ui),
+) {
+    let image = paint_ui(options, add_contents);
+    if let Err(err) = check_snapshot(path, &image, options) {
+        panic!("{}", err);
+    }
+}
+
+/// `foo/bar.png` -> `foo/bar.<suffix>.png`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}.{}.png", stem, suffix))
+}
@@ -0,0 +1,81 @@
+//! Golden image tests of the widgets.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to update the stored images after an intended visual change.
+
+use egui::{widgets::color_picker, *};
+use egui_bitmap::snapshot::{assert_snapshot, SnapshotOptions};
+
+fn snapshot_path(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.png", name))
+}
+
+#[test]
+fn button() {
+    let options = SnapshotOptions::default();
+    assert_snapshot(snapshot_path("button"), &options, |ui| {
+        let _ = ui.button("Click me");
+        ui.add(Button::new("Disabled").enabled(false));
+    });
+}
+
+#[test]
+fn slider() {
+    let options = SnapshotOptions::default();
+    let mut value = 42.0;
+    assert_snapshot(snapshot_path("slider"), &options, |ui| {
+        ui.add(Slider::f32(&mut value, 0.0..=100.0).text("value"));
+    });
+}
+
+#[test]
+fn checkbox() {
+    let options = SnapshotOptions::default();
+    let (mut checked, mut unchecked) = (true, false);
+    assert_snapshot(snapshot_path("checkbox"), &options, |ui| {
+        ui.checkbox(&mut checked, "Checked");
+        ui.checkbox(&mut unchecked, "Unchecked");
+    });
+}
+
+#[test]
+fn window() {
+    let options = SnapshotOptions {
+        screen_size: vec2(320.0, 240.0),
+        ..Default::default()
+    };
+    assert_snapshot(snapshot_path("window"), &options, |ui| {
+        Window::new("Window")
+            .default_pos(pos2(16.0, 16.0))
+            .show(ui.ctx(), |ui| {
+                ui.label("Hello from a window!");
+                let _ = ui.button("Button");
+            });
+    });
+}
+
+#[test]
+fn color_picker() {
+    let options = SnapshotOptions {
+        screen_size: vec2(160.0, 560.0),
+        ..Default::default()
+    };
+    let mut hsva = color::Hsva::new(0.1, 0.8, 0.9, 1.0);
+    assert_snapshot(snapshot_path("color_picker"), &options, |ui| {
+        color_picker::color_picker_hsva_2d(ui, &mut hsva);
+    });
+}
+
+#[test]
+fn high_dpi() {
+    let options = SnapshotOptions {
+        pixels_per_point: 2.0,
+        ..Default::default()
+    };
+    let mut checked = true;
+    assert_snapshot(snapshot_path("high_dpi"), &options, |ui| {
+        let _ = ui.button("Click me");
+        ui.checkbox(&mut checked, "Checked");
+    });
+}
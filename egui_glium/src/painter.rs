@@ -211,11 +211,11 @@ impl Painter {
             glium::VertexBuffer::new(display, &vertices).unwrap()
         };
 
-        let indices: Vec<u32> = triangles.indices.iter().map(|idx| *idx as u32).collect();
-
+        // OpenGL supports `u32` indices, so each job is a single draw call (no need for `split_to_u16`).
         // TODO: we should probably reuse the `IndexBuffer` instead of allocating a new one each frame.
         let index_buffer =
-            glium::IndexBuffer::new(display, PrimitiveType::TrianglesList, &indices).unwrap();
+            glium::IndexBuffer::new(display, PrimitiveType::TrianglesList, &triangles.indices)
+                .unwrap();
 
         let pixels_per_point = display.gl_window().window().scale_factor() as f32;
         let (width_pixels, height_pixels) = display.get_framebuffer_dimensions();
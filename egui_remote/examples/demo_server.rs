@@ -0,0 +1,79 @@
+//! Run the Egui demo app without a window and write its frames to stdout.
+//!
+//! Pipe the output into the viewer:
+//!
+//! ```
+//! cargo run --example demo_server -p egui_remote | cargo run --example viewer -p egui_remote
+//! ```
+
+#![deny(warnings)]
+#![warn(clippy::all)]
+
+use std::time::{Duration, Instant};
+
+use egui::app::{App, Backend};
+
+/// There is no window, so there is not much a backend can do.
+struct HeadlessBackend {
+    previous_frame_time: Option<f32>,
+    next_texture_id: u64,
+}
+
+impl Backend for HeadlessBackend {
+    fn cpu_usage(&self) -> Option<f32> {
+        self.previous_frame_time
+    }
+
+    fn new_texture_srgba_premultiplied(
+        &mut self,
+        _size: (usize, usize),
+        _pixels: &[egui::Srgba],
+    ) -> egui::TextureId {
+        // User textures are not sent to the viewer (yet).
+        self.next_texture_id += 1;
+        egui::TextureId::User(self.next_texture_id - 1)
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let screen_size = egui::vec2(1280.0, 800.0);
+    let pixels_per_point = 1.0;
+
+    let mut ctx = egui::Context::new();
+    let mut app = egui::demos::DemoApp::default();
+    let mut backend = HeadlessBackend {
+        previous_frame_time: None,
+        next_texture_id: 0,
+    };
+    let mut encoder = egui_remote::FrameEncoder::new();
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+
+    let start_time = Instant::now();
+    loop {
+        let frame_start = Instant::now();
+        let raw_input = egui::RawInput {
+            screen_size,
+            pixels_per_point: Some(pixels_per_point),
+            time: start_time.elapsed().as_secs_f64(),
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        App::ui(&mut app, &mut ui, &mut backend);
+        let (output, paint_jobs) = ctx.end_frame();
+        backend.previous_frame_time = Some(frame_start.elapsed().as_secs_f32());
+
+        encoder.write_frame(
+            &mut stdout,
+            screen_size,
+            pixels_per_point,
+            &output,
+            &paint_jobs,
+            &ctx.texture(),
+        )?;
+
+        // No input arrives, so only the animations change the frame.
+        let frame_time = if output.needs_repaint { 16 } else { 1000 };
+        std::thread::sleep(Duration::from_millis(frame_time));
+    }
+}
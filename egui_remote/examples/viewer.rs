@@ -0,0 +1,77 @@
+//! Paint Egui frames read from stdin, using the glium painter.
+//!
+//! ```
+//! cargo run --example demo_server -p egui_remote | cargo run --example viewer -p egui_remote
+//! ```
+
+#![deny(warnings)]
+#![warn(clippy::all)]
+
+use glium::glutin;
+
+fn main() {
+    let event_loop = glutin::event_loop::EventLoop::<egui_remote::Frame>::with_user_event();
+    let window = glutin::window::WindowBuilder::new()
+        .with_decorations(true)
+        .with_resizable(true)
+        .with_title("Egui remote viewer")
+        .with_transparent(false);
+    let context = glutin::ContextBuilder::new()
+        .with_depth_buffer(0)
+        .with_srgb(true)
+        .with_stencil_buffer(0)
+        .with_vsync(true);
+    let display = glium::Display::new(window, context, &event_loop).unwrap();
+
+    // Read frames on a separate thread, so that the window stays responsive:
+    let proxy = event_loop.create_proxy();
+    std::thread::spawn(move || {
+        let stdin = std::io::stdin();
+        let mut stdin = stdin.lock();
+        let mut decoder = egui_remote::FrameDecoder::new();
+        loop {
+            match decoder.read_frame(&mut stdin) {
+                Ok(frame) => {
+                    if proxy.send_event(frame).is_err() {
+                        break; // The window was closed
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Stopped reading frames: {}", err);
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut painter = egui_glium::Painter::new(&display);
+    let mut latest_frame: Option<egui_remote::Frame> = None;
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = glutin::event_loop::ControlFlow::Wait;
+        match event {
+            glutin::event::Event::UserEvent(frame) => {
+                let window = display.gl_window();
+                let window = window.window();
+                let size = glutin::dpi::LogicalSize::new(frame.screen_size.x, frame.screen_size.y);
+                if window.inner_size().to_logical::<f32>(window.scale_factor()) != size {
+                    window.set_inner_size(size);
+                }
+                window.request_redraw();
+
+                egui_glium::handle_output(frame.output.clone(), &display, None);
+                latest_frame = Some(frame);
+            }
+            glutin::event::Event::RedrawRequested(_) => {
+                if let Some(frame) = &latest_frame {
+                    painter.paint_jobs(&display, frame.paint_jobs.clone(), &frame.texture);
+                }
+            }
+            glutin::event::Event::WindowEvent {
+                event: glutin::event::WindowEvent::CloseRequested,
+                ..
+            } => *control_flow = glutin::event_loop::ControlFlow::Exit,
+            _ => {}
+        }
+    });
+}
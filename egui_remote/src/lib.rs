@@ -0,0 +1,333 @@
+//! Send Egui frames from one process to another.
+//!
+//! The sending side runs Egui as usual and hands the result of `Context::end_frame` to a [`FrameEncoder`].
+//! The receiving side reads the frames with a [`FrameDecoder`] and paints them with any Egui painter.
+//!
+//! Each message is a little-endian `u32` length followed by that many bytes of
+//! [bincode](https://crates.io/crates/bincode) with variable-length integers.
+//! The font texture is only included when it has changed since the previous frame.
+
+#![deny(warnings)]
+#![warn(clippy::all)]
+
+use std::{
+    io::{self, Read, Write},
+    sync::Arc,
+};
+
+use bincode::Options as _;
+use egui::{paint::PaintJobs, Output, Texture, Vec2};
+
+/// Messages larger than this are rejected, so that a corrupt length can't make us allocate all of memory.
+pub const MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+/// Everything a viewer needs to paint one frame.
+#[derive(Clone)]
+pub struct Frame {
+    /// In points.
+    pub screen_size: Vec2,
+
+    /// The Egui texture was rasterized for this.
+    pub pixels_per_point: f32,
+
+    pub output: Output,
+
+    pub paint_jobs: PaintJobs,
+
+    /// The Egui texture. If it didn't change, this is the same `Arc` as for the previous frame.
+    pub texture: Arc<Texture>,
+}
+
+/// What is sent. The texture is only included when it changed.
+#[derive(serde::Serialize)]
+struct FrameRef<'a> {
+    screen_size: Vec2,
+    pixels_per_point: f32,
+    output: &'a Output,
+    paint_jobs: &'a PaintJobs,
+    texture: Option<&'a Texture>,
+}
+
+/// What is received. Must have the same layout as `FrameRef`.
+#[derive(serde::Deserialize)]
+struct FrameData {
+    screen_size: Vec2,
+    pixels_per_point: f32,
+    output: Output,
+    paint_jobs: PaintJobs,
+    texture: Option<Texture>,
+}
+
+fn options() -> impl bincode::Options {
+    bincode::DefaultOptions::new().with_limit(MAX_MESSAGE_SIZE as u64)
+}
+
+fn to_io_error(err: bincode::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+// ----------------------------------------------------------------------------
+
+/// Encodes frames on the sending side.
+#[derive(Default)]
+pub struct FrameEncoder {
+    /// Version of the last texture we sent.
+    texture_version: Option<u64>,
+}
+
+impl FrameEncoder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Encode a frame into a message (without the length prefix).
+    ///
+    /// The message is assumed to be delivered, so the texture is not included again until it changes.
+    /// Use [`Self::write_frame`] to only count messages that were actually written.
+    pub fn encode(
+        &mut self,
+        screen_size: Vec2,
+        pixels_per_point: f32,
+        output: &Output,
+        paint_jobs: &PaintJobs,
+        texture: &Texture,
+    ) -> Vec<u8> {
+        let message = self.message(screen_size, pixels_per_point, output, paint_jobs, texture);
+        self.texture_version = Some(texture.version);
+        message
+    }
+
+    /// Encode a frame and write it, with its length prefix, to e.g. a pipe or socket.
+    ///
+    /// If writing fails, the next frame includes the texture again.
+    pub fn write_frame(
+        &mut self,
+        writer: &mut impl Write,
+        screen_size: Vec2,
+        pixels_per_point: f32,
+        output: &Output,
+        paint_jobs: &PaintJobs,
+        texture: &Texture,
+    ) -> io::Result<()> {
+        let message = self.message(screen_size, pixels_per_point, output, paint_jobs, texture);
+        writer.write_all(&(message.len() as u32).to_le_bytes())?;
+        writer.write_all(&message)?;
+        writer.flush()?;
+        self.texture_version = Some(texture.version);
+        Ok(())
+    }
+
+    /// The texture is included if it changed since the last message that was sent.
+    fn message(
+        &self,
+        screen_size: Vec2,
+        pixels_per_point: f32,
+        output: &Output,
+        paint_jobs: &PaintJobs,
+        texture: &Texture,
+    ) -> Vec<u8> {
+        let texture_changed = self.texture_version != Some(texture.version);
+        let frame = FrameRef {
+            screen_size,
+            pixels_per_point,
+            output,
+            paint_jobs,
+            texture: if texture_changed { Some(texture) } else { None },
+        };
+        options()
+            .serialize(&frame)
+            .expect("Failed to encode Egui frame")
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Decodes frames on the receiving side.
+#[derive(Default)]
+pub struct FrameDecoder {
+    texture: Option<Arc<Texture>>,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Decode a message produced by [`FrameEncoder::encode`].
+    ///
+    /// The messages must be decoded in the same order as they were encoded,
+    /// since the texture is only sent when it changes.
+    pub fn decode(&mut self, message: &[u8]) -> io::Result<Frame> {
+        let data: FrameData = options().deserialize(message).map_err(to_io_error)?;
+        if let Some(texture) = data.texture {
+            self.texture = Some(Arc::new(texture));
+        }
+        let texture = self.texture.clone().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The first Egui frame is missing the texture",
+            )
+        })?;
+        Ok(Frame {
+            screen_size: data.screen_size,
+            pixels_per_point: data.pixels_per_point,
+            output: data.output,
+            paint_jobs: data.paint_jobs,
+            texture,
+        })
+    }
+
+    /// Read and decode the next frame written by [`FrameEncoder::write_frame`].
+    pub fn read_frame(&mut self, reader: &mut impl Read) -> io::Result<Frame> {
+        let mut length = [0_u8; 4];
+        reader.read_exact(&mut length)?;
+        let length = u32::from_le_bytes(length) as usize;
+        if length > MAX_MESSAGE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Egui frame too large: {} bytes", length),
+            ));
+        }
+        let mut message = vec![0_u8; length];
+        reader.read_exact(&mut message)?;
+        self.decode(&message)
+    }
+}
+
+#[test]
+fn test_loopback() {
+    use egui::{paint::tessellator::PaintJob, Context, RawInput};
+
+    // The vertex and index types lack `PartialEq`, so compare the parts that matter:
+    fn summary(jobs: &[PaintJob]) -> Vec<(egui::Rect, Vec<u32>, Vec<[f32; 4]>, Vec<[u8; 4]>)> {
+        jobs.iter()
+            .map(|(clip_rect, triangles)| {
+                (
+                    *clip_rect,
+                    triangles.indices.clone(),
+                    triangles
+                        .vertices
+                        .iter()
+                        .map(|v| [v.pos.x, v.pos.y, v.uv.x, v.uv.y])
+                        .collect(),
+                    triangles
+                        .vertices
+                        .iter()
+                        .map(|v| v.color.to_array())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (expected_sender, expected) = std::sync::mpsc::channel();
+
+    // Send from another thread, so that large frames can't fill up the socket and block us:
+    let sender_thread = std::thread::spawn(move || {
+        let mut sender = std::net::TcpStream::connect(address).unwrap();
+        let mut ctx = Context::new();
+        let mut encoder = FrameEncoder::new();
+        let mut demos = egui::demos::DemoWindow::default();
+
+        for frame_nr in 0..3 {
+            let raw_input = RawInput {
+                screen_size: egui::vec2(800.0, 600.0),
+                pixels_per_point: Some(1.0),
+                time: frame_nr as f64,
+                ..Default::default()
+            };
+            let mut ui = ctx.begin_frame(raw_input);
+            egui::Window::new("Demo").show(ui.ctx(), |ui| demos.ui(ui));
+            let _ = ui.button("Hello");
+            let (output, paint_jobs) = ctx.end_frame();
+            let texture = ctx.texture();
+            encoder
+                .write_frame(
+                    &mut sender,
+                    ctx.input().screen_size,
+                    1.0,
+                    &output,
+                    &paint_jobs,
+                    &texture,
+                )
+                .unwrap();
+            expected_sender
+                .send((output.needs_repaint, summary(&paint_jobs), texture))
+                .unwrap();
+        }
+
+        // An unchanged texture is not sent again:
+        let texture = ctx.texture();
+        let message = encoder.encode(Vec2::default(), 1.0, &Output::default(), &vec![], &texture);
+        assert!(message.len() < 32, "{} bytes", message.len());
+        message
+    });
+
+    let mut receiver = listener.accept().unwrap().0;
+    let mut decoder = FrameDecoder::new();
+    let mut last_texture_version = None;
+    for _ in 0..3 {
+        let frame = decoder.read_frame(&mut receiver).unwrap();
+        let (needs_repaint, paint_jobs, texture) = expected.recv().unwrap();
+        assert_eq!(frame.screen_size, egui::vec2(800.0, 600.0));
+        assert_eq!(frame.output.needs_repaint, needs_repaint);
+        assert_eq!(summary(&frame.paint_jobs), paint_jobs);
+        assert_eq!(frame.texture.version, texture.version);
+        assert_eq!(frame.texture.pixels, texture.pixels);
+        last_texture_version = Some(texture.version);
+    }
+
+    let message = sender_thread.join().unwrap();
+    let frame = decoder.decode(&message).unwrap();
+    assert!(frame.paint_jobs.is_empty());
+    assert_eq!(Some(frame.texture.version), last_texture_version);
+}
+
+#[test]
+fn test_failed_write_resends_texture() {
+    struct BrokenPipe;
+    impl Write for BrokenPipe {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let texture = Texture {
+        version: 42,
+        width: 1,
+        height: 1,
+        pixels: vec![255],
+    };
+    let mut encoder = FrameEncoder::new();
+    let (output, paint_jobs) = (Output::default(), vec![]);
+
+    let result = encoder.write_frame(
+        &mut BrokenPipe,
+        Vec2::default(),
+        1.0,
+        &output,
+        &paint_jobs,
+        &texture,
+    );
+    assert!(result.is_err());
+
+    // The texture never arrived, so it must be in the next frame:
+    let mut message = vec![];
+    encoder
+        .write_frame(
+            &mut message,
+            Vec2::default(),
+            1.0,
+            &output,
+            &paint_jobs,
+            &texture,
+        )
+        .unwrap();
+    let frame = FrameDecoder::new().read_frame(&mut &message[..]).unwrap();
+    assert_eq!(frame.texture.version, 42);
+}
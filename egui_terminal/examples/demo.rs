@@ -0,0 +1,6 @@
+//! Run the Egui demo app in the terminal. Press Ctrl+C to quit.
+
+fn main() -> std::io::Result<()> {
+    let mut app = egui::demos::DemoApp::default();
+    egui_terminal::run(&mut app)
+}
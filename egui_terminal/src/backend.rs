@@ -0,0 +1,189 @@
+use std::{
+    io::{self, Read, Write},
+    time::Instant,
+};
+
+use egui::{
+    app::{App, Backend},
+    Srgba, TextureId,
+};
+
+use crate::{Grid, InputParser};
+
+/// Alternate screen, hide cursor, report mouse clicks and drags with SGR coordinates.
+const ENTER: &str = "\x1b[?1049h\x1b[?25l\x1b[?1000h\x1b[?1002h\x1b[?1006h";
+const LEAVE: &str = "\x1b[?1006l\x1b[?1002l\x1b[?1000l\x1b[?25h\x1b[?1049l";
+
+pub struct TerminalBackend {
+    previous_frame_time: Option<f32>,
+    quit: bool,
+    next_texture_id: u64,
+}
+
+impl Backend for TerminalBackend {
+    fn cpu_usage(&self) -> Option<f32> {
+        self.previous_frame_time
+    }
+
+    fn quit(&mut self) {
+        self.quit = true;
+    }
+
+    /// Images can't be shown in a terminal, so this just hands out ids.
+    fn new_texture_srgba_premultiplied(
+        &mut self,
+        _size: (usize, usize),
+        _pixels: &[Srgba],
+    ) -> TextureId {
+        self.next_texture_id += 1;
+        TextureId::User(self.next_texture_id - 1)
+    }
+}
+
+/// Puts the terminal in raw mode, and restores it on drop.
+struct RawMode {
+    original: libc::termios,
+}
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = original;
+            libc::cfmakeraw(&mut raw);
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Self { original })
+        }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Switches to the alternate screen and turns on mouse reporting, and switches back on drop,
+/// so the terminal is usable again even if the app panics.
+struct AlternateScreen;
+
+impl AlternateScreen {
+    fn enter() -> io::Result<Self> {
+        let mut stdout = io::stdout();
+        write!(stdout, "{}", ENTER)?;
+        stdout.flush()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for AlternateScreen {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "\x1b[0m{}", LEAVE);
+        let _ = stdout.flush();
+    }
+}
+
+/// Columns and rows of the terminal.
+fn terminal_size() -> (usize, usize) {
+    unsafe {
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) == 0 && size.ws_col > 0 {
+            (size.ws_col as usize, size.ws_row as usize)
+        } else {
+            (80, 24)
+        }
+    }
+}
+
+/// Wait at most `timeout_ms` for input on stdin.
+fn poll_stdin(timeout_ms: i32) -> bool {
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut fd, 1, timeout_ms) > 0 }
+}
+
+/// Run an Egui app in the terminal until it quits or the user presses Ctrl+C.
+pub fn run(app: &mut dyn App) -> io::Result<()> {
+    let _raw_mode = RawMode::enable()?;
+    let _screen = AlternateScreen::enter()?; // Dropped first, while still in raw mode
+    run_loop(app, &mut io::stdout())
+}
+
+fn run_loop(app: &mut dyn App, stdout: &mut io::Stdout) -> io::Result<()> {
+    let mut ctx = egui::Context::new();
+    let cell_size = crate::setup(&ctx);
+    let (cols, rows) = terminal_size();
+    let mut grid = Grid::new(cols, rows, cell_size);
+    let mut parser = InputParser::new(cell_size);
+    let mut backend = TerminalBackend {
+        previous_frame_time: None,
+        quit: false,
+        next_texture_id: 0,
+    };
+
+    let start_time = Instant::now();
+    let mut raw_input = egui::RawInput {
+        pixels_per_point: Some(1.0),
+        ..Default::default()
+    };
+    let mut last_screen = String::new();
+    let mut needs_repaint = true;
+
+    loop {
+        let timeout_ms = if needs_repaint || parser.has_queued_input() {
+            16
+        } else {
+            250
+        };
+        if poll_stdin(timeout_ms) {
+            let mut buffer = [0_u8; 1024];
+            let n = io::stdin().read(&mut buffer)?;
+            parser.parse(&buffer[..n], &mut raw_input);
+            if parser.quit_requested {
+                return Ok(());
+            }
+        } else {
+            parser.timeout(&mut raw_input);
+        }
+
+        let (cols, rows) = terminal_size();
+        if (cols, rows) != (grid.cols(), grid.rows()) {
+            grid.resize(cols, rows);
+        }
+
+        let frame_start = Instant::now();
+        raw_input.screen_size = grid.screen_size();
+        raw_input.time = start_time.elapsed().as_secs_f64();
+        let mut ui = ctx.begin_frame(raw_input.take());
+        parser.next_frame(&mut raw_input);
+        app.ui(&mut ui, &mut backend);
+        let (output, commands) = ctx.end_frame_commands();
+
+        grid.clear();
+        grid.paint(&commands, ctx.fonts());
+        backend.previous_frame_time = Some(frame_start.elapsed().as_secs_f32());
+
+        if backend.quit {
+            return Ok(());
+        }
+        needs_repaint = output.needs_repaint;
+
+        let screen = grid.to_ansi_string();
+        if screen != last_screen {
+            write!(stdout, "\x1b[H{}", screen)?;
+            stdout.flush()?;
+            last_screen = screen;
+        }
+    }
+}
@@ -0,0 +1,790 @@
+use std::fmt::Write as _;
+
+use egui::{
+    math::*,
+    paint::{tessellator, Fill, Fonts, PaintCmd, PaintOptions, Rounding, Stroke, Triangles},
+    Rgba, Srgba,
+};
+
+/// One character in the terminal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    /// Color of the character. Premultiplied alpha.
+    pub fg: Srgba,
+    /// Opaque.
+    pub bg: Srgba,
+}
+
+/// A range of cells. `max` is exclusive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct CellRect {
+    min_col: i32,
+    min_row: i32,
+    max_col: i32,
+    max_row: i32,
+}
+
+impl CellRect {
+    fn intersect(self, other: CellRect) -> CellRect {
+        CellRect {
+            min_col: self.min_col.max(other.min_col),
+            min_row: self.min_row.max(other.min_row),
+            max_col: self.max_col.min(other.max_col),
+            max_row: self.max_row.min(other.max_row),
+        }
+    }
+
+    fn cols(&self) -> i32 {
+        (self.max_col - self.min_col).max(0)
+    }
+
+    fn rows(&self) -> i32 {
+        (self.max_row - self.min_row).max(0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cols() == 0 || self.rows() == 0
+    }
+
+    fn contains(&self, col: i32, row: i32) -> bool {
+        self.min_col <= col && col < self.max_col && self.min_row <= row && row < self.max_row
+    }
+}
+
+/// A grid of characters, which Egui paint commands are painted into.
+///
+/// Everything is snapped to whole cells: a cell is covered by a shape if its center is.
+#[derive(Clone, Debug)]
+pub struct Grid {
+    cols: usize,
+    rows: usize,
+    /// In points.
+    cell_size: Vec2,
+    background: Srgba,
+    /// Row by row.
+    cells: Vec<Cell>,
+}
+
+impl Grid {
+    pub fn new(cols: usize, rows: usize, cell_size: Vec2) -> Self {
+        let background = Srgba::black_alpha(255);
+        Self {
+            cols,
+            rows,
+            cell_size,
+            background,
+            cells: vec![Self::empty_cell(background); cols * rows],
+        }
+    }
+
+    fn empty_cell(background: Srgba) -> Cell {
+        Cell {
+            ch: ' ',
+            fg: Srgba::gray(255),
+            bg: background,
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Size of a cell in points.
+    pub fn cell_size(&self) -> Vec2 {
+        self.cell_size
+    }
+
+    /// Size of the whole grid in points.
+    pub fn screen_size(&self) -> Vec2 {
+        vec2(
+            self.cols as f32 * self.cell_size.x,
+            self.rows as f32 * self.cell_size.y,
+        )
+    }
+
+    pub fn cell(&self, col: usize, row: usize) -> &Cell {
+        &self.cells[row * self.cols + col]
+    }
+
+    /// Resize and clear the grid.
+    pub fn resize(&mut self, cols: usize, rows: usize) {
+        self.cols = cols;
+        self.rows = rows;
+        self.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.cells
+            .resize(self.cols * self.rows, Self::empty_cell(self.background));
+    }
+
+    /// Paint the commands of a frame (see `Context::end_frame_commands`) on top of what is already there.
+    pub fn paint(&mut self, commands: &[(Rect, PaintCmd)], fonts: &Fonts) {
+        for (clip_rect, cmd) in commands {
+            let clip = self.cells_in(*clip_rect).intersect(self.all_cells());
+            self.paint_cmd(clip, cmd, fonts);
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Coordinates:
+
+    /// The cells whose centers are inside the rectangle.
+    fn cells_in(&self, rect: Rect) -> CellRect {
+        // Float-to-int casts saturate, so infinite rectangles are fine.
+        let col = |x: f32| (x / self.cell_size.x - 0.5).ceil() as i32;
+        let row = |y: f32| (y / self.cell_size.y - 0.5).ceil() as i32;
+        CellRect {
+            min_col: col(rect.min.x),
+            min_row: row(rect.min.y),
+            max_col: col(rect.max.x),
+            max_row: row(rect.max.y),
+        }
+    }
+
+    fn all_cells(&self) -> CellRect {
+        CellRect {
+            min_col: 0,
+            min_row: 0,
+            max_col: self.cols as i32,
+            max_row: self.rows as i32,
+        }
+    }
+
+    /// The area covered by the cells, in points.
+    fn rect_of(&self, cells: CellRect) -> Rect {
+        Rect::from_min_max(
+            pos2(
+                cells.min_col as f32 * self.cell_size.x,
+                cells.min_row as f32 * self.cell_size.y,
+            ),
+            pos2(
+                cells.max_col as f32 * self.cell_size.x,
+                cells.max_row as f32 * self.cell_size.y,
+            ),
+        )
+    }
+
+    /// The cell containing the given position.
+    fn cell_at(&self, pos: Pos2) -> (i32, i32) {
+        (
+            (pos.x / self.cell_size.x).floor() as i32,
+            (pos.y / self.cell_size.y).floor() as i32,
+        )
+    }
+
+    /// The cell whose top left corner is closest to the given position.
+    /// Used for text, which is layed out from the top left corner.
+    fn snap(&self, pos: Pos2) -> (i32, i32) {
+        (
+            (pos.x / self.cell_size.x).round() as i32,
+            (pos.y / self.cell_size.y).round() as i32,
+        )
+    }
+
+    fn cell_center(&self, col: i32, row: i32) -> Pos2 {
+        pos2(
+            (col as f32 + 0.5) * self.cell_size.x,
+            (row as f32 + 0.5) * self.cell_size.y,
+        )
+    }
+
+    fn cell_mut(&mut self, clip: CellRect, col: i32, row: i32) -> Option<&mut Cell> {
+        let inside =
+            0 <= col && (col as usize) < self.cols && 0 <= row && (row as usize) < self.rows;
+        if inside && clip.contains(col, row) {
+            Some(&mut self.cells[row as usize * self.cols + col as usize])
+        } else {
+            None
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Primitives:
+
+    fn fill_cell(&mut self, clip: CellRect, col: i32, row: i32, color: Srgba) {
+        if let Some(cell) = self.cell_mut(clip, col, row) {
+            let color = Rgba::from(color);
+            cell.bg = (color + Rgba::from(cell.bg) * (1.0 - color.a())).into();
+            if color.a() >= 0.5 {
+                cell.ch = ' '; // covered
+            }
+        }
+    }
+
+    fn put_char(&mut self, clip: CellRect, col: i32, row: i32, ch: char, fg: Srgba) {
+        if let Some(cell) = self.cell_mut(clip, col, row) {
+            cell.ch = ch;
+            cell.fg = fg;
+        }
+    }
+
+    fn fill_cells(&mut self, clip: CellRect, cells: CellRect, fill: &Fill) {
+        if fill.is_transparent() {
+            return;
+        }
+        let cells = cells.intersect(clip);
+        for row in cells.min_row..cells.max_row {
+            for col in cells.min_col..cells.max_col {
+                let color = fill.color_at(self.cell_center(col, row));
+                self.fill_cell(clip, col, row, color);
+            }
+        }
+    }
+
+    /// Fill the cells whose centers are inside the convex or concave `polygon`.
+    fn fill_polygon(&mut self, clip: CellRect, polygon: &[Pos2], fill: &Fill) {
+        if fill.is_transparent() || polygon.len() < 3 {
+            return;
+        }
+        let mut bounds = Rect::nothing();
+        for &p in polygon {
+            bounds.extend_with(p);
+        }
+        let cells = self.cells_in(bounds).intersect(clip);
+        for row in cells.min_row..cells.max_row {
+            for col in cells.min_col..cells.max_col {
+                let center = self.cell_center(col, row);
+                if polygon_contains(polygon, center) {
+                    self.fill_cell(clip, col, row, fill.color_at(center));
+                }
+            }
+        }
+    }
+
+    fn line(&mut self, clip: CellRect, a: Pos2, b: Pos2, color: Srgba) {
+        if clip.is_empty() {
+            return;
+        }
+        let d = b - a;
+        if d.y.abs() < 0.5 * self.cell_size.y {
+            // Horizontal:
+            let row = self.cell_at(a + 0.5 * d).1;
+            let cols = self
+                .cells_in(Rect::from_min_max(a.min(b), a.max(b)).expand2(vec2(0.0, 1.0)))
+                .intersect(clip);
+            for col in cols.min_col..cols.max_col {
+                self.put_char(clip, col, row, '─', color);
+            }
+        } else if d.x.abs() < 0.5 * self.cell_size.x {
+            // Vertical:
+            let col = self.cell_at(a + 0.5 * d).0;
+            let rows = self
+                .cells_in(Rect::from_min_max(a.min(b), a.max(b)).expand2(vec2(1.0, 0.0)))
+                .intersect(clip);
+            for row in rows.min_row..rows.max_row {
+                self.put_char(clip, col, row, '│', color);
+            }
+        } else {
+            // Diagonal. Remember that y goes down:
+            let ch = if d.x * d.y > 0.0 { '╲' } else { '╱' };
+            // Only step through the part that can be seen:
+            let (a, b) = match clip_segment(a, b, self.rect_of(clip).expand2(self.cell_size)) {
+                Some(segment) => segment,
+                None => return,
+            };
+            let d = b - a;
+            let steps = (d.x.abs() / self.cell_size.x)
+                .max(d.y.abs() / self.cell_size.y)
+                .ceil()
+                .max(1.0) as i32; // A single point if it only touches the corner
+            for i in 0..=steps {
+                let (col, row) = self.cell_at(a + d * (i as f32 / steps as f32));
+                self.put_char(clip, col, row, ch, color);
+            }
+        }
+    }
+
+    fn polyline(&mut self, clip: CellRect, points: &[Pos2], closed: bool, stroke: &Stroke) {
+        if !is_visible(stroke) {
+            return;
+        }
+        for segment in points.windows(2) {
+            self.line(clip, segment[0], segment[1], stroke.color);
+        }
+        if closed && points.len() > 2 {
+            self.line(clip, points[points.len() - 1], points[0], stroke.color);
+        }
+    }
+
+    /// A single character for shapes that are too small to be painted with cells.
+    fn small_shape(&mut self, clip: CellRect, center: Pos2, ch: char, color: Srgba) {
+        let (col, row) = self.cell_at(center);
+        self.put_char(clip, col, row, ch, color);
+    }
+
+    fn is_small(&self, rect: Rect) -> bool {
+        rect.width() <= 1.5 * self.cell_size.x && rect.height() <= 1.5 * self.cell_size.y
+    }
+
+    // ------------------------------------------------------------------------
+    // Paint commands:
+
+    fn paint_cmd(&mut self, clip: CellRect, cmd: &PaintCmd, fonts: &Fonts) {
+        match cmd {
+            PaintCmd::Noop => {}
+            PaintCmd::Circle {
+                center,
+                radius,
+                fill,
+                stroke,
+            } => {
+                let rect = Rect::from_center_size(*center, Vec2::splat(2.0 * radius));
+                if self.is_small(rect) {
+                    // E.g. radio buttons and slider handles:
+                    if is_visible(stroke) {
+                        self.small_shape(clip, *center, '○', stroke.color);
+                    } else if !fill.is_transparent() {
+                        self.small_shape(clip, *center, '●', fill.color_at(*center));
+                    }
+                    return;
+                }
+                let points = circle_points(*center, *radius);
+                self.fill_polygon(clip, &points, fill);
+                self.polyline(clip, &points, true, stroke);
+            }
+            PaintCmd::LineSegment { points, stroke } => {
+                if is_visible(stroke) {
+                    self.line(clip, points[0], points[1], stroke.color);
+                }
+            }
+            PaintCmd::Path {
+                points,
+                closed,
+                fill,
+                stroke,
+            } => self.path(clip, points, *closed, fill, stroke),
+            PaintCmd::Rect {
+                rect,
+                rounding,
+                fill,
+                stroke,
+            } => self.rect(clip, *rect, *rounding, fill, stroke),
+            PaintCmd::Shadow { .. } => {
+                // No room for soft shadows in a character grid.
+            }
+            PaintCmd::Text {
+                pos, galley, color, ..
+            } => {
+                let mut chars = galley.text.chars();
+                for line in &galley.lines {
+                    for x_offset in line.x_offsets.iter().take(line.x_offsets.len() - 1) {
+                        let c = chars.next().unwrap();
+                        if c == '\n' {
+                            continue;
+                        }
+                        let (col, row) = self.snap(*pos + vec2(*x_offset, line.y_min));
+                        self.put_char(clip, col, row, c, *color);
+                    }
+                }
+            }
+            PaintCmd::QuadraticBezier {
+                points,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let [a, b, c] = *points;
+                let points: Vec<Pos2> = (0..=16)
+                    .map(|i| {
+                        let t = i as f32 / 16.0;
+                        let ab = lerp(a.to_vec2()..=b.to_vec2(), t);
+                        let bc = lerp(b.to_vec2()..=c.to_vec2(), t);
+                        let p = lerp(ab..=bc, t);
+                        pos2(p.x, p.y)
+                    })
+                    .collect();
+                self.path(clip, &points, *closed, fill, stroke);
+            }
+            PaintCmd::CubicBezier {
+                points,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let [a, b, c, d] = *points;
+                let points: Vec<Pos2> = (0..=16)
+                    .map(|i| {
+                        let t = i as f32 / 16.0;
+                        let s = 1.0 - t;
+                        let p = s * s * s * a.to_vec2()
+                            + 3.0 * s * s * t * b.to_vec2()
+                            + 3.0 * s * t * t * c.to_vec2()
+                            + t * t * t * d.to_vec2();
+                        pos2(p.x, p.y)
+                    })
+                    .collect();
+                self.path(clip, &points, *closed, fill, stroke);
+            }
+            PaintCmd::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                closed,
+                fill,
+                stroke,
+            } => {
+                let mut points: Vec<Pos2> = (0..=16)
+                    .map(|i| {
+                        let angle = lerp(*start_angle..=*end_angle, i as f32 / 16.0);
+                        *center + *radius * Vec2::angled(angle)
+                    })
+                    .collect();
+                if *closed {
+                    points.push(*center);
+                }
+                self.path(clip, &points, *closed, fill, stroke);
+            }
+            PaintCmd::Triangles(triangles) => self.triangles(clip, triangles),
+            PaintCmd::Transformed { .. } => {
+                // Too hard to do with characters, so just paint the tessellated fills:
+                let options = PaintOptions {
+                    anti_alias: false,
+                    ..Default::default()
+                };
+                let jobs = tessellator::tessellate_paint_commands(
+                    vec![(Rect::everything(), cmd.clone())],
+                    options,
+                    fonts,
+                );
+                for (_, triangles) in &jobs {
+                    self.triangles(clip, triangles);
+                }
+            }
+            PaintCmd::Clipped { rect, commands, .. } => {
+                let clip = clip.intersect(self.cells_in(*rect));
+                for cmd in commands {
+                    self.paint_cmd(clip, cmd, fonts);
+                }
+            }
+        }
+    }
+
+    fn path(
+        &mut self,
+        clip: CellRect,
+        points: &[Pos2],
+        closed: bool,
+        fill: &Fill,
+        stroke: &Stroke,
+    ) {
+        if points.is_empty() {
+            return;
+        }
+        let mut bounds = Rect::nothing();
+        for &p in points {
+            bounds.extend_with(p);
+        }
+
+        if self.is_small(bounds) {
+            // Icons, like the check mark of a checkbox and the arrow of a collapsing header:
+            let center = bounds.center();
+            if closed && points.len() == 3 {
+                let color = if fill.is_transparent() {
+                    stroke.color
+                } else {
+                    fill.color_at(center)
+                };
+                self.small_shape(clip, center, arrow(points), color);
+            } else if !closed && is_visible(stroke) {
+                self.small_shape(clip, center, '✓', stroke.color);
+            } else if closed && !fill.is_transparent() {
+                self.small_shape(clip, center, '■', fill.color_at(center));
+            }
+            return;
+        }
+
+        if closed {
+            self.fill_polygon(clip, points, fill);
+        }
+        self.polyline(clip, points, closed, stroke);
+    }
+
+    fn rect(
+        &mut self,
+        clip: CellRect,
+        rect: Rect,
+        rounding: Rounding,
+        fill: &Fill,
+        stroke: &Stroke,
+    ) {
+        let cells = self.cells_in(rect);
+        let stroke_visible = is_visible(stroke);
+
+        if cells.cols() == 0 && cells.rows() == 0 {
+            if !fill.is_transparent() {
+                self.small_shape(clip, rect.center(), '■', fill.color_at(rect.center()));
+            } else if stroke_visible {
+                self.small_shape(clip, rect.center(), '□', stroke.color);
+            }
+            return;
+        }
+        if cells.rows() == 0 || cells.cols() == 0 {
+            // Thin, like the rail of a slider or a separator:
+            let color = if fill.is_transparent() {
+                stroke.color
+            } else {
+                fill.color_at(rect.center())
+            };
+            if color != egui::color::TRANSPARENT {
+                let (a, b) = if cells.rows() == 0 {
+                    (rect.left_center(), rect.right_center())
+                } else {
+                    (rect.center_top(), rect.center_bottom())
+                };
+                self.line(clip, a, b, color);
+            }
+            return;
+        }
+
+        self.fill_cells(clip, cells, fill);
+        if !stroke_visible {
+            return;
+        }
+
+        let color = stroke.color;
+        let CellRect {
+            min_col,
+            min_row,
+            max_col,
+            max_row,
+        } = cells;
+        let (right, bottom) = (max_col - 1, max_row - 1);
+
+        if cells.rows() == 1 && cells.cols() == 1 {
+            self.put_char(clip, min_col, min_row, '□', color);
+        } else if cells.rows() == 1 {
+            // E.g. a button: [ text ]
+            self.put_char(clip, min_col, min_row, '[', color);
+            self.put_char(clip, right, min_row, ']', color);
+        } else if cells.cols() == 1 {
+            for row in min_row.max(clip.min_row)..max_row.min(clip.max_row) {
+                self.put_char(clip, min_col, row, '│', color);
+            }
+        } else {
+            for col in (min_col + 1).max(clip.min_col)..right.min(clip.max_col) {
+                self.put_char(clip, col, min_row, '─', color);
+                self.put_char(clip, col, bottom, '─', color);
+            }
+            for row in (min_row + 1).max(clip.min_row)..bottom.min(clip.max_row) {
+                self.put_char(clip, min_col, row, '│', color);
+                self.put_char(clip, right, row, '│', color);
+            }
+            let corner = |radius: f32, sharp: char, round: char| {
+                if radius > 0.0 {
+                    round
+                } else {
+                    sharp
+                }
+            };
+            let Rounding { nw, ne, sw, se } = rounding;
+            self.put_char(clip, min_col, min_row, corner(nw, '┌', '╭'), color);
+            self.put_char(clip, right, min_row, corner(ne, '┐', '╮'), color);
+            self.put_char(clip, min_col, bottom, corner(sw, '└', '╰'), color);
+            self.put_char(clip, right, bottom, corner(se, '┘', '╯'), color);
+        }
+    }
+
+    fn triangles(&mut self, clip: CellRect, triangles: &Triangles) {
+        for triangle in triangles.indices.chunks_exact(3) {
+            let vertices = [
+                triangles.vertices[triangle[0] as usize],
+                triangles.vertices[triangle[1] as usize],
+                triangles.vertices[triangle[2] as usize],
+            ];
+            let color = vertices
+                .iter()
+                .fold(Rgba::default(), |sum, v| sum + Rgba::from(v.color))
+                * (1.0 / 3.0);
+            let points = [vertices[0].pos, vertices[1].pos, vertices[2].pos];
+            self.fill_polygon(clip, &points, &Fill::Solid(color.into()));
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Output:
+
+    /// Just the characters, one line per row, without any colors.
+    /// Trailing spaces are removed. Useful for tests.
+    pub fn to_plain_string(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.chunks(self.cols.max(1)) {
+            let line: String = row.iter().map(|cell| cell.ch).collect();
+            out += line.trim_end();
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The grid with ANSI 24-bit color escape codes, ready to be written to a terminal
+    /// (after moving the cursor to the top left corner).
+    pub fn to_ansi_string(&self) -> String {
+        let mut out = String::new();
+        for (i, row) in self.cells.chunks(self.cols.max(1)).enumerate() {
+            if i > 0 {
+                out += "\r\n";
+            }
+            let mut current = None;
+            for cell in row {
+                let bg = Rgba::from(cell.bg);
+                let fg = Rgba::from(cell.fg);
+                let fg = Srgba::from(fg + bg * (1.0 - fg.a())); // Blend text onto the background
+                let colors = (fg, cell.bg);
+                if current != Some(colors) {
+                    let _ = write!(
+                        out,
+                        "\x1b[38;2;{};{};{};48;2;{};{};{}m",
+                        fg.r(),
+                        fg.g(),
+                        fg.b(),
+                        cell.bg.r(),
+                        cell.bg.g(),
+                        cell.bg.b()
+                    );
+                    current = Some(colors);
+                }
+                out.push(cell.ch);
+            }
+            out += "\x1b[0m";
+        }
+        out
+    }
+}
+
+fn is_visible(stroke: &Stroke) -> bool {
+    stroke.width > 0.0 && stroke.color != egui::color::TRANSPARENT
+}
+
+fn circle_points(center: Pos2, radius: f32) -> Vec<Pos2> {
+    (0..32)
+        .map(|i| center + radius * Vec2::angled(TAU * i as f32 / 32.0))
+        .collect()
+}
+
+/// The part of the line segment from `a` to `b` that is inside `rect`, if any.
+fn clip_segment(a: Pos2, b: Pos2, rect: Rect) -> Option<(Pos2, Pos2)> {
+    let d = b - a;
+    let (mut t0, mut t1) = (0.0_f32, 1.0_f32);
+    for &(p, q) in &[
+        (-d.x, a.x - rect.min.x),
+        (d.x, rect.max.x - a.x),
+        (-d.y, a.y - rect.min.y),
+        (d.y, rect.max.y - a.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None; // Parallel to, and outside of, this side
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    if t0 <= t1 {
+        Some((a + t0 * d, a + t1 * d))
+    } else {
+        None
+    }
+}
+
+/// Even-odd rule.
+fn polygon_contains(polygon: &[Pos2], p: Pos2) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// An arrow pointing the same way as the (isosceles) triangle,
+/// i.e. from the middle of its base to the opposite corner.
+fn arrow(triangle: &[Pos2]) -> char {
+    let side = |i: usize| triangle[(i + 2) % 3] - triangle[(i + 1) % 3];
+    let length = |i: usize| side(i).length();
+    // The base is the side that differs the most from the other two:
+    let oddness = |i: usize| (2.0 * length(i) - length((i + 1) % 3) - length((i + 2) % 3)).abs();
+    let base = (0..3)
+        .max_by(|&a, &b| oddness(a).partial_cmp(&oddness(b)).unwrap())
+        .unwrap();
+    let base_center = triangle[(base + 1) % 3] + 0.5 * side(base);
+    let dir = triangle[base] - base_center;
+    if dir.x.abs() > dir.y.abs() {
+        if dir.x > 0.0 {
+            '▸'
+        } else {
+            '◂'
+        }
+    } else if dir.y > 0.0 {
+        '▾'
+    } else {
+        '▴'
+    }
+}
+
+#[test]
+fn test_paint_frame() {
+    use egui::paint::FontDefinitions;
+    let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+    let cell_size = vec2(10.0, 20.0);
+    let mut grid = Grid::new(6, 4, cell_size);
+    let cmd = PaintCmd::Rect {
+        rect: Rect::from_min_max(pos2(10.0, 20.0), pos2(50.0, 80.0)),
+        rounding: Rounding::same(2.0),
+        fill: Fill::Solid(Srgba::gray(50)),
+        stroke: Stroke::new(1.0, Srgba::gray(255)),
+    };
+    grid.paint(&[(Rect::everything(), cmd)], &fonts);
+    assert_eq!(grid.to_plain_string(), "\n ╭──╮\n │  │\n ╰──╯\n");
+    assert_eq!(grid.cell(2, 2).bg, Srgba::gray(50));
+}
+
+#[test]
+fn test_paint_huge_shapes() {
+    use egui::paint::FontDefinitions;
+    let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+    let mut grid = Grid::new(6, 4, vec2(10.0, 20.0));
+    let stroke = Stroke::new(1.0, Srgba::gray(255));
+    // These would take forever if every cell they cover was visited:
+    let commands = vec![
+        PaintCmd::Rect {
+            rect: Rect::from_min_max(pos2(-1e9, -1e9), pos2(1e9, 1e9)),
+            rounding: Rounding::none(),
+            fill: Fill::Solid(Srgba::gray(50)),
+            stroke,
+        },
+        PaintCmd::circle_filled(pos2(30.0, 40.0), 1e9, Srgba::gray(60)),
+        PaintCmd::line_segment([pos2(-1e9, -2e9), pos2(1e9, 2e9)], stroke),
+    ];
+    let clip_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(40.0, 1e9));
+    let commands: Vec<_> = commands.into_iter().map(|cmd| (clip_rect, cmd)).collect();
+    grid.paint(&commands, &fonts);
+    assert_eq!(grid.to_plain_string(), "╲\n ╲\n  ╲\n   ╲\n");
+    assert_eq!(grid.cell(0, 3).bg, grid.cell(3, 0).bg);
+    assert_eq!(grid.cell(4, 0).bg, Srgba::black_alpha(255), "clipped");
+}
+
+#[test]
+fn test_paint_line_touching_corner() {
+    use egui::paint::FontDefinitions;
+    let fonts = Fonts::from_definitions(FontDefinitions::with_pixels_per_point(1.0));
+    let mut grid = Grid::new(6, 4, vec2(10.0, 20.0));
+    let stroke = Stroke::new(1.0, Srgba::gray(255));
+    // Only touches a corner of the clip rectangle, expanded by a cell:
+    let line = PaintCmd::line_segment([pos2(-20.0, -10.0), pos2(0.0, -30.0)], stroke);
+    let clip_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(60.0, 80.0));
+    grid.paint(&[(clip_rect, line)], &fonts);
+    assert_eq!(grid.to_plain_string(), "\n\n\n\n");
+}
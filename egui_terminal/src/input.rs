@@ -0,0 +1,370 @@
+use std::collections::VecDeque;
+
+use egui::{pos2, vec2, Event, Key, RawInput, Vec2};
+
+/// Turns the bytes a terminal sends on stdin (in raw mode) into Egui input.
+///
+/// Handles printable text, the common key escape sequences and SGR mouse reporting (`ESC [ < b ; x ; y M`).
+/// Escape sequences split over several reads are buffered until they are complete.
+///
+/// Call [`Self::next_frame`] after each `RawInput::take`, and [`Self::timeout`] when no input arrived for a while.
+pub struct InputParser {
+    cell_size: Vec2,
+    pending: Vec<u8>,
+    /// The mouse button was pressed or released since the last frame.
+    mouse_changed: bool,
+    /// Mouse button changes for the coming frames, e.g. the release of a click that came in the same read as the press.
+    queued_mouse_down: VecDeque<bool>,
+    /// The user pressed Ctrl+C.
+    pub quit_requested: bool,
+}
+
+impl InputParser {
+    pub fn new(cell_size: Vec2) -> Self {
+        Self {
+            cell_size,
+            pending: vec![],
+            mouse_changed: false,
+            queued_mouse_down: VecDeque::new(),
+            quit_requested: false,
+        }
+    }
+
+    pub fn parse(&mut self, bytes: &[u8], raw_input: &mut RawInput) {
+        self.pending.extend_from_slice(bytes);
+        let pending = std::mem::take(&mut self.pending);
+
+        let mut i = 0;
+        while i < pending.len() {
+            match self.parse_one(&pending[i..], raw_input) {
+                Some(consumed) => i += consumed,
+                None => break, // Incomplete
+            }
+        }
+        self.pending = pending[i..].to_vec();
+    }
+
+    /// Call after `raw_input.take()`, to apply the mouse button changes that were queued for the next frame.
+    ///
+    /// Egui only looks at whether the button is down each frame,
+    /// so a press and release in the same frame would otherwise be lost.
+    pub fn next_frame(&mut self, raw_input: &mut RawInput) {
+        self.mouse_changed = false;
+        if let Some(mouse_down) = self.queued_mouse_down.pop_front() {
+            raw_input.mouse_down = mouse_down;
+            self.mouse_changed = true;
+        }
+    }
+
+    /// Is there input waiting for [`Self::next_frame`] or [`Self::timeout`]?
+    pub fn has_queued_input(&self) -> bool {
+        !self.queued_mouse_down.is_empty() || !self.pending.is_empty()
+    }
+
+    /// Call when no bytes have arrived for a while.
+    ///
+    /// Incomplete input is kept in case the rest of it arrives with the next read.
+    /// If nothing follows, a lone ESC was the Escape key, and anything else is dropped.
+    pub fn timeout(&mut self, raw_input: &mut RawInput) {
+        if self.pending == [0x1b] {
+            key(raw_input, Key::Escape);
+        }
+        self.pending.clear();
+    }
+
+    /// Returns the number of bytes consumed, or `None` if more bytes are needed.
+    fn parse_one(&mut self, bytes: &[u8], raw_input: &mut RawInput) -> Option<usize> {
+        match bytes[0] {
+            0x03 => {
+                self.quit_requested = true;
+                Some(1)
+            }
+            b'\r' | b'\n' => {
+                key(raw_input, Key::Enter);
+                Some(1)
+            }
+            0x7f | 0x08 => {
+                key(raw_input, Key::Backspace);
+                Some(1)
+            }
+            b'\t' => {
+                key(raw_input, Key::Tab);
+                Some(1)
+            }
+            0x1b => self.parse_escape(bytes, raw_input),
+            b if b < 0x20 => Some(1), // Other control characters
+            _ => {
+                // UTF-8:
+                let len = match bytes[0] {
+                    b if b < 0x80 => 1,
+                    b if b >> 5 == 0b110 => 2,
+                    b if b >> 4 == 0b1110 => 3,
+                    b if b >> 3 == 0b11110 => 4,
+                    _ => return Some(1), // Not a lead byte: skip it and look for the next character
+                };
+                if bytes.len() < len {
+                    return None;
+                }
+                match std::str::from_utf8(&bytes[..len]) {
+                    Ok(text) => {
+                        raw_input.events.push(Event::Text(text.to_owned()));
+                        Some(len)
+                    }
+                    Err(_) => Some(1),
+                }
+            }
+        }
+    }
+
+    fn parse_escape(&mut self, bytes: &[u8], raw_input: &mut RawInput) -> Option<usize> {
+        match bytes.get(1) {
+            None => None, // The Escape key, or the rest of the sequence is in the next read. See `timeout`.
+            Some(b'[') => {
+                // CSI: parameters, then a final byte in 0x40..=0x7e
+                let end = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b))? + 2;
+                self.csi(&bytes[2..end], bytes[end], raw_input);
+                Some(end + 1)
+            }
+            Some(b'O') => {
+                // SS3, sent by some terminals for arrow keys and Home/End.
+                let last = *bytes.get(2)?;
+                self.csi(&[], last, raw_input);
+                Some(3)
+            }
+            Some(0x1b) => {
+                key(raw_input, Key::Escape);
+                Some(1)
+            }
+            Some(_) => {
+                // Alt + key. Just ignore the Alt.
+                Some(1)
+            }
+        }
+    }
+
+    fn csi(&mut self, params: &[u8], last: u8, raw_input: &mut RawInput) {
+        if params.first() == Some(&b'<') && (last == b'M' || last == b'm') {
+            self.mouse(&params[1..], last == b'M', raw_input);
+            return;
+        }
+
+        let k = match (last, params) {
+            (b'A', _) => Key::Up,
+            (b'B', _) => Key::Down,
+            (b'C', _) => Key::Right,
+            (b'D', _) => Key::Left,
+            (b'H', _) | (b'~', b"1") | (b'~', b"7") => Key::Home,
+            (b'F', _) | (b'~', b"4") | (b'~', b"8") => Key::End,
+            (b'~', b"2") => Key::Insert,
+            (b'~', b"3") => Key::Delete,
+            (b'~', b"5") => Key::PageUp,
+            (b'~', b"6") => Key::PageDown,
+            (b'Z', _) => {
+                // Shift+Tab
+                press(raw_input, Key::Shift, true);
+                key(raw_input, Key::Tab);
+                press(raw_input, Key::Shift, false);
+                return;
+            }
+            _ => return, // Unknown
+        };
+        key(raw_input, k);
+    }
+
+    /// `params` is `button;col;row`, with 1-based coordinates.
+    fn mouse(&mut self, params: &[u8], pressed: bool, raw_input: &mut RawInput) {
+        let params: Vec<u32> = std::str::from_utf8(params)
+            .unwrap_or_default()
+            .split(';')
+            .filter_map(|p| p.parse().ok())
+            .collect();
+        if params.len() != 3 {
+            return;
+        }
+        let (button, col, row) = (params[0], params[1], params[2]);
+
+        raw_input.mouse_pos = Some(pos2(
+            (col as f32 - 0.5) * self.cell_size.x,
+            (row as f32 - 0.5) * self.cell_size.y,
+        ));
+
+        let is_motion = button & 32 != 0;
+        let is_wheel = button & 64 != 0;
+        if is_wheel {
+            let lines = 3.0 * self.cell_size.y;
+            match button & 3 {
+                0 => raw_input.scroll_delta += vec2(0.0, lines),
+                1 => raw_input.scroll_delta -= vec2(0.0, lines),
+                _ => {}
+            }
+        } else if !is_motion && button & 3 == 0 {
+            // Egui only supports the primary mouse button.
+            if self.mouse_changed || !self.queued_mouse_down.is_empty() {
+                self.queued_mouse_down.push_back(pressed);
+            } else {
+                raw_input.mouse_down = pressed;
+                self.mouse_changed = true;
+            }
+        }
+    }
+}
+
+fn press(raw_input: &mut RawInput, key: Key, pressed: bool) {
+    raw_input.events.push(Event::Key { key, pressed });
+}
+
+/// Terminals don't report key releases, so we send both.
+fn key(raw_input: &mut RawInput, key: Key) {
+    press(raw_input, key, true);
+    press(raw_input, key, false);
+}
+
+#[test]
+fn test_parse_keys_and_text() {
+    let mut parser = InputParser::new(vec2(7.0, 13.0));
+    let mut raw_input = RawInput::default();
+    parser.parse("hé\x1b[A\x1b[3~\r".as_bytes(), &mut raw_input);
+    assert_eq!(
+        raw_input.events,
+        vec![
+            Event::Text("h".into()),
+            Event::Text("é".into()),
+            Event::Key {
+                key: Key::Up,
+                pressed: true
+            },
+            Event::Key {
+                key: Key::Up,
+                pressed: false
+            },
+            Event::Key {
+                key: Key::Delete,
+                pressed: true
+            },
+            Event::Key {
+                key: Key::Delete,
+                pressed: false
+            },
+            Event::Key {
+                key: Key::Enter,
+                pressed: true
+            },
+            Event::Key {
+                key: Key::Enter,
+                pressed: false
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_mouse() {
+    let mut parser = InputParser::new(vec2(7.0, 13.0));
+    let mut raw_input = RawInput::default();
+
+    // Split in the middle of the sequence:
+    parser.parse(b"\x1b[<0;3", &mut raw_input);
+    assert_eq!(raw_input.mouse_pos, None);
+    parser.parse(b";2M", &mut raw_input);
+    assert!(raw_input.mouse_down);
+    assert_eq!(raw_input.mouse_pos, Some(pos2(2.5 * 7.0, 1.5 * 13.0)));
+
+    let mut raw_input = raw_input.take();
+    parser.next_frame(&mut raw_input);
+    parser.parse(b"\x1b[<0;3;2m\x1b[<65;1;1M", &mut raw_input);
+    assert!(!raw_input.mouse_down);
+    assert_eq!(raw_input.scroll_delta, vec2(0.0, -3.0 * 13.0));
+}
+
+#[test]
+fn test_parse_click_in_one_read() {
+    let mut parser = InputParser::new(vec2(7.0, 13.0));
+    let mut raw_input = RawInput::default();
+
+    // A double-click, all in one read:
+    parser.parse(
+        b"\x1b[<0;3;2M\x1b[<0;3;2m\x1b[<0;3;2M\x1b[<0;3;2m",
+        &mut raw_input,
+    );
+    let mut frames = vec![];
+    for _ in 0..5 {
+        frames.push(raw_input.take().mouse_down);
+        parser.next_frame(&mut raw_input);
+    }
+    assert_eq!(frames, vec![true, false, true, false, false]);
+    assert!(!parser.has_queued_input());
+}
+
+#[test]
+fn test_parse_split_escape() {
+    let mut parser = InputParser::new(vec2(7.0, 13.0));
+    let mut raw_input = RawInput::default();
+
+    // An escape sequence split right after the ESC:
+    parser.parse(b"a\x1b", &mut raw_input);
+    parser.parse(b"[B", &mut raw_input);
+    assert_eq!(
+        raw_input.events,
+        vec![
+            Event::Text("a".into()),
+            Event::Key {
+                key: Key::Down,
+                pressed: true
+            },
+            Event::Key {
+                key: Key::Down,
+                pressed: false
+            },
+        ]
+    );
+
+    // The Escape key on its own:
+    raw_input.events.clear();
+    parser.parse(b"\x1b", &mut raw_input);
+    assert!(raw_input.events.is_empty());
+    parser.timeout(&mut raw_input);
+    assert_eq!(
+        raw_input.events,
+        vec![
+            Event::Key {
+                key: Key::Escape,
+                pressed: true
+            },
+            Event::Key {
+                key: Key::Escape,
+                pressed: false
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_timeout_drops_incomplete_input() {
+    for incomplete in [&b"\xc3"[..], b"\x1b[", b"\x1bO", b"\x1b[<0;5"] {
+        let mut parser = InputParser::new(vec2(7.0, 13.0));
+        let mut raw_input = RawInput::default();
+        parser.parse(incomplete, &mut raw_input);
+        assert!(parser.has_queued_input());
+        parser.timeout(&mut raw_input);
+        assert!(!parser.has_queued_input());
+        // Later input isn't stuck behind it:
+        parser.parse(b"a", &mut raw_input);
+        assert_eq!(raw_input.events, vec![Event::Text("a".into())]);
+    }
+}
+
+#[test]
+fn test_parse_invalid_utf8() {
+    let mut parser = InputParser::new(vec2(7.0, 13.0));
+    let mut raw_input = RawInput::default();
+    // A stray continuation byte, an invalid lead byte and a lead byte without its continuation:
+    parser.parse(b"\x80a\xffb\xc3c", &mut raw_input);
+    assert_eq!(
+        raw_input.events,
+        vec![
+            Event::Text("a".into()),
+            Event::Text("b".into()),
+            Event::Text("c".into()),
+        ]
+    );
+}
@@ -0,0 +1,110 @@
+//! Run Egui in a terminal.
+//!
+//! Egui is painted into a [`Grid`] of characters, which is written to the terminal with ANSI colors.
+//! Text is placed character by character, frames and rectangles become box-drawing characters,
+//! and everything is snapped to whole cells.
+//! Terminal key and mouse escape sequences are turned into `RawInput` by an [`InputParser`].
+//!
+//! For tests, [`render_to_string`] paints a `Ui` into a plain string, without any terminal.
+
+#![deny(warnings)]
+#![warn(clippy::all)]
+
+#[cfg(unix)]
+mod backend;
+mod grid;
+mod input;
+
+#[cfg(unix)]
+pub use backend::{run, TerminalBackend};
+pub use grid::{Cell, Grid};
+pub use input::InputParser;
+
+use egui::{
+    paint::{fonts::FontFamily, FontDefinitions, Fonts},
+    vec2, Context, RawInput, Style, TextStyle, Ui, Vec2,
+};
+
+/// Only monospace text fits in a character grid.
+pub fn font_definitions() -> FontDefinitions {
+    let mut definitions = FontDefinitions::with_pixels_per_point(1.0);
+    for text_style in &[
+        TextStyle::Body,
+        TextStyle::Button,
+        TextStyle::Heading,
+        TextStyle::Monospace,
+    ] {
+        definitions
+            .fonts
+            .insert(*text_style, (FontFamily::Monospace, 13.0));
+    }
+    definitions
+}
+
+/// The size of one character cell in points, with the fonts of [`font_definitions`].
+pub fn cell_size() -> Vec2 {
+    let fonts = Fonts::from_definitions(font_definitions());
+    let font = &fonts[TextStyle::Monospace];
+    vec2(font.layout_single_line("M".into()).size.x, font.height())
+}
+
+/// Spacing that makes widgets line up with the cells.
+pub fn style(cell_size: Vec2) -> Style {
+    let mut style = Style::default();
+    let spacing = &mut style.spacing;
+    spacing.item_spacing = vec2(cell_size.x, 0.0);
+    spacing.window_padding = cell_size;
+    spacing.button_padding = vec2(cell_size.x, 0.0);
+    spacing.indent = 2.0 * cell_size.x;
+    spacing.interact_size.y = cell_size.y;
+    spacing.slider_width = 20.0 * cell_size.x;
+    spacing.icon_width = cell_size.x;
+    spacing.icon_spacing = cell_size.x;
+    style
+}
+
+/// Set the fonts and style of `ctx` for use in a terminal. Returns the cell size.
+pub fn setup(ctx: &Context) -> Vec2 {
+    let cell_size = cell_size();
+    ctx.set_fonts(font_definitions());
+    ctx.set_style(style(cell_size));
+    cell_size
+}
+
+/// Paint `add_contents` into a grid of `cols` x `rows` characters and return it as plain text.
+///
+/// A few frames are run first, so that e.g. windows have time to size themselves.
+pub fn render_to_string(cols: usize, rows: usize, mut add_contents: impl FnMut(&mut Ui)) -> String {
+    let mut ctx = Context::new();
+    let cell_size = setup(&ctx);
+    let mut grid = Grid::new(cols, rows, cell_size);
+
+    let mut commands = vec![];
+    for frame in 0..3 {
+        let raw_input = RawInput {
+            screen_size: grid.screen_size(),
+            pixels_per_point: Some(1.0),
+            time: frame as f64, // Long enough for all animations to finish
+            ..Default::default()
+        };
+        let mut ui = ctx.begin_frame(raw_input);
+        add_contents(&mut ui);
+        commands = ctx.end_frame_commands().1;
+    }
+
+    grid.paint(&commands, ctx.fonts());
+    grid.to_plain_string()
+}
+
+#[test]
+fn test_render_to_string() {
+    let mut checked = true;
+    let text = render_to_string(40, 4, |ui| {
+        let _ = ui.button("Click me");
+        ui.checkbox(&mut checked, "Checked");
+    });
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].contains("[Click me]"), "{}", text);
+    assert!(lines[1].contains("Checked"), "{}", text);
+}
@@ -85,6 +85,9 @@ pub struct Painter {
     canvas: web_sys::HtmlCanvasElement,
     gl: WebGlRenderingContext,
     program: WebGlProgram,
+    /// Does the context support `u32` indices (`OES_element_index_uint`)?
+    /// If not, we need to split large meshes into pieces with `u16` indices.
+    index_u32: bool,
     index_buffer: WebGlBuffer,
     pos_buffer: WebGlBuffer,
     tc_buffer: WebGlBuffer,
@@ -111,11 +114,13 @@ impl Painter {
     pub fn debug_info(&self) -> String {
         format!(
             "Stored canvas size: {} x {}\n\
-             gl context size: {} x {}",
+             gl context size: {} x {}\n\
+             32-bit indices: {}",
             self.canvas.width(),
             self.canvas.height(),
             self.gl.drawing_buffer_width(),
             self.gl.drawing_buffer_height(),
+            self.index_u32,
         )
     }
 
@@ -127,6 +132,9 @@ impl Painter {
             .unwrap()
             .dyn_into::<WebGlRenderingContext>()?;
 
+        // Enabling the extension is enough to allow `UNSIGNED_INT` indices in `draw_elements`:
+        let index_u32 = matches!(gl.get_extension("OES_element_index_uint"), Ok(Some(_)));
+
         // --------------------------------------------------------------------
 
         let egui_texture = gl.create_texture().unwrap();
@@ -150,6 +158,7 @@ impl Painter {
             canvas,
             gl,
             program,
+            index_u32,
             index_buffer,
             pos_buffer,
             tc_buffer,
@@ -352,8 +361,12 @@ impl Painter {
                 clip_max_y - clip_min_y,
             );
 
-            for triangles in triangles.split_to_u16() {
+            if self.index_u32 {
                 self.paint_triangles(&triangles)?;
+            } else {
+                for triangles in triangles.split_to_u16() {
+                    self.paint_triangles(&triangles)?;
+                }
             }
         }
         Ok(())
@@ -361,7 +374,6 @@ impl Painter {
 
     fn paint_triangles(&self, triangles: &Triangles) -> Result<(), JsValue> {
         debug_assert!(triangles.is_valid());
-        let indices: Vec<u16> = triangles.indices.iter().map(|idx| *idx as u16).collect();
 
         let mut positions: Vec<f32> = Vec::with_capacity(2 * triangles.vertices.len());
         let mut tex_coords: Vec<f32> = Vec::with_capacity(2 * triangles.vertices.len());
@@ -384,19 +396,36 @@ impl Painter {
 
         let gl = &self.gl;
 
-        let indices_memory_buffer = wasm_bindgen::memory()
-            .dyn_into::<WebAssembly::Memory>()?
-            .buffer();
-        let indices_ptr = indices.as_ptr() as u32 / 2;
-        let indices_array = js_sys::Int16Array::new(&indices_memory_buffer)
-            .subarray(indices_ptr, indices_ptr + indices.len() as u32);
-
         gl.bind_buffer(Gl::ELEMENT_ARRAY_BUFFER, Some(&self.index_buffer));
-        gl.buffer_data_with_array_buffer_view(
-            Gl::ELEMENT_ARRAY_BUFFER,
-            &indices_array,
-            Gl::STREAM_DRAW,
-        );
+        let index_type = if self.index_u32 {
+            let indices = &triangles.indices;
+            let indices_memory_buffer = wasm_bindgen::memory()
+                .dyn_into::<WebAssembly::Memory>()?
+                .buffer();
+            let indices_ptr = indices.as_ptr() as u32 / 4;
+            let indices_array = js_sys::Uint32Array::new(&indices_memory_buffer)
+                .subarray(indices_ptr, indices_ptr + indices.len() as u32);
+            gl.buffer_data_with_array_buffer_view(
+                Gl::ELEMENT_ARRAY_BUFFER,
+                &indices_array,
+                Gl::STREAM_DRAW,
+            );
+            Gl::UNSIGNED_INT
+        } else {
+            let indices: Vec<u16> = triangles.indices.iter().map(|idx| *idx as u16).collect();
+            let indices_memory_buffer = wasm_bindgen::memory()
+                .dyn_into::<WebAssembly::Memory>()?
+                .buffer();
+            let indices_ptr = indices.as_ptr() as u32 / 2;
+            let indices_array = js_sys::Int16Array::new(&indices_memory_buffer)
+                .subarray(indices_ptr, indices_ptr + indices.len() as u32);
+            gl.buffer_data_with_array_buffer_view(
+                Gl::ELEMENT_ARRAY_BUFFER,
+                &indices_array,
+                Gl::STREAM_DRAW,
+            );
+            Gl::UNSIGNED_SHORT
+        };
 
         // --------------------------------------------------------------------
 
@@ -473,7 +502,8 @@ impl Painter {
 
         // --------------------------------------------------------------------
 
-        gl.draw_elements_with_i32(Gl::TRIANGLES, indices.len() as i32, Gl::UNSIGNED_SHORT, 0);
+        let num_indices = triangles.indices.len() as i32;
+        gl.draw_elements_with_i32(Gl::TRIANGLES, num_indices, index_type, 0);
 
         Ok(())
     }